@@ -0,0 +1,243 @@
+// Requires blueshift_escrow.so in SBF_OUT_DIR (cargo build-sbf) and
+// spl_token.so / spl_associated_token_account.so in tests/fixtures.
+// Run with: cargo bench --features bench
+
+use std::collections::HashMap;
+
+use blueshift_escrow::state::Config;
+use bytemuck::Zeroable;
+use mollusk_svm::{
+    Mollusk,
+    program::{create_program_account_loader_v3, keyed_account_for_system_program, loader_keys},
+    result::InstructionResult,
+};
+use solana_account::Account;
+use solana_address::Address;
+use solana_instruction::{AccountMeta, Instruction};
+
+const MAKE_BUDGET: u64 = 45_000;
+const TAKE_BUDGET: u64 = 60_000;
+const REFUND_BUDGET: u64 = 25_000;
+
+const SEED: u64 = 42;
+const AMOUNT: u64 = 1_000_000;
+const RECEIVE: u64 = 500_000;
+
+struct Bench {
+    mollusk: Mollusk,
+    store: HashMap<Address, Account>,
+    maker: Address,
+    taker: Address,
+    escrow: Address,
+    mint_a: Address,
+    mint_b: Address,
+    config: Address,
+}
+
+impl Bench {
+    fn new() -> Self {
+        let mut mollusk = Mollusk::new(&blueshift_escrow::ID, "blueshift_escrow");
+        mollusk.add_program(&pinocchio_token::ID, "spl_token", &loader_keys::LOADER_V3);
+        mollusk.add_program(
+            &pinocchio_associated_token_account::ID,
+            "spl_associated_token_account",
+            &loader_keys::LOADER_V3,
+        );
+
+        let maker = Address::new_from_array([1; 32]);
+        let taker = Address::new_from_array([2; 32]);
+        let mint_a = Address::new_from_array([3; 32]);
+        let mint_b = Address::new_from_array([4; 32]);
+        let (escrow, _) = Address::find_program_address(
+            &[b"escrow", maker.as_ref(), &SEED.to_le_bytes()],
+            &blueshift_escrow::ID,
+        );
+        let (config, config_bump) =
+            Address::find_program_address(&[b"config"], &blueshift_escrow::ID);
+
+        let mut bench = Self {
+            mollusk,
+            store: HashMap::new(),
+            maker,
+            taker,
+            escrow,
+            mint_a,
+            mint_b,
+            config,
+        };
+
+        let (system_program, system_account) = keyed_account_for_system_program();
+        bench.store.insert(system_program, system_account);
+        for program in [pinocchio_token::ID, pinocchio_associated_token_account::ID] {
+            bench
+                .store
+                .insert(program, create_program_account_loader_v3(&program));
+        }
+        for wallet in [maker, taker] {
+            bench.store.insert(
+                wallet,
+                Account::new(10_000_000_000, 0, &pinocchio_system::ID),
+            );
+        }
+        bench.insert_mint(mint_a);
+        bench.insert_mint(mint_b);
+        bench.insert_token_account(bench.ata(&maker, &mint_a), mint_a, maker, AMOUNT);
+        bench.insert_token_account(bench.ata(&taker, &mint_b), mint_b, taker, RECEIVE);
+        bench.store.insert(escrow, Account::default());
+        for (owner, mint) in [(escrow, mint_a), (taker, mint_a), (maker, mint_b)] {
+            bench
+                .store
+                .insert(bench.ata(&owner, &mint), Account::default());
+        }
+
+        let mut state = Config::zeroed();
+        state.set_inner(maker, maker, [config_bump]);
+        let mut account = bench.rent_exempt_account(Config::LEN, blueshift_escrow::ID);
+        account.data.copy_from_slice(bytemuck::bytes_of(&state));
+        bench.store.insert(config, account);
+        bench
+    }
+
+    fn ata(&self, owner: &Address, mint: &Address) -> Address {
+        Address::find_program_address(
+            &[owner.as_ref(), pinocchio_token::ID.as_ref(), mint.as_ref()],
+            &pinocchio_associated_token_account::ID,
+        )
+        .0
+    }
+
+    fn rent_exempt_account(&self, len: usize, owner: Address) -> Account {
+        Account::new(self.mollusk.sysvars.rent.minimum_balance(len), len, &owner)
+    }
+
+    fn insert_mint(&mut self, mint: Address) {
+        let mut account = self.rent_exempt_account(82, pinocchio_token::ID);
+        account.data[0..4].copy_from_slice(&1u32.to_le_bytes());
+        account.data[4..36].copy_from_slice(self.maker.as_ref());
+        account.data[36..44].copy_from_slice(&u64::MAX.to_le_bytes());
+        account.data[44] = 6;
+        account.data[45] = 1;
+        self.store.insert(mint, account);
+    }
+
+    fn insert_token_account(
+        &mut self,
+        address: Address,
+        mint: Address,
+        owner: Address,
+        amount: u64,
+    ) {
+        let mut account = self.rent_exempt_account(165, pinocchio_token::ID);
+        account.data[0..32].copy_from_slice(mint.as_ref());
+        account.data[32..64].copy_from_slice(owner.as_ref());
+        account.data[64..72].copy_from_slice(&amount.to_le_bytes());
+        account.data[108] = 1;
+        self.store.insert(address, account);
+    }
+
+    fn process(&mut self, accounts: Vec<AccountMeta>, data: Vec<u8>) -> InstructionResult {
+        let keyed_accounts: Vec<(Address, Account)> = accounts
+            .iter()
+            .map(|meta| (meta.pubkey, self.store[&meta.pubkey].clone()))
+            .collect();
+        let result = self.mollusk.process_instruction(
+            &Instruction {
+                program_id: blueshift_escrow::ID,
+                accounts,
+                data,
+            },
+            &keyed_accounts,
+        );
+        assert!(
+            result.program_result.is_ok(),
+            "instruction failed: {:?}",
+            result.program_result
+        );
+        self.store.extend(result.resulting_accounts.iter().cloned());
+        result
+    }
+
+    fn make(&mut self) -> u64 {
+        let mut data = vec![
+            *blueshift_escrow::Make::DISCRIMINATOR,
+            blueshift_escrow::MakeInstructionData::VERSION,
+        ];
+        data.extend_from_slice(&SEED.to_le_bytes());
+        data.extend_from_slice(&RECEIVE.to_le_bytes());
+        data.extend_from_slice(&AMOUNT.to_le_bytes());
+        data.resize(1 + blueshift_escrow::MakeInstructionData::LEN, 0);
+        let accounts = vec![
+            AccountMeta::new(self.maker, true),
+            AccountMeta::new(self.escrow, false),
+            AccountMeta::new_readonly(self.mint_a, false),
+            AccountMeta::new_readonly(self.mint_b, false),
+            AccountMeta::new(self.ata(&self.maker, &self.mint_a), false),
+            AccountMeta::new(self.ata(&self.escrow, &self.mint_a), false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new_readonly(pinocchio_token::ID, false),
+            AccountMeta::new(self.config, false),
+            AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+        ];
+        self.process(accounts, data).compute_units_consumed
+    }
+
+    fn take(&mut self) -> u64 {
+        let accounts = vec![
+            AccountMeta::new(self.taker, true),
+            AccountMeta::new(self.maker, false),
+            AccountMeta::new(self.escrow, false),
+            AccountMeta::new_readonly(self.mint_a, false),
+            AccountMeta::new_readonly(self.mint_b, false),
+            AccountMeta::new(self.ata(&self.escrow, &self.mint_a), false),
+            AccountMeta::new(self.ata(&self.taker, &self.mint_a), false),
+            AccountMeta::new(self.ata(&self.taker, &self.mint_b), false),
+            AccountMeta::new(self.ata(&self.maker, &self.mint_b), false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new_readonly(pinocchio_token::ID, false),
+            AccountMeta::new(self.config, false),
+            AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+        ];
+        self.process(accounts, vec![*blueshift_escrow::Take::DISCRIMINATOR, 0])
+            .compute_units_consumed
+    }
+
+    fn refund(&mut self) -> u64 {
+        let accounts = vec![
+            AccountMeta::new(self.maker, true),
+            AccountMeta::new(self.escrow, false),
+            AccountMeta::new_readonly(self.mint_a, false),
+            AccountMeta::new(self.ata(&self.escrow, &self.mint_a), false),
+            AccountMeta::new(self.ata(&self.maker, &self.mint_a), false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new_readonly(pinocchio_token::ID, false),
+            AccountMeta::new(self.config, false),
+            AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+        ];
+        self.process(accounts, vec![*blueshift_escrow::Refund::DISCRIMINATOR])
+            .compute_units_consumed
+    }
+}
+
+fn main() {
+    let mut bench = Bench::new();
+    let make = bench.make();
+    let take = bench.take();
+
+    let mut bench = Bench::new();
+    bench.make();
+    let refund = bench.refund();
+
+    let mut over_budget = false;
+    for (name, consumed, budget) in [
+        ("Make", make, MAKE_BUDGET),
+        ("Take", take, TAKE_BUDGET),
+        ("Refund", refund, REFUND_BUDGET),
+    ] {
+        println!("{name:<8} {consumed:>8} CU (budget {budget})");
+        over_budget |= consumed > budget;
+    }
+    if over_budget {
+        eprintln!("compute unit budget exceeded");
+        std::process::exit(1);
+    }
+}
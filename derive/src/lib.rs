@@ -0,0 +1,175 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, ExprArray, Fields, Ident, Type, parse_macro_input};
+
+#[derive(Default)]
+struct Ata {
+    authority: Option<Ident>,
+    mint: Option<Ident>,
+    token_program: Option<Ident>,
+}
+
+#[derive(Default)]
+struct Constraints {
+    signer: bool,
+    checks: Vec<Type>,
+    owned_by: Option<Type>,
+    ata: Option<Ata>,
+    seeds: Option<ExprArray>,
+}
+
+fn parse_constraints(field: &syn::Field) -> syn::Result<Constraints> {
+    let mut constraints = Constraints::default();
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("account"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("signer") {
+                constraints.signer = true;
+            } else if meta.path.is_ident("check") {
+                constraints.checks.push(meta.value()?.parse()?);
+            } else if meta.path.is_ident("owned_by") {
+                constraints.owned_by = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("ata") {
+                let mut ata = Ata::default();
+                meta.parse_nested_meta(|inner| {
+                    let value = inner.value()?.parse()?;
+                    if inner.path.is_ident("authority") {
+                        ata.authority = Some(value);
+                    } else if inner.path.is_ident("mint") {
+                        ata.mint = Some(value);
+                    } else if inner.path.is_ident("token_program") {
+                        ata.token_program = Some(value);
+                    } else {
+                        return Err(inner.error("expected `authority`, `mint` or `token_program`"));
+                    }
+                    Ok(())
+                })?;
+                constraints.ata = Some(ata);
+            } else if meta.path.is_ident("pda") {
+                meta.parse_nested_meta(|inner| {
+                    if !inner.path.is_ident("seeds") {
+                        return Err(inner.error("expected `seeds`"));
+                    }
+                    constraints.seeds = Some(inner.value()?.parse()?);
+                    Ok(())
+                })?;
+            } else {
+                return Err(meta.error("unsupported account constraint"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(constraints)
+}
+
+fn is_account_slice(ty: &Type) -> bool {
+    matches!(ty, Type::Reference(reference) if matches!(*reference.elem, Type::Slice(_)))
+}
+
+fn expand_checks(name: &Ident, constraints: &Constraints) -> syn::Result<TokenStream2> {
+    let mut checks = TokenStream2::new();
+    if constraints.signer {
+        checks.extend(quote! {
+            <crate::helpers::SignerAccount as crate::helpers::AccountCheck>::check(#name)?;
+        });
+    }
+    if let Some(program) = &constraints.owned_by {
+        checks.extend(quote! {
+            <crate::helpers::OwnedBy<#program> as crate::helpers::AccountCheck>::check(#name)?;
+        });
+    }
+    for check in &constraints.checks {
+        checks.extend(quote! {
+            <#check as crate::helpers::AccountCheck>::check(#name)?;
+        });
+    }
+    if let Some(ata) = &constraints.ata {
+        let (Some(authority), Some(mint), Some(token_program)) =
+            (&ata.authority, &ata.mint, &ata.token_program)
+        else {
+            return Err(syn::Error::new(
+                name.span(),
+                "`ata` requires `authority`, `mint` and `token_program`",
+            ));
+        };
+        checks.extend(quote! {
+            <crate::helpers::AssociatedTokenAccount as crate::helpers::AssociatedTokenAccountCheck>::check(
+                #name,
+                #authority,
+                #mint,
+                #token_program,
+            )?;
+        });
+    }
+    if let Some(seeds) = &constraints.seeds {
+        checks.extend(quote! {
+            if ::pinocchio::Address::find_program_address(&#seeds, &crate::ID)
+                .0
+                .ne(#name.address())
+            {
+                return Err(::pinocchio::error::ProgramError::InvalidSeeds);
+            }
+        });
+    }
+    Ok(checks)
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            ident.span(),
+            "`Accounts` requires a struct",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new(
+            ident.span(),
+            "`Accounts` requires named fields",
+        ));
+    };
+    let mut parse = TokenStream2::new();
+    let mut checks = TokenStream2::new();
+    let mut names = Vec::new();
+    for field in &fields.named {
+        let Some(name) = &field.ident else {
+            continue;
+        };
+        if is_account_slice(&field.ty) {
+            parse.extend(quote! { let #name = iter.as_slice(); });
+        } else {
+            parse.extend(quote! { let #name = crate::helpers::next_account(&mut iter)?; });
+            checks.extend(expand_checks(name, &parse_constraints(field)?)?);
+        }
+        names.push(name);
+    }
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let Some(lifetime) = input.generics.lifetimes().next().map(|def| &def.lifetime) else {
+        return Err(syn::Error::new(
+            ident.span(),
+            "`Accounts` requires a lifetime",
+        ));
+    };
+    Ok(quote! {
+        impl #impl_generics TryFrom<&#lifetime [::pinocchio::AccountView]> for #ident #ty_generics #where_clause {
+            type Error = ::pinocchio::error::ProgramError;
+            fn try_from(accounts: &#lifetime [::pinocchio::AccountView]) -> Result<Self, Self::Error> {
+                let mut iter = accounts.iter();
+                #parse
+                #checks
+                Ok(Self { #(#names),* })
+            }
+        }
+    })
+}
+
+#[proc_macro_derive(Accounts, attributes(account))]
+pub fn derive_accounts(input: TokenStream) -> TokenStream {
+    expand(parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,154 @@
+use blueshift_escrow::{
+    MakeInstructionData,
+    helpers::{
+        AccountCheck, EXTENSION_MINT_CLOSE_AUTHORITY, EXTENSION_NON_TRANSFERABLE,
+        EXTENSION_PERMANENT_DELEGATE, Mint2022Account, MintInterface, MintPolicy, MintPolicyCheck,
+        TOKEN_2022_PROGRAM_ID, TokenAccount2022Account, TokenAccountInterface, TokenInterface,
+        find_extension,
+    },
+    state::{Config, Escrow},
+};
+use pinocchio::{
+    AccountView, Address,
+    account::{MAX_PERMITTED_DATA_INCREASE, NOT_BORROWED, RuntimeAccount},
+};
+
+const MAX_ACCOUNTS: usize = 20;
+const DATA_LENS: [usize; 8] = [0, 82, 165, 166, 170, Config::LEN, Escrow::LEN, 512];
+
+pub struct Input<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Input<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+    pub fn byte(&mut self) -> u8 {
+        match self.bytes.split_first() {
+            Some((byte, rest)) => {
+                self.bytes = rest;
+                *byte
+            }
+            None => 0,
+        }
+    }
+    pub fn take(&mut self, len: usize) -> &'a [u8] {
+        let (head, rest) = self.bytes.split_at(len.min(self.bytes.len()));
+        self.bytes = rest;
+        head
+    }
+    pub fn rest(self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+pub struct FuzzAccount {
+    buffer: Vec<u64>,
+}
+
+impl FuzzAccount {
+    pub fn new(input: &mut Input) -> Self {
+        let flags = input.byte();
+        let address = Address::new_from_array([input.byte(); 32]);
+        let owner = match flags >> 4 {
+            0 => blueshift_escrow::ID,
+            1 => pinocchio_token::ID,
+            2 => TOKEN_2022_PROGRAM_ID.into(),
+            3 => Address::default(),
+            _ => Address::new_from_array([flags; 32]),
+        };
+        let data_len = match input.byte() {
+            selector if (selector as usize) < DATA_LENS.len() => DATA_LENS[selector as usize],
+            selector => selector as usize,
+        };
+        let mut data = input.take(data_len).to_vec();
+        data.resize(data_len, 0);
+        Self::with_data(address, owner, flags & 1 != 0, flags & 2 != 0, &data)
+    }
+
+    pub fn with_data(
+        address: Address,
+        owner: Address,
+        is_signer: bool,
+        is_writable: bool,
+        data: &[u8],
+    ) -> Self {
+        let header = size_of::<RuntimeAccount>();
+        let len = header + data.len() + MAX_PERMITTED_DATA_INCREASE;
+        let mut buffer = vec![0u64; len.div_ceil(size_of::<u64>())];
+        let raw = buffer.as_mut_ptr() as *mut RuntimeAccount;
+        unsafe {
+            raw.write(RuntimeAccount {
+                borrow_state: NOT_BORROWED,
+                is_signer: is_signer as u8,
+                is_writable: is_writable as u8,
+                executable: 0,
+                resize_delta: 0,
+                address,
+                owner,
+                lamports: 1_000_000_000,
+                data_len: data.len() as u64,
+            });
+            (raw as *mut u8)
+                .add(header)
+                .copy_from_nonoverlapping(data.as_ptr(), data.len());
+        }
+        Self { buffer }
+    }
+
+    pub fn view(&mut self) -> AccountView {
+        unsafe { AccountView::new_unchecked(self.buffer.as_mut_ptr() as *mut RuntimeAccount) }
+    }
+}
+
+pub fn make_instruction_data(data: &[u8]) {
+    if let Ok(parsed) = MakeInstructionData::try_from(data) {
+        assert_eq!(data.len(), MakeInstructionData::LEN);
+        assert_eq!(data[0], MakeInstructionData::VERSION);
+        assert!(parsed.split_bps.iter().map(|bps| *bps as u32).sum::<u32>() <= 10_000);
+        assert!(parsed.split_bps[parsed.splits()..].iter().all(|bps| *bps == 0));
+        assert!(parsed.burn_bps <= Escrow::MAX_BURN_BPS);
+        assert!(parsed.overpay_mode <= Escrow::OVERPAY_TO_MAKER);
+        assert!(parsed.max_fill_per_tx == 0 || parsed.min_fill <= parsed.max_fill_per_tx);
+        assert_ne!(parsed.amount, 0);
+        assert!(parsed.deadline_kind <= Escrow::DEADLINE_SLOT);
+    }
+}
+
+pub fn dispatch(data: &[u8]) {
+    let mut input = Input::new(data);
+    let count = input.byte() as usize % (MAX_ACCOUNTS + 1);
+    let mut accounts: Vec<FuzzAccount> = (0..count).map(|_| FuzzAccount::new(&mut input)).collect();
+    let views: Vec<AccountView> = accounts.iter_mut().map(FuzzAccount::view).collect();
+    let _ = blueshift_escrow::process_instruction(&blueshift_escrow::ID, &views, input.rest());
+}
+
+pub fn token_2022_checks(data: &[u8]) {
+    let mut input = Input::new(data);
+    let policy = input.byte();
+    let mut account = FuzzAccount::new(&mut input);
+    let view = account.view();
+
+    let _ = MintInterface::check(&view);
+    let _ = TokenAccountInterface::check(&view);
+    let _ = Mint2022Account::check(&view);
+    let _ = TokenAccount2022Account::check(&view);
+    let _ = MintPolicy::check(&view, policy);
+    let _ = TokenInterface::get_amount(&view);
+    let _ = TokenInterface::get_mint(&view);
+    let _ = TokenInterface::get_owner(&view);
+    let _ = TokenInterface::get_decimals(&view);
+
+    let bytes = view.try_borrow().unwrap();
+    for extension in [
+        EXTENSION_MINT_CLOSE_AUTHORITY,
+        EXTENSION_NON_TRANSFERABLE,
+        EXTENSION_PERMANENT_DELEGATE,
+    ] {
+        if let Some(found) = find_extension(&bytes, extension) {
+            let start = found.as_ptr() as usize - bytes.as_ptr() as usize;
+            assert!(start + found.len() <= bytes.len());
+        }
+    }
+}
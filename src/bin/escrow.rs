@@ -0,0 +1,236 @@
+use std::{collections::HashMap, io::Read, process::ExitCode, str::FromStr};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use blueshift_escrow::{
+    client::{self, MakeArgs},
+    state::Escrow,
+};
+use serde_json::{Value, json};
+use solana_address::Address;
+use solana_instruction::Instruction;
+
+const USAGE: &str = "usage: escrow <command> [--flag value ...]
+
+commands:
+  make    --maker <addr> --mint-a <addr> --mint-b <addr> --seed <u64> --receive <u64> --amount <u64>
+          [--deadline <i64>] [--deadline-kind <u8>] [--flags <u8>] [--label <text>] [--token-program <addr>]
+  take    --taker <addr> --escrow <addr> --data <base64> [--token-program <addr>]
+  refund  --escrow <addr> --data <base64> [--token-program <addr>]
+  list    [--maker <addr>] [--mint-a <addr>] [--mint-b <addr>]   (getProgramAccounts JSON on stdin)
+  decode  --data <base64>";
+
+struct Flags(HashMap<String, String>);
+
+impl Flags {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut flags = HashMap::new();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let name = arg
+                .strip_prefix("--")
+                .ok_or_else(|| format!("unexpected argument `{arg}`"))?;
+            let value = iter
+                .next()
+                .ok_or_else(|| format!("missing value for `--{name}`"))?;
+            flags.insert(name.to_string(), value.clone());
+        }
+        Ok(Self(flags))
+    }
+
+    fn get<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.0
+            .get(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("invalid value for `--{name}`: {value}"))
+            })
+            .transpose()
+    }
+
+    fn require<T: FromStr>(&self, name: &str) -> Result<T, String> {
+        self.get(name)?
+            .ok_or_else(|| format!("missing required flag `--{name}`"))
+    }
+
+    fn token_program(&self) -> Result<Address, String> {
+        Ok(self.get("token-program")?.unwrap_or(pinocchio_token::ID))
+    }
+
+    fn escrow(&self) -> Result<Escrow, String> {
+        decode_escrow(&self.require::<String>("data")?)
+    }
+}
+
+fn decode_escrow(data: &str) -> Result<Escrow, String> {
+    let bytes = STANDARD
+        .decode(data.trim())
+        .map_err(|err| format!("invalid base64 account data: {err}"))?;
+    client::decode_escrow(&bytes).ok_or_else(|| "account data is not an escrow".to_string())
+}
+
+fn instruction_json(instruction: &Instruction) -> Value {
+    json!({
+        "programId": instruction.program_id.to_string(),
+        "accounts": instruction
+            .accounts
+            .iter()
+            .map(|meta| json!({
+                "pubkey": meta.pubkey.to_string(),
+                "isSigner": meta.is_signer,
+                "isWritable": meta.is_writable,
+            }))
+            .collect::<Vec<_>>(),
+        "data": STANDARD.encode(&instruction.data),
+    })
+}
+
+fn escrow_json(address: Option<&Address>, escrow: &Escrow) -> Value {
+    let label_len = escrow
+        .label
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(escrow.label.len());
+    json!({
+        "address": address.map(Address::to_string),
+        "version": escrow.version,
+        "seed": escrow.seed,
+        "maker": escrow.maker.to_string(),
+        "mintA": escrow.mint_a.to_string(),
+        "mintB": escrow.mint_b.to_string(),
+        "receive": escrow.receive,
+        "amount": escrow.amount,
+        "fills": escrow.fills,
+        "deadline": escrow.deadline,
+        "deadlineKind": escrow.deadline_kind,
+        "flags": escrow.flags,
+        "frozen": escrow.is_frozen(),
+        "vault": escrow.vault.to_string(),
+        "label": String::from_utf8_lossy(&escrow.label[..label_len]),
+    })
+}
+
+fn make(flags: &Flags) -> Result<Value, String> {
+    let mut label = [0u8; 32];
+    if let Some(text) = flags.get::<String>("label")? {
+        let bytes = text.as_bytes();
+        if bytes.len() > label.len() {
+            return Err("label is longer than 32 bytes".to_string());
+        }
+        label[..bytes.len()].copy_from_slice(bytes);
+    }
+    let args = MakeArgs {
+        seed: flags.require("seed")?,
+        receive: flags.require("receive")?,
+        amount: flags.require("amount")?,
+        deadline: flags.get("deadline")?.unwrap_or(0),
+        vesting_cliff: 0,
+        vesting_duration: 0,
+        flags: flags.get("flags")?.unwrap_or(0),
+        label,
+        deadline_kind: flags
+            .get("deadline-kind")?
+            .unwrap_or(Escrow::DEADLINE_UNIX_TIMESTAMP),
+        ..MakeArgs::default()
+    };
+    let maker = flags.require("maker")?;
+    let instruction = client::make(
+        &maker,
+        &flags.require("mint-a")?,
+        &flags.require("mint-b")?,
+        &flags.token_program()?,
+        &args,
+    );
+    let mut output = instruction_json(&instruction);
+    output["escrow"] = json!(client::escrow_address(&maker, args.seed).to_string());
+    Ok(output)
+}
+
+fn take(flags: &Flags) -> Result<Value, String> {
+    Ok(instruction_json(&client::take_with_terms(
+        &flags.require("taker")?,
+        &flags.require("escrow")?,
+        &flags.escrow()?,
+        &flags.token_program()?,
+    )))
+}
+
+fn refund(flags: &Flags) -> Result<Value, String> {
+    Ok(instruction_json(&client::refund(
+        &flags.require("escrow")?,
+        &flags.escrow()?,
+        &flags.token_program()?,
+    )))
+}
+
+fn list(flags: &Flags) -> Result<Value, String> {
+    let maker: Option<Address> = flags.get("maker")?;
+    let mint_a: Option<Address> = flags.get("mint-a")?;
+    let mint_b: Option<Address> = flags.get("mint-b")?;
+
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|err| format!("failed to read stdin: {err}"))?;
+    let response: Value =
+        serde_json::from_str(&input).map_err(|err| format!("invalid JSON on stdin: {err}"))?;
+    let accounts = response
+        .get("result")
+        .unwrap_or(&response)
+        .as_array()
+        .ok_or("expected a getProgramAccounts result array")?;
+
+    let mut escrows = Vec::new();
+    for account in accounts {
+        let Some(pubkey) = account["pubkey"].as_str() else {
+            continue;
+        };
+        let Some(data) = account["account"]["data"][0].as_str() else {
+            continue;
+        };
+        let Ok(escrow) = decode_escrow(data) else {
+            continue;
+        };
+        if maker.is_some_and(|maker| maker != escrow.maker)
+            || mint_a.is_some_and(|mint| mint != escrow.mint_a)
+            || mint_b.is_some_and(|mint| mint != escrow.mint_b)
+        {
+            continue;
+        }
+        let address =
+            Address::from_str(pubkey).map_err(|_| format!("invalid account pubkey: {pubkey}"))?;
+        escrows.push(escrow_json(Some(&address), &escrow));
+    }
+    Ok(Value::Array(escrows))
+}
+
+fn decode(flags: &Flags) -> Result<Value, String> {
+    Ok(escrow_json(None, &flags.escrow()?))
+}
+
+fn run(args: &[String]) -> Result<Value, String> {
+    let (command, rest) = args.split_first().ok_or(USAGE)?;
+    let flags = Flags::parse(rest)?;
+    match command.as_str() {
+        "make" => make(&flags),
+        "take" => take(&flags),
+        "refund" => refund(&flags),
+        "list" => list(&flags),
+        "decode" => decode(&flags),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(output) => {
+            println!("{}", serde_json::to_string_pretty(&output).unwrap());
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
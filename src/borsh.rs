@@ -0,0 +1,65 @@
+use pinocchio::{AccountView, ProgramResult, error::ProgramError};
+
+use crate::{Make, Refund, Take, TakeInstructionData};
+
+pub const MAKE_DISCRIMINATOR: [u8; 8] = [138, 227, 232, 77, 223, 166, 96, 197];
+pub const TAKE_DISCRIMINATOR: [u8; 8] = [149, 226, 52, 104, 6, 142, 230, 39];
+pub const REFUND_DISCRIMINATOR: [u8; 8] = [2, 96, 183, 251, 63, 208, 46, 46];
+
+const TAKE_DATA_LEN: usize = 1 + size_of::<u64>() * 2;
+
+fn option_u64(data: &[u8]) -> Result<(Option<u64>, &[u8]), ProgramError> {
+    match data.split_first() {
+        Some((0, rest)) => Ok((None, rest)),
+        Some((1, rest)) => {
+            let (value, rest) = rest
+                .split_first_chunk::<8>()
+                .ok_or(ProgramError::InvalidInstructionData)?;
+            Ok((Some(u64::from_le_bytes(*value)), rest))
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn take_data(data: &[u8], buffer: &mut [u8; TAKE_DATA_LEN]) -> Result<usize, ProgramError> {
+    let (fill, rest) = option_u64(data)?;
+    let (pay, rest) = option_u64(rest)?;
+    if !rest.is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let mut len = 1;
+    for (flag, value) in [
+        (TakeInstructionData::PARTIAL, fill),
+        (TakeInstructionData::PAY, pay),
+    ] {
+        if let Some(value) = value {
+            buffer[0] |= flag;
+            buffer
+                .get_mut(len..len + 8)
+                .ok_or(ProgramError::InvalidInstructionData)?
+                .copy_from_slice(&value.to_le_bytes());
+            len += 8;
+        }
+    }
+    Ok(len)
+}
+
+pub fn process_instruction(
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> Option<ProgramResult> {
+    let (discriminator, data) = instruction_data.split_first_chunk::<8>()?;
+    Some(match *discriminator {
+        MAKE_DISCRIMINATOR => Make::try_from((data, accounts)).and_then(|mut make| make.process()),
+        TAKE_DISCRIMINATOR => {
+            let mut buffer = [0u8; TAKE_DATA_LEN];
+            take_data(data, &mut buffer).and_then(|len| {
+                Take::try_from((&buffer[..len], accounts)).and_then(|mut take| take.process())
+            })
+        }
+        REFUND_DISCRIMINATOR if data.is_empty() => {
+            Refund::try_from((data, accounts)).and_then(|mut refund| refund.process())
+        }
+        _ => return None,
+    })
+}
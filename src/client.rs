@@ -0,0 +1,844 @@
+use solana_address::Address;
+use solana_instruction::{AccountMeta, Instruction};
+use std::vec::Vec;
+
+pub mod compute_budget;
+#[cfg(feature = "alt")]
+pub mod lookup_table;
+#[cfg(feature = "offline")]
+pub mod offline;
+#[cfg(feature = "rpc")]
+pub mod rpc;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+use crate::{helpers::EscrowSeeds, state::Escrow};
+
+#[derive(Default)]
+pub struct MakeArgs {
+    pub seed: u64,
+    pub receive: u64,
+    pub amount: u64,
+    pub deadline: i64,
+    pub vesting_cliff: i64,
+    pub vesting_duration: i64,
+    pub flags: u8,
+    pub label: [u8; 32],
+    pub deadline_kind: u8,
+    pub min_fill: u64,
+    pub max_fill_per_tx: u64,
+    pub cooldown_slots: u64,
+    pub overpay_mode: u8,
+    pub receive_in_ui: bool,
+    pub burn_bps: u16,
+    pub split_bps: [u16; 4],
+    pub category: u16,
+    pub tag_flags: u16,
+    pub price_mode: u8,
+    pub min_price: u64,
+    pub max_price: u64,
+    pub account_flags: u8,
+}
+
+pub fn config_address() -> Address {
+    Address::find_program_address(&[b"config"], &crate::ID).0
+}
+
+pub fn escrow_address(maker: &Address, seed: u64) -> Address {
+    Address::find_program_address(
+        &[EscrowSeeds::PREFIX, maker.as_ref(), &seed.to_le_bytes()],
+        &crate::ID,
+    )
+    .0
+}
+
+pub fn nonce_escrow_address(maker: &Address, nonce: u64) -> Address {
+    Address::find_program_address(
+        &[
+            EscrowSeeds::NONCE_PREFIX,
+            maker.as_ref(),
+            &nonce.to_le_bytes(),
+        ],
+        &crate::ID,
+    )
+    .0
+}
+
+pub fn maker_nonce_address(maker: &Address) -> Address {
+    Address::find_program_address(
+        &[crate::state::MakerNonce::PREFIX, maker.as_ref()],
+        &crate::ID,
+    )
+    .0
+}
+
+pub fn order_nonce_address(maker: &Address) -> Address {
+    Address::find_program_address(
+        &[crate::state::OrderNonce::PREFIX, maker.as_ref()],
+        &crate::ID,
+    )
+    .0
+}
+
+pub fn proceeds_split_address(escrow: &Address) -> Address {
+    Address::find_program_address(
+        &[crate::state::ProceedsSplit::PREFIX, escrow.as_ref()],
+        &crate::ID,
+    )
+    .0
+}
+
+pub fn escrow_close_accounts(escrow_address: &Address, escrow: &Escrow) -> Vec<AccountMeta> {
+    let mut accounts = Vec::new();
+    if escrow.is_indexed() {
+        accounts.push(AccountMeta::new(
+            maker_nonce_address(&escrow.creator),
+            false,
+        ));
+    }
+    if escrow.has_split() {
+        accounts.push(AccountMeta::new(
+            proceeds_split_address(escrow_address),
+            false,
+        ));
+    }
+    if escrow.is_release_gated() {
+        accounts.push(AccountMeta::new(
+            release_approvals_address(escrow_address),
+            false,
+        ));
+    }
+    accounts
+}
+
+pub fn delegate_address() -> Address {
+    Address::find_program_address(&[crate::TakeSigned::DELEGATE_SEED], &crate::ID).0
+}
+
+pub fn take_signed_message(
+    escrow_address: &Address,
+    escrow: &Escrow,
+    amount: u64,
+    receive: u64,
+) -> [u8; 56] {
+    crate::TakeSigned::message(escrow_address, escrow.last_update_seq, amount, receive)
+}
+
+pub fn event_authority_address() -> Address {
+    Address::find_program_address(&[crate::EmitEvent::AUTHORITY_SEED], &crate::ID).0
+}
+
+pub fn with_event_cpi(mut instruction: Instruction) -> Instruction {
+    instruction.accounts.extend([
+        AccountMeta::new_readonly(event_authority_address(), false),
+        AccountMeta::new_readonly(crate::ID, false),
+    ]);
+    instruction
+}
+
+pub fn associated_token_address(
+    owner: &Address,
+    mint: &Address,
+    token_program: &Address,
+) -> Address {
+    Address::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &pinocchio_associated_token_account::ID,
+    )
+    .0
+}
+
+pub fn decode_escrow(data: &[u8]) -> Option<Escrow> {
+    Escrow::load(data)
+        .ok()
+        .filter(|escrow| escrow.discriminator == Escrow::DISCRIMINATOR)
+        .copied()
+}
+
+pub fn make(
+    maker: &Address,
+    mint_a: &Address,
+    mint_b: &Address,
+    token_program: &Address,
+    args: &MakeArgs,
+) -> Instruction {
+    let escrow = escrow_address(maker, args.seed);
+    let mut data = Vec::with_capacity(1 + crate::MakeInstructionData::LEN);
+    data.push(*crate::Make::DISCRIMINATOR);
+    data.push(crate::MakeInstructionData::VERSION);
+    data.extend_from_slice(&args.seed.to_le_bytes());
+    data.extend_from_slice(&args.receive.to_le_bytes());
+    data.extend_from_slice(&args.amount.to_le_bytes());
+    data.extend_from_slice(&args.deadline.to_le_bytes());
+    data.extend_from_slice(&args.vesting_cliff.to_le_bytes());
+    data.extend_from_slice(&args.vesting_duration.to_le_bytes());
+    data.push(args.flags);
+    data.extend_from_slice(&args.label);
+    data.push(args.deadline_kind);
+    data.extend_from_slice(&args.min_fill.to_le_bytes());
+    data.extend_from_slice(&args.max_fill_per_tx.to_le_bytes());
+    data.extend_from_slice(&args.cooldown_slots.to_le_bytes());
+    data.push(args.overpay_mode);
+    data.push(args.receive_in_ui as u8);
+    data.extend_from_slice(&args.burn_bps.to_le_bytes());
+    for bps in args.split_bps {
+        data.extend_from_slice(&bps.to_le_bytes());
+    }
+    data.extend_from_slice(&args.category.to_le_bytes());
+    data.extend_from_slice(&args.tag_flags.to_le_bytes());
+    data.push(args.price_mode);
+    data.extend_from_slice(&args.min_price.to_le_bytes());
+    data.extend_from_slice(&args.max_price.to_le_bytes());
+    data.push(args.account_flags);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: std::vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new_readonly(*mint_a, false),
+            AccountMeta::new_readonly(*mint_b, false),
+            AccountMeta::new(
+                associated_token_address(maker, mint_a, token_program),
+                false
+            ),
+            AccountMeta::new(
+                associated_token_address(&escrow, mint_a, token_program),
+                false
+            ),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new(config_address(), false),
+            AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+        ],
+        data,
+    }
+}
+
+pub fn make_many(
+    maker: &Address,
+    mint_a: &Address,
+    mint_b: &Address,
+    token_program: &Address,
+    offers: &[(u64, u64, u64)],
+) -> Instruction {
+    let mut data = Vec::with_capacity(1 + offers.len() * crate::MakeManyInstructionData::OFFER_LEN);
+    data.push(*crate::MakeMany::DISCRIMINATOR);
+    for (seed, receive, amount) in offers {
+        data.extend_from_slice(&seed.to_le_bytes());
+        data.extend_from_slice(&receive.to_le_bytes());
+        data.extend_from_slice(&amount.to_le_bytes());
+    }
+    let vault = |seed: u64| {
+        let escrow = escrow_address(maker, seed);
+        (
+            escrow,
+            associated_token_address(&escrow, mint_a, token_program),
+        )
+    };
+    let (escrow, escrow_vault) = vault(offers.first().map_or(0, |offer| offer.0));
+    let mut accounts = std::vec![
+        AccountMeta::new(*maker, true),
+        AccountMeta::new(escrow, false),
+        AccountMeta::new_readonly(*mint_a, false),
+        AccountMeta::new_readonly(*mint_b, false),
+        AccountMeta::new(
+            associated_token_address(maker, mint_a, token_program),
+            false
+        ),
+        AccountMeta::new(escrow_vault, false),
+        AccountMeta::new_readonly(pinocchio_system::ID, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new(config_address(), false),
+        AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+    ];
+    for (seed, _, _) in offers.iter().skip(1) {
+        let (escrow, escrow_vault) = vault(*seed);
+        accounts.push(AccountMeta::new(escrow, false));
+        accounts.push(AccountMeta::new(escrow_vault, false));
+    }
+
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data,
+    }
+}
+
+pub fn take(
+    taker: &Address,
+    escrow_address: &Address,
+    escrow: &Escrow,
+    token_program: &Address,
+) -> Instruction {
+    let maker_ata_b = if escrow.has_proceeds() {
+        escrow.proceeds
+    } else {
+        associated_token_address(&escrow.maker, &escrow.mint_b, token_program)
+    };
+    Instruction {
+        program_id: crate::ID,
+        accounts: std::vec![
+            AccountMeta::new(*taker, true),
+            AccountMeta::new(escrow.maker, false),
+            AccountMeta::new(*escrow_address, false),
+            AccountMeta::new_readonly(escrow.mint_a, false),
+            AccountMeta::new_readonly(escrow.mint_b, false),
+            AccountMeta::new(escrow.vault, false),
+            AccountMeta::new(
+                associated_token_address(taker, &escrow.mint_a, token_program),
+                false
+            ),
+            AccountMeta::new(
+                associated_token_address(taker, &escrow.mint_b, token_program),
+                false
+            ),
+            AccountMeta::new(maker_ata_b, false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new(config_address(), false),
+            AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+        ],
+        data: std::vec![*crate::Take::DISCRIMINATOR],
+    }
+}
+
+pub fn take_with_terms(
+    taker: &Address,
+    escrow_address: &Address,
+    escrow: &Escrow,
+    token_program: &Address,
+) -> Instruction {
+    let mut instruction = take(taker, escrow_address, escrow, token_program);
+    instruction.data.push(0);
+    instruction.data.extend_from_slice(&escrow.terms_hash());
+    instruction
+}
+
+pub fn fill_signed_order(
+    taker: &Address,
+    order: &crate::FillSignedOrderInstructionData,
+    token_program: &Address,
+) -> Instruction {
+    let mut data = std::vec![*crate::FillSignedOrder::DISCRIMINATOR];
+    data.extend_from_slice(&order.message());
+    Instruction {
+        program_id: crate::ID,
+        accounts: std::vec![
+            AccountMeta::new(*taker, true),
+            AccountMeta::new_readonly(order.maker, false),
+            AccountMeta::new_readonly(order.mint_a, false),
+            AccountMeta::new_readonly(order.mint_b, false),
+            AccountMeta::new(
+                associated_token_address(&order.maker, &order.mint_a, token_program),
+                false
+            ),
+            AccountMeta::new(
+                associated_token_address(taker, &order.mint_a, token_program),
+                false
+            ),
+            AccountMeta::new(
+                associated_token_address(taker, &order.mint_b, token_program),
+                false
+            ),
+            AccountMeta::new(
+                associated_token_address(&order.maker, &order.mint_b, token_program),
+                false
+            ),
+            AccountMeta::new(order_nonce_address(&order.maker), false),
+            AccountMeta::new_readonly(delegate_address(), false),
+            AccountMeta::new_readonly(pinocchio::sysvars::instructions::INSTRUCTIONS_ID, false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new(config_address(), false),
+            AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+        ],
+        data,
+    }
+}
+
+pub fn cancel_order_nonce(maker: &Address, nonce: u64) -> Instruction {
+    let mut data = std::vec![*crate::CancelOrderNonce::DISCRIMINATOR];
+    data.extend_from_slice(&nonce.to_le_bytes());
+    Instruction {
+        program_id: crate::ID,
+        accounts: std::vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new(order_nonce_address(maker), false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+        ],
+        data,
+    }
+}
+
+pub fn refund(escrow_address: &Address, escrow: &Escrow, token_program: &Address) -> Instruction {
+    let mut accounts = std::vec![
+        AccountMeta::new(escrow.maker, true),
+        AccountMeta::new(*escrow_address, false),
+        AccountMeta::new_readonly(escrow.mint_a, false),
+        AccountMeta::new(escrow.vault, false),
+        AccountMeta::new(
+            associated_token_address(&escrow.maker, &escrow.mint_a, token_program),
+            false
+        ),
+        AccountMeta::new_readonly(pinocchio_system::ID, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new(config_address(), false),
+        AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+    ];
+    accounts.extend(escrow_close_accounts(escrow_address, escrow));
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: std::vec![*crate::Refund::DISCRIMINATOR],
+    }
+}
+
+pub fn stake_escrow_address(stake: &Address) -> Address {
+    Address::find_program_address(
+        &[crate::state::StakeEscrow::PREFIX, stake.as_ref()],
+        &crate::ID,
+    )
+    .0
+}
+
+pub fn make_stake(maker: &Address, stake: &Address, mint_b: &Address, receive: u64) -> Instruction {
+    let mut data = std::vec![*crate::MakeStake::DISCRIMINATOR];
+    data.extend_from_slice(&receive.to_le_bytes());
+    Instruction {
+        program_id: crate::ID,
+        accounts: std::vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new(stake_escrow_address(stake), false),
+            AccountMeta::new(*stake, false),
+            AccountMeta::new_readonly(*mint_b, false),
+            AccountMeta::new_readonly(pinocchio::sysvars::clock::CLOCK_ID, false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new_readonly(crate::helpers::STAKE_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+pub fn take_stake(
+    taker: &Address,
+    stake_escrow: &crate::state::StakeEscrow,
+    token_program: &Address,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: std::vec![
+            AccountMeta::new(*taker, true),
+            AccountMeta::new(stake_escrow.maker, false),
+            AccountMeta::new(stake_escrow_address(&stake_escrow.stake), false),
+            AccountMeta::new(stake_escrow.stake, false),
+            AccountMeta::new_readonly(stake_escrow.mint_b, false),
+            AccountMeta::new(
+                associated_token_address(taker, &stake_escrow.mint_b, token_program),
+                false
+            ),
+            AccountMeta::new(
+                associated_token_address(&stake_escrow.maker, &stake_escrow.mint_b, token_program),
+                false
+            ),
+            AccountMeta::new_readonly(pinocchio::sysvars::clock::CLOCK_ID, false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+            AccountMeta::new_readonly(crate::helpers::STAKE_PROGRAM_ID, false),
+        ],
+        data: std::vec![*crate::TakeStake::DISCRIMINATOR],
+    }
+}
+
+pub fn refund_stake(maker: &Address, stake: &Address) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: std::vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new(stake_escrow_address(stake), false),
+            AccountMeta::new(*stake, false),
+            AccountMeta::new_readonly(pinocchio::sysvars::clock::CLOCK_ID, false),
+            AccountMeta::new_readonly(crate::helpers::STAKE_PROGRAM_ID, false),
+        ],
+        data: std::vec![*crate::RefundStake::DISCRIMINATOR],
+    }
+}
+
+#[cfg(feature = "wormhole")]
+pub fn wormhole_accounts(seq: u64) -> Vec<AccountMeta> {
+    use crate::helpers::{WORMHOLE_PROGRAM_ID, WormholeAccounts};
+
+    let wormhole = |seeds: &[&[u8]]| Address::find_program_address(seeds, &WORMHOLE_PROGRAM_ID).0;
+    let emitter = Address::find_program_address(&[WormholeAccounts::EMITTER_SEED], &crate::ID).0;
+    let message = Address::find_program_address(
+        &[WormholeAccounts::MESSAGE_SEED, &seq.to_le_bytes()],
+        &crate::ID,
+    )
+    .0;
+    std::vec![
+        AccountMeta::new_readonly(WORMHOLE_PROGRAM_ID, false),
+        AccountMeta::new(wormhole(&[b"Bridge"]), false),
+        AccountMeta::new(message, false),
+        AccountMeta::new_readonly(emitter, false),
+        AccountMeta::new(wormhole(&[b"Sequence", emitter.as_ref()]), false),
+        AccountMeta::new(wormhole(&[b"fee_collector"]), false),
+        AccountMeta::new_readonly(pinocchio::sysvars::clock::CLOCK_ID, false),
+        AccountMeta::new_readonly(pinocchio::sysvars::rent::RENT_ID, false),
+    ]
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AutoRefundTrigger {
+    UnixTimestamp(i64),
+    Slot(u64),
+}
+
+pub fn auto_refund_trigger(escrow: &Escrow) -> Option<AutoRefundTrigger> {
+    if !escrow.has_deadline() || escrow.is_auction() || escrow.vesting_start != 0 {
+        return None;
+    }
+    Some(match escrow.deadline_kind {
+        Escrow::DEADLINE_SLOT => AutoRefundTrigger::Slot(escrow.deadline as u64),
+        _ => AutoRefundTrigger::UnixTimestamp(escrow.deadline),
+    })
+}
+
+pub fn auto_refund(
+    escrow_address: &Address,
+    escrow: &Escrow,
+    token_program: &Address,
+) -> Instruction {
+    let mut accounts = std::vec![
+        AccountMeta::new(escrow.maker, false),
+        AccountMeta::new(*escrow_address, false),
+        AccountMeta::new_readonly(escrow.mint_a, false),
+        AccountMeta::new(escrow.vault, false),
+        AccountMeta::new(
+            associated_token_address(&escrow.maker, &escrow.mint_a, token_program),
+            false
+        ),
+        AccountMeta::new_readonly(pinocchio_system::ID, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new(config_address(), false),
+        AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+    ];
+    accounts.extend(escrow_close_accounts(escrow_address, escrow));
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: std::vec![*crate::AutoRefund::DISCRIMINATOR],
+    }
+}
+
+pub fn account_escrow_address(token_account: &Address) -> Address {
+    Address::find_program_address(
+        &[crate::state::AccountEscrow::PREFIX, token_account.as_ref()],
+        &crate::ID,
+    )
+    .0
+}
+
+pub fn make_account_escrow(
+    maker: &Address,
+    token_account: &Address,
+    mint_b: &Address,
+    receive: u64,
+) -> Instruction {
+    let mut data = std::vec![*crate::MakeAccountEscrow::DISCRIMINATOR];
+    data.extend_from_slice(&receive.to_le_bytes());
+    Instruction {
+        program_id: crate::ID,
+        accounts: std::vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new(account_escrow_address(token_account), false),
+            AccountMeta::new(*token_account, false),
+            AccountMeta::new_readonly(*mint_b, false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+        ],
+        data,
+    }
+}
+
+pub fn take_account_escrow(
+    taker: &Address,
+    account_escrow: &crate::state::AccountEscrow,
+    token_program: &Address,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: std::vec![
+            AccountMeta::new(*taker, true),
+            AccountMeta::new(account_escrow.maker, false),
+            AccountMeta::new(account_escrow_address(&account_escrow.token_account), false),
+            AccountMeta::new(account_escrow.token_account, false),
+            AccountMeta::new_readonly(account_escrow.mint_b, false),
+            AccountMeta::new(
+                associated_token_address(taker, &account_escrow.mint_b, token_program),
+                false
+            ),
+            AccountMeta::new(
+                associated_token_address(
+                    &account_escrow.maker,
+                    &account_escrow.mint_b,
+                    token_program
+                ),
+                false
+            ),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+        ],
+        data: std::vec![*crate::TakeAccountEscrow::DISCRIMINATOR],
+    }
+}
+
+pub fn refund_account_escrow(
+    maker: &Address,
+    token_account: &Address,
+    token_program: &Address,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: std::vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new(account_escrow_address(token_account), false),
+            AccountMeta::new(*token_account, false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+        data: std::vec![*crate::RefundAccountEscrow::DISCRIMINATOR],
+    }
+}
+
+pub fn collection_offer_address(maker: &Address, seed: u64) -> Address {
+    Address::find_program_address(
+        &[
+            crate::state::CollectionOffer::PREFIX,
+            maker.as_ref(),
+            &seed.to_le_bytes(),
+        ],
+        &crate::ID,
+    )
+    .0
+}
+
+pub fn metadata_address(mint: &Address) -> Address {
+    Address::find_program_address(
+        &[
+            b"metadata",
+            crate::helpers::TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &crate::helpers::TOKEN_METADATA_PROGRAM_ID,
+    )
+    .0
+}
+
+#[cfg(feature = "nft")]
+pub fn make_collection_offer(
+    maker: &Address,
+    collection: &Address,
+    mint_b: &Address,
+    token_program: &Address,
+    seed: u64,
+    amount: u64,
+) -> Instruction {
+    let offer = collection_offer_address(maker, seed);
+    let mut data = Vec::with_capacity(1 + crate::MakeCollectionOfferInstructionData::LEN);
+    data.push(*crate::MakeCollectionOffer::DISCRIMINATOR);
+    data.extend_from_slice(&seed.to_le_bytes());
+    data.extend_from_slice(&amount.to_le_bytes());
+    Instruction {
+        program_id: crate::ID,
+        accounts: std::vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new(offer, false),
+            AccountMeta::new_readonly(*collection, false),
+            AccountMeta::new_readonly(*mint_b, false),
+            AccountMeta::new(
+                associated_token_address(maker, mint_b, token_program),
+                false
+            ),
+            AccountMeta::new(
+                associated_token_address(&offer, mint_b, token_program),
+                false
+            ),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+        ],
+        data,
+    }
+}
+
+#[cfg(feature = "nft")]
+pub fn with_offer_attributes(
+    mut instruction: Instruction,
+    attester: &Address,
+    attributes_hash: &[u8; 32],
+) -> Instruction {
+    instruction.data.extend_from_slice(attester.as_ref());
+    instruction.data.extend_from_slice(attributes_hash);
+    instruction
+}
+
+#[cfg(feature = "nft")]
+pub fn take_collection_offer(
+    taker: &Address,
+    offer: &crate::state::CollectionOffer,
+    nft_mint: &Address,
+    token_program: &Address,
+) -> Instruction {
+    let offer_address = collection_offer_address(&offer.maker, offer.seed);
+    let mut accounts = std::vec![
+        AccountMeta::new(*taker, true),
+        AccountMeta::new(offer.maker, false),
+        AccountMeta::new(offer_address, false),
+        AccountMeta::new_readonly(*nft_mint, false),
+        AccountMeta::new_readonly(metadata_address(nft_mint), false),
+        AccountMeta::new_readonly(offer.mint_b, false),
+        AccountMeta::new(
+            associated_token_address(&offer_address, &offer.mint_b, token_program),
+            false
+        ),
+        AccountMeta::new(
+            associated_token_address(taker, nft_mint, token_program),
+            false
+        ),
+        AccountMeta::new(
+            associated_token_address(&offer.maker, nft_mint, token_program),
+            false
+        ),
+        AccountMeta::new(
+            associated_token_address(taker, &offer.mint_b, token_program),
+            false
+        ),
+        AccountMeta::new_readonly(pinocchio_system::ID, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+    ];
+    if offer.has_attributes() {
+        accounts.push(AccountMeta::new_readonly(
+            pinocchio::sysvars::instructions::INSTRUCTIONS_ID,
+            false,
+        ));
+    }
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: std::vec![*crate::TakeCollectionOffer::DISCRIMINATOR],
+    }
+}
+
+#[cfg(feature = "nft")]
+pub fn refund_collection_offer(
+    offer: &crate::state::CollectionOffer,
+    token_program: &Address,
+) -> Instruction {
+    let offer_address = collection_offer_address(&offer.maker, offer.seed);
+    Instruction {
+        program_id: crate::ID,
+        accounts: std::vec![
+            AccountMeta::new(offer.maker, true),
+            AccountMeta::new(offer_address, false),
+            AccountMeta::new_readonly(offer.mint_b, false),
+            AccountMeta::new(
+                associated_token_address(&offer_address, &offer.mint_b, token_program),
+                false
+            ),
+            AccountMeta::new(
+                associated_token_address(&offer.maker, &offer.mint_b, token_program),
+                false
+            ),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+        ],
+        data: std::vec![*crate::RefundCollectionOffer::DISCRIMINATOR],
+    }
+}
+
+pub fn value_floor_address(escrow: &Address) -> Address {
+    Address::find_program_address(
+        &[crate::state::ValueFloor::PREFIX, escrow.as_ref()],
+        &crate::ID,
+    )
+    .0
+}
+
+pub fn set_value_floor(
+    maker: &Address,
+    escrow: &Address,
+    oracle: &Address,
+    oracle_program: &Address,
+    floor: &crate::state::ValueFloor,
+) -> Instruction {
+    let mut data = Vec::with_capacity(1 + crate::SetValueFloorInstructionData::LEN);
+    data.push(*crate::SetValueFloor::DISCRIMINATOR);
+    data.extend_from_slice(&floor.price_offset.to_le_bytes());
+    data.extend_from_slice(&floor.timestamp_offset.to_le_bytes());
+    data.extend_from_slice(&floor.max_age.to_le_bytes());
+    data.extend_from_slice(&floor.min_value_bps.to_le_bytes());
+    data.extend_from_slice(&floor.expo.to_le_bytes());
+    Instruction {
+        program_id: crate::ID,
+        accounts: std::vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new(*escrow, false),
+            AccountMeta::new(value_floor_address(escrow), false),
+            AccountMeta::new_readonly(*oracle, false),
+            AccountMeta::new_readonly(*oracle_program, false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new(config_address(), false),
+        ],
+        data,
+    }
+}
+
+pub fn release_approvals_address(escrow: &Address) -> Address {
+    Address::find_program_address(
+        &[crate::state::ReleaseApprovals::PREFIX, escrow.as_ref()],
+        &crate::ID,
+    )
+    .0
+}
+
+#[cfg(feature = "dispute")]
+pub fn set_release_signers(
+    maker: &Address,
+    escrow: &Address,
+    threshold: u8,
+    signers: &[Address],
+) -> Instruction {
+    let mut data = Vec::with_capacity(2 + signers.len() * 32);
+    data.push(*crate::SetReleaseSigners::DISCRIMINATOR);
+    data.push(threshold);
+    for signer in signers {
+        data.extend_from_slice(signer.as_ref());
+    }
+    Instruction {
+        program_id: crate::ID,
+        accounts: std::vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new(*escrow, false),
+            AccountMeta::new(release_approvals_address(escrow), false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new(config_address(), false),
+        ],
+        data,
+    }
+}
+
+#[cfg(feature = "dispute")]
+pub fn approve_release(signer: &Address, escrow: &Address) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: std::vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new_readonly(*escrow, false),
+            AccountMeta::new(release_approvals_address(escrow), false),
+        ],
+        data: std::vec![*crate::ApproveRelease::DISCRIMINATOR],
+    }
+}
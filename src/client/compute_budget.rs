@@ -0,0 +1,104 @@
+use std::vec::Vec;
+
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_instruction::Instruction;
+
+pub const MAKE_COMPUTE_UNITS: u32 = 45_000;
+pub const TAKE_COMPUTE_UNITS: u32 = 60_000;
+pub const REFUND_COMPUTE_UNITS: u32 = 25_000;
+pub const ESCROW_COMPUTE_UNITS: u32 = 30_000;
+pub const COMPUTE_BUDGET_COMPUTE_UNITS: u32 = 150;
+pub const DEFAULT_COMPUTE_UNITS: u32 = 200_000;
+pub const MAX_COMPUTE_UNITS: u32 = 1_400_000;
+pub const DEFAULT_MARGIN_BPS: u16 = 1_000;
+
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+
+pub fn instruction_compute_units(instruction: &Instruction) -> u32 {
+    if instruction.program_id == solana_compute_budget_interface::ID {
+        return COMPUTE_BUDGET_COMPUTE_UNITS;
+    }
+    if instruction.program_id != crate::ID {
+        return DEFAULT_COMPUTE_UNITS;
+    }
+    match instruction.data.first() {
+        Some(discriminator)
+            if discriminator == crate::Make::DISCRIMINATOR
+                || discriminator == crate::Make::PAIR_DISCRIMINATOR
+                || discriminator == crate::Make::NONCE_DISCRIMINATOR =>
+        {
+            MAKE_COMPUTE_UNITS
+        }
+        Some(discriminator)
+            if discriminator == crate::Take::DISCRIMINATOR
+                || discriminator == crate::TakeSigned::DISCRIMINATOR
+                || discriminator == crate::FillSignedOrder::DISCRIMINATOR
+                || discriminator == crate::TakeRoll::DISCRIMINATOR =>
+        {
+            TAKE_COMPUTE_UNITS
+        }
+        Some(discriminator) if discriminator == crate::Refund::DISCRIMINATOR => {
+            REFUND_COMPUTE_UNITS
+        }
+        _ => ESCROW_COMPUTE_UNITS,
+    }
+}
+
+pub fn with_margin(units: u32, margin_bps: u16) -> u32 {
+    let units = units as u64 + units as u64 * margin_bps as u64 / 10_000;
+    units.min(MAX_COMPUTE_UNITS as u64) as u32
+}
+
+pub fn estimate_compute_units(instructions: &[Instruction], margin_bps: u16) -> u32 {
+    let units = instructions
+        .iter()
+        .map(instruction_compute_units)
+        .fold(COMPUTE_BUDGET_COMPUTE_UNITS * 2, u32::saturating_add);
+    with_margin(units, margin_bps)
+}
+
+pub fn simulated_compute_units(units_consumed: u64, margin_bps: u16) -> u32 {
+    with_margin(
+        units_consumed.min(MAX_COMPUTE_UNITS as u64) as u32,
+        margin_bps,
+    )
+}
+
+pub fn priority_fee_lamports(compute_units: u32, micro_lamports_per_cu: u64) -> u64 {
+    (compute_units as u128 * micro_lamports_per_cu as u128).div_ceil(MICRO_LAMPORTS_PER_LAMPORT)
+        as u64
+}
+
+pub fn fee_percentile(fees: &[u64], percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    let mut sorted = fees.to_vec();
+    sorted.sort_unstable();
+    let index = (sorted.len() - 1) * percentile.min(100) as usize / 100;
+    sorted[index]
+}
+
+pub fn compute_budget_instructions(
+    compute_units: u32,
+    micro_lamports_per_cu: u64,
+) -> [Instruction; 2] {
+    [
+        ComputeBudgetInstruction::set_compute_unit_limit(compute_units),
+        ComputeBudgetInstruction::set_compute_unit_price(micro_lamports_per_cu),
+    ]
+}
+
+pub fn with_compute_budget(
+    instructions: &[Instruction],
+    micro_lamports_per_cu: u64,
+) -> Vec<Instruction> {
+    let compute_units = estimate_compute_units(instructions, DEFAULT_MARGIN_BPS);
+    let mut budgeted = Vec::with_capacity(instructions.len() + 2);
+    budgeted.extend(compute_budget_instructions(
+        compute_units,
+        micro_lamports_per_cu,
+    ));
+    budgeted.extend_from_slice(instructions);
+    budgeted
+}
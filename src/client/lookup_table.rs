@@ -0,0 +1,72 @@
+use std::vec::Vec;
+
+use solana_address::Address;
+use solana_address_lookup_table_interface::{
+    instruction::{create_lookup_table, extend_lookup_table},
+    state::AddressLookupTable,
+};
+use solana_hash::Hash;
+use solana_instruction::Instruction;
+use solana_message::{AddressLookupTableAccount, CompileError, VersionedMessage, v0};
+
+use super::config_address;
+
+pub fn escrow_lookup_table_addresses(
+    mint_a: &Address,
+    mint_b: &Address,
+    token_program: &Address,
+) -> Vec<Address> {
+    std::vec![
+        pinocchio_system::ID,
+        *token_program,
+        pinocchio_associated_token_account::ID,
+        config_address(),
+        *mint_a,
+        *mint_b,
+    ]
+}
+
+pub fn create_escrow_lookup_table(
+    authority: &Address,
+    payer: &Address,
+    recent_slot: u64,
+    mint_a: &Address,
+    mint_b: &Address,
+    token_program: &Address,
+) -> (Address, [Instruction; 2]) {
+    let (create, table) = create_lookup_table(*authority, *payer, recent_slot);
+    let extend = extend_lookup_table(
+        table,
+        *authority,
+        Some(*payer),
+        escrow_lookup_table_addresses(mint_a, mint_b, token_program),
+    );
+    (table, [create, extend])
+}
+
+pub fn extend_escrow_lookup_table(
+    table: &Address,
+    authority: &Address,
+    payer: &Address,
+    addresses: &[Address],
+) -> Instruction {
+    extend_lookup_table(*table, *authority, Some(*payer), addresses.to_vec())
+}
+
+pub fn decode_lookup_table(key: &Address, data: &[u8]) -> Option<AddressLookupTableAccount> {
+    let table = AddressLookupTable::deserialize(data).ok()?;
+    Some(AddressLookupTableAccount {
+        key: *key,
+        addresses: table.addresses.into_owned(),
+    })
+}
+
+pub fn compile_v0(
+    payer: &Address,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> Result<VersionedMessage, CompileError> {
+    v0::Message::try_compile(payer, instructions, lookup_tables, recent_blockhash)
+        .map(VersionedMessage::V0)
+}
@@ -0,0 +1,114 @@
+use std::vec::Vec;
+
+use solana_address::Address;
+use solana_hash::Hash;
+use solana_instruction::Instruction;
+use solana_message::{Message, VersionedMessage};
+use solana_system_interface::instruction::advance_nonce_account;
+
+pub const NONCE_ACCOUNT_LEN: usize = 80;
+pub const SIGNATURE_LEN: usize = 64;
+
+const NONCE_STATE_INITIALIZED: u32 = 1;
+
+pub struct NonceState {
+    pub authority: Address,
+    pub durable_nonce: Hash,
+}
+
+pub fn decode_nonce(data: &[u8]) -> Option<NonceState> {
+    if data.len() != NONCE_ACCOUNT_LEN {
+        return None;
+    }
+    let state = u32::from_le_bytes(data[4..8].try_into().unwrap());
+    if state != NONCE_STATE_INITIALIZED {
+        return None;
+    }
+    Some(NonceState {
+        authority: Address::new_from_array(data[8..40].try_into().unwrap()),
+        durable_nonce: Hash::new_from_array(data[40..72].try_into().unwrap()),
+    })
+}
+
+pub fn with_advance_nonce(
+    nonce_account: &Address,
+    nonce_authority: &Address,
+    instructions: &[Instruction],
+) -> Vec<Instruction> {
+    let mut with_nonce = Vec::with_capacity(instructions.len() + 1);
+    with_nonce.push(advance_nonce_account(nonce_account, nonce_authority));
+    with_nonce.extend_from_slice(instructions);
+    with_nonce
+}
+
+pub fn nonce_message(
+    payer: &Address,
+    instructions: &[Instruction],
+    nonce_account: &Address,
+    nonce_authority: &Address,
+    durable_nonce: Hash,
+) -> VersionedMessage {
+    VersionedMessage::Legacy(Message::new_with_blockhash(
+        &with_advance_nonce(nonce_account, nonce_authority, instructions),
+        Some(payer),
+        &durable_nonce,
+    ))
+}
+
+#[cfg(feature = "alt")]
+pub fn nonce_message_v0(
+    payer: &Address,
+    instructions: &[Instruction],
+    lookup_tables: &[solana_message::AddressLookupTableAccount],
+    nonce_account: &Address,
+    nonce_authority: &Address,
+    durable_nonce: Hash,
+) -> Result<VersionedMessage, solana_message::CompileError> {
+    super::lookup_table::compile_v0(
+        payer,
+        &with_advance_nonce(nonce_account, nonce_authority, instructions),
+        lookup_tables,
+        durable_nonce,
+    )
+}
+
+pub fn signing_payload(message: &VersionedMessage) -> Vec<u8> {
+    message.serialize()
+}
+
+pub fn required_signers(message: &VersionedMessage) -> &[Address] {
+    let signers = message.header().num_required_signatures as usize;
+    &message.static_account_keys()[..signers]
+}
+
+pub fn signer_index(message: &VersionedMessage, signer: &Address) -> Option<usize> {
+    required_signers(message)
+        .iter()
+        .position(|address| address == signer)
+}
+
+pub fn serialize_transaction(
+    message: &VersionedMessage,
+    signatures: &[[u8; SIGNATURE_LEN]],
+) -> Option<Vec<u8>> {
+    if signatures.len() != required_signers(message).len() {
+        return None;
+    }
+    let payload = signing_payload(message);
+    let mut transaction = Vec::with_capacity(3 + signatures.len() * SIGNATURE_LEN + payload.len());
+    let mut len = signatures.len() as u16;
+    loop {
+        let byte = (len & 0x7f) as u8;
+        len >>= 7;
+        if len == 0 {
+            transaction.push(byte);
+            break;
+        }
+        transaction.push(byte | 0x80);
+    }
+    for signature in signatures {
+        transaction.extend_from_slice(signature);
+    }
+    transaction.extend_from_slice(&payload);
+    Some(transaction)
+}
@@ -0,0 +1,301 @@
+use core::{fmt, mem::offset_of};
+use std::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde_json::{Value, json};
+use solana_address::Address;
+
+use crate::state::Escrow;
+
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+#[derive(Debug)]
+pub enum RpcError {
+    Http(reqwest::Error),
+    Rpc { code: i64, message: String },
+    InvalidResponse(&'static str),
+}
+
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Http(err) => write!(f, "rpc transport error: {err}"),
+            Self::Rpc { code, message } => write!(f, "rpc error {code}: {message}"),
+            Self::InvalidResponse(reason) => write!(f, "invalid rpc response: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+impl From<reqwest::Error> for RpcError {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Http(err)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Filter {
+    DataSize(usize),
+    Memcmp { offset: usize, bytes: Vec<u8> },
+}
+
+impl Filter {
+    pub fn memcmp(offset: usize, bytes: &[u8]) -> Self {
+        Self::Memcmp {
+            offset,
+            bytes: bytes.to_vec(),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            Self::DataSize(size) => json!({ "dataSize": size }),
+            Self::Memcmp { offset, bytes } => json!({
+                "memcmp": {
+                    "offset": offset,
+                    "bytes": STANDARD.encode(bytes),
+                    "encoding": "base64",
+                }
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DataSlice {
+    pub offset: usize,
+    pub length: usize,
+}
+
+pub fn escrow_filters() -> Vec<Filter> {
+    std::vec![
+        Filter::DataSize(Escrow::LEN),
+        Filter::memcmp(offset_of!(Escrow, discriminator), &[Escrow::DISCRIMINATOR]),
+    ]
+}
+
+pub fn category_filters(category: u16) -> Vec<Filter> {
+    let mut filters = escrow_filters();
+    filters.push(Filter::memcmp(
+        offset_of!(Escrow, category),
+        &category.to_le_bytes(),
+    ));
+    filters
+}
+
+pub fn maker_filters(maker: &Address) -> Vec<Filter> {
+    let mut filters = escrow_filters();
+    filters.push(Filter::memcmp(offset_of!(Escrow, maker), maker.as_ref()));
+    filters
+}
+
+pub fn mint_pair_filters(mint_a: &Address, mint_b: &Address) -> Vec<Filter> {
+    let mut filters = escrow_filters();
+    filters.push(Filter::memcmp(offset_of!(Escrow, mint_a), mint_a.as_ref()));
+    filters.push(Filter::memcmp(offset_of!(Escrow, mint_b), mint_b.as_ref()));
+    filters
+}
+
+pub struct RpcClient {
+    http: reqwest::Client,
+    url: String,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::new_with_client(reqwest::Client::new(), url)
+    }
+
+    pub fn new_with_client(http: reqwest::Client, url: impl Into<String>) -> Self {
+        Self {
+            http,
+            url: url.into(),
+        }
+    }
+
+    async fn request(&self, method: &str, params: Value) -> Result<Value, RpcError> {
+        let mut response: Value = self
+            .http
+            .post(&self.url)
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": params,
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if let Some(error) = response.get("error") {
+            return Err(RpcError::Rpc {
+                code: error["code"].as_i64().unwrap_or_default(),
+                message: error["message"].as_str().unwrap_or_default().into(),
+            });
+        }
+        match response.get_mut("result") {
+            Some(result) => Ok(result.take()),
+            None => Err(RpcError::InvalidResponse("missing result")),
+        }
+    }
+
+    pub async fn get_program_accounts(
+        &self,
+        filters: &[Filter],
+        data_slice: Option<DataSlice>,
+    ) -> Result<Vec<(Address, Vec<u8>)>, RpcError> {
+        let mut config = json!({
+            "encoding": "base64",
+            "filters": filters.iter().map(Filter::to_json).collect::<Vec<_>>(),
+        });
+        if let Some(slice) = data_slice {
+            config["dataSlice"] = json!({ "offset": slice.offset, "length": slice.length });
+        }
+        let result = self
+            .request("getProgramAccounts", json!([crate::ID.to_string(), config]))
+            .await?;
+        result
+            .as_array()
+            .ok_or(RpcError::InvalidResponse("expected an account array"))?
+            .iter()
+            .map(|keyed| {
+                let address = keyed["pubkey"]
+                    .as_str()
+                    .and_then(|pubkey| pubkey.parse().ok())
+                    .ok_or(RpcError::InvalidResponse("invalid account pubkey"))?;
+                Ok((address, account_data(&keyed["account"])?))
+            })
+            .collect()
+    }
+
+    pub async fn get_multiple_accounts(
+        &self,
+        addresses: &[Address],
+    ) -> Result<Vec<Option<Vec<u8>>>, RpcError> {
+        let mut accounts = Vec::with_capacity(addresses.len());
+        for page in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let keys = page.iter().map(Address::to_string).collect::<Vec<_>>();
+            let result = self
+                .request(
+                    "getMultipleAccounts",
+                    json!([keys, { "encoding": "base64" }]),
+                )
+                .await?;
+            let values = result["value"]
+                .as_array()
+                .filter(|values| values.len() == page.len())
+                .ok_or(RpcError::InvalidResponse("expected one entry per address"))?;
+            for value in values {
+                accounts.push(match value {
+                    Value::Null => None,
+                    account => Some(account_data(account)?),
+                });
+            }
+        }
+        Ok(accounts)
+    }
+
+    pub async fn get_recent_prioritization_fees(
+        &self,
+        addresses: &[Address],
+    ) -> Result<Vec<u64>, RpcError> {
+        let keys = addresses.iter().map(Address::to_string).collect::<Vec<_>>();
+        let result = self
+            .request("getRecentPrioritizationFees", json!([keys]))
+            .await?;
+        result
+            .as_array()
+            .ok_or(RpcError::InvalidResponse("expected a fee array"))?
+            .iter()
+            .map(|fee| {
+                fee["prioritizationFee"]
+                    .as_u64()
+                    .ok_or(RpcError::InvalidResponse("invalid prioritization fee"))
+            })
+            .collect()
+    }
+
+    pub async fn get_escrow(&self, address: &Address) -> Result<Option<Escrow>, RpcError> {
+        let accounts = self
+            .get_multiple_accounts(core::slice::from_ref(address))
+            .await?;
+        Ok(accounts
+            .into_iter()
+            .flatten()
+            .find_map(|data| super::decode_escrow(&data)))
+    }
+
+    pub async fn escrow_addresses(&self, filters: &[Filter]) -> Result<Vec<Address>, RpcError> {
+        let accounts = self
+            .get_program_accounts(
+                filters,
+                Some(DataSlice {
+                    offset: 0,
+                    length: 0,
+                }),
+            )
+            .await?;
+        Ok(accounts.into_iter().map(|(address, _)| address).collect())
+    }
+
+    pub async fn escrows_by_address(
+        &self,
+        addresses: &[Address],
+    ) -> Result<Vec<(Address, Escrow)>, RpcError> {
+        let accounts = self.get_multiple_accounts(addresses).await?;
+        Ok(addresses
+            .iter()
+            .zip(accounts)
+            .filter_map(|(address, data)| {
+                data.and_then(|data| super::decode_escrow(&data))
+                    .map(|escrow| (*address, escrow))
+            })
+            .collect())
+    }
+
+    pub async fn escrows(&self, filters: &[Filter]) -> Result<Vec<(Address, Escrow)>, RpcError> {
+        let accounts = self.get_program_accounts(filters, None).await?;
+        Ok(accounts
+            .into_iter()
+            .filter_map(|(address, data)| {
+                super::decode_escrow(&data).map(|escrow| (address, escrow))
+            })
+            .collect())
+    }
+
+    pub async fn escrows_by_maker(
+        &self,
+        maker: &Address,
+    ) -> Result<Vec<(Address, Escrow)>, RpcError> {
+        self.escrows(&maker_filters(maker)).await
+    }
+
+    pub async fn escrows_by_category(
+        &self,
+        category: u16,
+    ) -> Result<Vec<(Address, Escrow)>, RpcError> {
+        self.escrows(&category_filters(category)).await
+    }
+
+    pub async fn escrows_by_mint_pair(
+        &self,
+        mint_a: &Address,
+        mint_b: &Address,
+    ) -> Result<Vec<(Address, Escrow)>, RpcError> {
+        self.escrows(&mint_pair_filters(mint_a, mint_b)).await
+    }
+}
+
+fn account_data(account: &Value) -> Result<Vec<u8>, RpcError> {
+    let data = account["data"][0]
+        .as_str()
+        .ok_or(RpcError::InvalidResponse("expected base64 account data"))?;
+    STANDARD
+        .decode(data)
+        .map_err(|_| RpcError::InvalidResponse("invalid base64 account data"))
+}
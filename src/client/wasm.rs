@@ -0,0 +1,230 @@
+use std::{string::ToString, vec::Vec};
+
+use solana_address::Address;
+use solana_instruction::Instruction;
+use wasm_bindgen::prelude::*;
+
+use crate::state::Escrow;
+
+use super::MakeArgs;
+
+const SIGNER: u8 = 1 << 0;
+const WRITABLE: u8 = 1 << 1;
+
+fn address(bytes: &[u8]) -> Result<Address, JsError> {
+    <[u8; 32]>::try_from(bytes)
+        .map(Address::new_from_array)
+        .map_err(|_| JsError::new("address must be 32 bytes"))
+}
+
+fn escrow(data: &[u8]) -> Result<Escrow, JsError> {
+    super::decode_escrow(data).ok_or_else(|| JsError::new("account data is not an escrow"))
+}
+
+#[wasm_bindgen(js_name = EscrowInstruction)]
+pub struct WasmInstruction(Instruction);
+
+#[wasm_bindgen(js_class = EscrowInstruction)]
+impl WasmInstruction {
+    #[wasm_bindgen(getter, js_name = programId)]
+    pub fn program_id(&self) -> Vec<u8> {
+        self.0.program_id.as_ref().to_vec()
+    }
+
+    #[wasm_bindgen(getter, js_name = accountKeys)]
+    pub fn account_keys(&self) -> Vec<u8> {
+        self.0
+            .accounts
+            .iter()
+            .flat_map(|meta| meta.pubkey.to_bytes())
+            .collect()
+    }
+
+    #[wasm_bindgen(getter, js_name = accountFlags)]
+    pub fn account_flags(&self) -> Vec<u8> {
+        self.0
+            .accounts
+            .iter()
+            .map(|meta| (meta.is_signer as u8 * SIGNER) | (meta.is_writable as u8 * WRITABLE))
+            .collect()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn data(&self) -> Vec<u8> {
+        self.0.data.clone()
+    }
+}
+
+#[wasm_bindgen(js_name = EscrowAccount)]
+pub struct WasmEscrow(Escrow);
+
+#[wasm_bindgen(js_class = EscrowAccount)]
+impl WasmEscrow {
+    #[wasm_bindgen(constructor)]
+    pub fn new(data: &[u8]) -> Result<WasmEscrow, JsError> {
+        escrow(data).map(Self)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn seed(&self) -> u64 {
+        self.0.seed
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn maker(&self) -> Vec<u8> {
+        self.0.maker.as_ref().to_vec()
+    }
+
+    #[wasm_bindgen(getter, js_name = mintA)]
+    pub fn mint_a(&self) -> Vec<u8> {
+        self.0.mint_a.as_ref().to_vec()
+    }
+
+    #[wasm_bindgen(getter, js_name = mintB)]
+    pub fn mint_b(&self) -> Vec<u8> {
+        self.0.mint_b.as_ref().to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn vault(&self) -> Vec<u8> {
+        self.0.vault.as_ref().to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn receive(&self) -> u64 {
+        self.0.receive
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn amount(&self) -> u64 {
+        self.0.amount
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn fills(&self) -> u64 {
+        self.0.fills
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn deadline(&self) -> i64 {
+        self.0.deadline
+    }
+
+    #[wasm_bindgen(getter, js_name = deadlineKind)]
+    pub fn deadline_kind(&self) -> u8 {
+        self.0.deadline_kind
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn flags(&self) -> u8 {
+        self.0.flags
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn frozen(&self) -> bool {
+        self.0.is_frozen()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn label(&self) -> Vec<u8> {
+        self.0.label.to_vec()
+    }
+}
+
+#[wasm_bindgen(js_name = programId)]
+pub fn program_id() -> Vec<u8> {
+    crate::ID.as_ref().to_vec()
+}
+
+#[wasm_bindgen(js_name = programIdBase58)]
+pub fn program_id_base58() -> std::string::String {
+    crate::ID.to_string()
+}
+
+#[wasm_bindgen(js_name = nonceEscrowAddress)]
+pub fn nonce_escrow_address(maker: &[u8], nonce: u64) -> Result<Vec<u8>, JsError> {
+    Ok(super::nonce_escrow_address(&address(maker)?, nonce)
+        .as_ref()
+        .to_vec())
+}
+
+#[wasm_bindgen(js_name = escrowAddress)]
+pub fn escrow_address(maker: &[u8], seed: u64) -> Result<Vec<u8>, JsError> {
+    Ok(super::escrow_address(&address(maker)?, seed)
+        .as_ref()
+        .to_vec())
+}
+
+#[wasm_bindgen(js_name = configAddress)]
+pub fn config_address() -> Vec<u8> {
+    super::config_address().as_ref().to_vec()
+}
+
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen(js_name = makeInstruction)]
+pub fn make(
+    maker: &[u8],
+    mint_a: &[u8],
+    mint_b: &[u8],
+    token_program: &[u8],
+    seed: u64,
+    receive: u64,
+    amount: u64,
+    deadline: i64,
+    deadline_kind: u8,
+    flags: u8,
+    label: &[u8],
+) -> Result<WasmInstruction, JsError> {
+    let mut padded = [0u8; 32];
+    padded
+        .get_mut(..label.len())
+        .ok_or_else(|| JsError::new("label is longer than 32 bytes"))?
+        .copy_from_slice(label);
+    let args = MakeArgs {
+        seed,
+        receive,
+        amount,
+        deadline,
+        vesting_cliff: 0,
+        vesting_duration: 0,
+        flags,
+        label: padded,
+        deadline_kind,
+        ..MakeArgs::default()
+    };
+    Ok(WasmInstruction(super::make(
+        &address(maker)?,
+        &address(mint_a)?,
+        &address(mint_b)?,
+        &address(token_program)?,
+        &args,
+    )))
+}
+
+#[wasm_bindgen(js_name = takeInstruction)]
+pub fn take(
+    taker: &[u8],
+    escrow_address: &[u8],
+    escrow_data: &[u8],
+    token_program: &[u8],
+) -> Result<WasmInstruction, JsError> {
+    Ok(WasmInstruction(super::take_with_terms(
+        &address(taker)?,
+        &address(escrow_address)?,
+        &escrow(escrow_data)?,
+        &address(token_program)?,
+    )))
+}
+
+#[wasm_bindgen(js_name = refundInstruction)]
+pub fn refund(
+    escrow_address: &[u8],
+    escrow_data: &[u8],
+    token_program: &[u8],
+) -> Result<WasmInstruction, JsError> {
+    Ok(WasmInstruction(super::refund(
+        &address(escrow_address)?,
+        &escrow(escrow_data)?,
+        &address(token_program)?,
+    )))
+}
@@ -0,0 +1,218 @@
+use pinocchio::{
+    AccountView, ProgramResult,
+    cpi::{Signer, invoke_signed},
+    instruction::{InstructionAccount, InstructionView},
+};
+
+pub struct MakeCpi<'a> {
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub mint_a: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub maker_ata_a: &'a AccountView,
+    pub vault: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub associated_token_program: &'a AccountView,
+    pub seed: u64,
+    pub receive: u64,
+    pub amount: u64,
+    pub deadline: i64,
+    pub vesting_cliff: i64,
+    pub vesting_duration: i64,
+    pub flags: u8,
+    pub label: [u8; 32],
+    pub deadline_kind: u8,
+}
+
+impl MakeCpi<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts = [
+            InstructionAccount::writable_signer(self.maker.address()),
+            InstructionAccount::writable(self.escrow.address()),
+            InstructionAccount::readonly(self.mint_a.address()),
+            InstructionAccount::readonly(self.mint_b.address()),
+            InstructionAccount::writable(self.maker_ata_a.address()),
+            InstructionAccount::writable(self.vault.address()),
+            InstructionAccount::readonly(self.system_program.address()),
+            InstructionAccount::readonly(self.token_program.address()),
+            InstructionAccount::writable(self.config.address()),
+            InstructionAccount::readonly(self.associated_token_program.address()),
+        ];
+
+        let mut data = [0u8; 83];
+        data[0] = *crate::Make::DISCRIMINATOR;
+        data[1..9].copy_from_slice(&self.seed.to_le_bytes());
+        data[9..17].copy_from_slice(&self.receive.to_le_bytes());
+        data[17..25].copy_from_slice(&self.amount.to_le_bytes());
+        data[25..33].copy_from_slice(&self.deadline.to_le_bytes());
+        data[33..41].copy_from_slice(&self.vesting_cliff.to_le_bytes());
+        data[41..49].copy_from_slice(&self.vesting_duration.to_le_bytes());
+        data[49] = self.flags;
+        data[50..82].copy_from_slice(&self.label);
+        data[82] = self.deadline_kind;
+
+        invoke_signed(
+            &InstructionView {
+                program_id: &crate::ID,
+                accounts: &instruction_accounts,
+                data: &data,
+            },
+            &[
+                self.maker,
+                self.escrow,
+                self.mint_a,
+                self.mint_b,
+                self.maker_ata_a,
+                self.vault,
+                self.system_program,
+                self.token_program,
+                self.config,
+                self.associated_token_program,
+            ],
+            signers,
+        )
+    }
+}
+
+pub struct TakeCpi<'a> {
+    pub taker: &'a AccountView,
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub mint_a: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub vault: &'a AccountView,
+    pub taker_ata_a: &'a AccountView,
+    pub taker_ata_b: &'a AccountView,
+    pub maker_ata_b: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub associated_token_program: &'a AccountView,
+}
+
+impl TakeCpi<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts = [
+            InstructionAccount::writable_signer(self.taker.address()),
+            InstructionAccount::writable(self.maker.address()),
+            InstructionAccount::writable(self.escrow.address()),
+            InstructionAccount::readonly(self.mint_a.address()),
+            InstructionAccount::readonly(self.mint_b.address()),
+            InstructionAccount::writable(self.vault.address()),
+            InstructionAccount::writable(self.taker_ata_a.address()),
+            InstructionAccount::writable(self.taker_ata_b.address()),
+            InstructionAccount::writable(self.maker_ata_b.address()),
+            InstructionAccount::readonly(self.system_program.address()),
+            InstructionAccount::readonly(self.token_program.address()),
+            InstructionAccount::writable(self.config.address()),
+            InstructionAccount::readonly(self.associated_token_program.address()),
+        ];
+
+        invoke_signed(
+            &InstructionView {
+                program_id: &crate::ID,
+                accounts: &instruction_accounts,
+                data: &[*crate::Take::DISCRIMINATOR],
+            },
+            &[
+                self.taker,
+                self.maker,
+                self.escrow,
+                self.mint_a,
+                self.mint_b,
+                self.vault,
+                self.taker_ata_a,
+                self.taker_ata_b,
+                self.maker_ata_b,
+                self.system_program,
+                self.token_program,
+                self.config,
+                self.associated_token_program,
+            ],
+            signers,
+        )
+    }
+}
+
+pub struct RefundCpi<'a> {
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub mint_a: &'a AccountView,
+    pub vault: &'a AccountView,
+    pub maker_ata_a: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub associated_token_program: &'a AccountView,
+}
+
+impl RefundCpi<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts = [
+            InstructionAccount::writable_signer(self.maker.address()),
+            InstructionAccount::writable(self.escrow.address()),
+            InstructionAccount::readonly(self.mint_a.address()),
+            InstructionAccount::writable(self.vault.address()),
+            InstructionAccount::writable(self.maker_ata_a.address()),
+            InstructionAccount::readonly(self.system_program.address()),
+            InstructionAccount::readonly(self.token_program.address()),
+            InstructionAccount::writable(self.config.address()),
+            InstructionAccount::readonly(self.associated_token_program.address()),
+        ];
+
+        invoke_signed(
+            &InstructionView {
+                program_id: &crate::ID,
+                accounts: &instruction_accounts,
+                data: &[*crate::Refund::DISCRIMINATOR],
+            },
+            &[
+                self.maker,
+                self.escrow,
+                self.mint_a,
+                self.vault,
+                self.maker_ata_a,
+                self.system_program,
+                self.token_program,
+                self.config,
+                self.associated_token_program,
+            ],
+            signers,
+        )
+    }
+}
+
+pub struct GetEscrowCpi<'a> {
+    pub escrow: &'a AccountView,
+}
+
+impl GetEscrowCpi<'_> {
+    pub fn invoke(&self) -> ProgramResult {
+        invoke_signed(
+            &InstructionView {
+                program_id: &crate::ID,
+                accounts: &[InstructionAccount::readonly(self.escrow.address())],
+                data: &[*crate::GetEscrow::DISCRIMINATOR],
+            },
+            &[self.escrow],
+            &[],
+        )
+    }
+}
@@ -0,0 +1,30 @@
+use pinocchio::error::ProgramError;
+
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EscrowError {
+    LamportOverflow,
+    FlashRepaymentShortfall,
+    EscrowFrozen,
+    TakerCooldown,
+    ConditionNotMet,
+    TakerNotAllowed,
+    Denylisted,
+    MintNotAllowed,
+    OpenEscrowLimit,
+    MintPaused,
+    MemoRequired,
+    CallerNotAllowed,
+    TermsMismatch,
+    StaleOrder,
+    PriceOutOfBounds,
+    StaleOracle,
+    BelowValueFloor,
+    ReleaseNotApproved,
+}
+
+impl From<EscrowError> for ProgramError {
+    fn from(e: EscrowError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,107 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::{Seed, Signer, invoke_signed},
+    error::ProgramError,
+    instruction::{InstructionAccount, InstructionView},
+};
+
+#[inline(always)]
+fn log_data(data: &[&[u8]]) {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        pinocchio::syscalls::sol_log_data(data.as_ptr() as *const u8, data.len() as u64);
+    }
+    #[cfg(not(target_os = "solana"))]
+    core::hint::black_box(data);
+}
+
+pub fn split_event_accounts(
+    accounts: &[AccountView],
+) -> Result<(&[AccountView], &[AccountView]), ProgramError> {
+    if accounts
+        .last()
+        .is_none_or(|program| program.address().ne(&crate::ID))
+    {
+        return Ok((accounts, &[]));
+    }
+    let (accounts, event_accounts) = accounts
+        .split_last_chunk::<2>()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    Ok((accounts, event_accounts))
+}
+
+fn self_cpi(accounts: &[AccountView], data: &[u8]) -> ProgramResult {
+    let [event_authority, _] = split_event_accounts(accounts)?.1 else {
+        return Ok(());
+    };
+    let (authority_key, bump) =
+        Address::find_program_address(&[crate::EmitEvent::AUTHORITY_SEED], &crate::ID);
+    if authority_key.ne(event_authority.address()) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let bump_binding = [bump];
+    let seeds = [
+        Seed::from(crate::EmitEvent::AUTHORITY_SEED),
+        Seed::from(&bump_binding),
+    ];
+    invoke_signed(
+        &InstructionView {
+            program_id: &crate::ID,
+            accounts: &[InstructionAccount::readonly_signer(
+                event_authority.address(),
+            )],
+            data,
+        },
+        &[event_authority],
+        &[Signer::from(&seeds)],
+    )
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct FillEvent {
+    pub escrow: Address,
+    pub maker: Address,
+    pub taker: Address,
+    pub mint_a: Address,
+    pub mint_b: Address,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub ui_amount_a: u64,
+    pub ui_amount_b: u64,
+    pub fills: u64,
+    pub label: [u8; 32],
+}
+
+impl FillEvent {
+    pub const DISCRIMINATOR: &'static [u8] = b"fill";
+    pub fn emit(&self) {
+        log_data(&[Self::DISCRIMINATOR, bytemuck::bytes_of(self)]);
+    }
+    pub fn emit_cpi(&self, accounts: &[AccountView]) -> ProgramResult {
+        self.emit();
+        let mut data = [0u8; 1 + 4 + size_of::<Self>()];
+        data[0] = *crate::EmitEvent::DISCRIMINATOR;
+        data[1..5].copy_from_slice(Self::DISCRIMINATOR);
+        data[5..].copy_from_slice(bytemuck::bytes_of(self));
+        self_cpi(accounts, &data)
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct GroupMemberEvent {
+    pub escrow: Address,
+    pub mint: Address,
+    pub group: Address,
+    pub metadata: Address,
+    pub member_number: u64,
+}
+
+impl GroupMemberEvent {
+    pub const DISCRIMINATOR: &'static [u8] = b"group_member";
+    pub fn emit(&self) {
+        log_data(&[Self::DISCRIMINATOR, bytemuck::bytes_of(self)]);
+    }
+}
@@ -1,13 +1,120 @@
+use core::{marker::PhantomData, ops::Deref};
+
 use pinocchio::{
-    AccountView, Address, ProgramResult, cpi::Signer, error::ProgramError, sysvars::rent::Rent,
+    AccountView, Address, ProgramResult,
+    cpi::{Seed, Signer, invoke, invoke_signed},
+    error::ProgramError,
+    instruction::{InstructionAccount, InstructionView},
+    sysvars::{Sysvar, clock::Clock, instructions::Instructions, rent::Rent},
+};
+use pinocchio_system::{
+    create_account_with_minimum_balance_signed,
+    instructions::{CreateAccount, Transfer},
 };
-use pinocchio_system::instructions::CreateAccount;
 use pinocchio_token::instructions::{InitializeAccount3, InitializeMint2};
 
+use crate::errors::EscrowError;
+
+#[inline(always)]
+pub fn next_account<'a>(
+    accounts: &mut core::slice::Iter<'a, AccountView>,
+) -> Result<&'a AccountView, ProgramError> {
+    accounts.next().ok_or(ProgramError::NotEnoughAccountKeys)
+}
+
+#[inline(always)]
+pub fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> Option<[u8; N]> {
+    data.get(offset..)?.first_chunk::<N>().copied()
+}
+
+#[inline(always)]
+pub fn instruction_bytes<const N: usize>(
+    data: &[u8],
+    offset: usize,
+) -> Result<[u8; N], ProgramError> {
+    read_bytes(data, offset).ok_or(ProgramError::InvalidInstructionData)
+}
+
+#[inline(always)]
+pub fn account_bytes<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N], ProgramError> {
+    read_bytes(data, offset).ok_or(ProgramError::InvalidAccountData)
+}
+
 pub trait AccountCheck {
     fn check(account: &AccountView) -> Result<(), ProgramError>;
 }
 
+pub trait ProgramId {
+    const ID: Address;
+    const NOT_OWNER: ProgramError = ProgramError::IllegalOwner;
+}
+pub struct EscrowProgram;
+impl ProgramId for EscrowProgram {
+    const ID: Address = crate::ID;
+    const NOT_OWNER: ProgramError = ProgramError::InvalidAccountOwner;
+}
+pub struct SystemProgram;
+impl ProgramId for SystemProgram {
+    const ID: Address = pinocchio_system::ID;
+}
+pub struct TokenProgram;
+impl ProgramId for TokenProgram {
+    const ID: Address = pinocchio_token::ID;
+}
+pub struct Token2022Program;
+impl ProgramId for Token2022Program {
+    const ID: Address = Address::new_from_array(TOKEN_2022_PROGRAM_ID);
+}
+pub struct AssociatedTokenProgram;
+impl ProgramId for AssociatedTokenProgram {
+    const ID: Address = pinocchio_associated_token_account::ID;
+}
+
+pub struct And<A, B>(PhantomData<(A, B)>);
+impl<A: AccountCheck, B: AccountCheck> AccountCheck for And<A, B> {
+    fn check(account: &AccountView) -> Result<(), ProgramError> {
+        A::check(account)?;
+        B::check(account)
+    }
+}
+
+pub struct Or<A, B>(PhantomData<(A, B)>);
+impl<A: AccountCheck, B: AccountCheck> AccountCheck for Or<A, B> {
+    fn check(account: &AccountView) -> Result<(), ProgramError> {
+        A::check(account).or_else(|_| B::check(account))
+    }
+}
+
+pub struct OwnedBy<P>(PhantomData<P>);
+impl<P: ProgramId> AccountCheck for OwnedBy<P> {
+    fn check(account: &AccountView) -> Result<(), ProgramError> {
+        if !account.owned_by(&P::ID) {
+            return Err(P::NOT_OWNER);
+        }
+        Ok(())
+    }
+}
+
+pub struct LenEq<const N: usize>;
+impl<const N: usize> AccountCheck for LenEq<N> {
+    fn check(account: &AccountView) -> Result<(), ProgramError> {
+        if account.data_len().ne(&N) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+}
+
+pub struct AddressEq<P>(PhantomData<P>);
+impl<P: ProgramId> AccountCheck for AddressEq<P> {
+    fn check(account: &AccountView) -> Result<(), ProgramError> {
+        if account.address().ne(&P::ID) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        Ok(())
+    }
+}
+
 pub struct SignerAccount;
 impl AccountCheck for SignerAccount {
     fn check(account: &AccountView) -> Result<(), ProgramError> {
@@ -18,16 +125,25 @@ impl AccountCheck for SignerAccount {
     }
 }
 
-pub struct SystemAccount;
-impl AccountCheck for SystemAccount {
+pub type SystemProgramAccount = AddressEq<SystemProgram>;
+#[cfg(feature = "token2022")]
+pub type TokenProgramAccount = Or<AddressEq<TokenProgram>, AddressEq<Token2022Program>>;
+#[cfg(not(feature = "token2022"))]
+pub type TokenProgramAccount = AddressEq<TokenProgram>;
+pub type AssociatedTokenProgramAccount = AddressEq<AssociatedTokenProgram>;
+
+pub struct RentExemptAccount;
+impl AccountCheck for RentExemptAccount {
     fn check(account: &AccountView) -> Result<(), ProgramError> {
-        if !account.owned_by(&pinocchio_system::ID) {
-            return Err(ProgramError::IllegalOwner);
+        if !Rent::get()?.is_exempt(account.lamports(), account.data_len()) {
+            return Err(ProgramError::AccountNotRentExempt);
         }
         Ok(())
     }
 }
 
+pub type SystemAccount = OwnedBy<SystemProgram>;
+
 pub struct MintAccount;
 impl AccountCheck for MintAccount {
     fn check(account: &AccountView) -> Result<(), ProgramError> {
@@ -185,13 +301,19 @@ pub const TOKEN_2022_PROGRAM_ID: [u8; 32] = [
     0x06, 0xdd, 0xf6, 0xe1, 0xee, 0x75, 0x8f, 0xde, 0x18, 0x42, 0x5d, 0xbc, 0xe4, 0x6c, 0xcd, 0xda,
     0xb6, 0x1a, 0xfc, 0x4d, 0x83, 0xb9, 0x0d, 0x27, 0xfe, 0xbd, 0xf9, 0x28, 0xd8, 0xa1, 0x8b, 0xfc,
 ];
+#[inline(always)]
+pub fn is_token_2022(account: &AccountView) -> bool {
+    cfg!(feature = "token2022") && account.owned_by(&TOKEN_2022_PROGRAM_ID.into())
+}
 const TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET: usize = 165;
 pub const TOKEN2022_MINT_DISCRIMINATOR: u8 = 0x01;
 pub const TOKEN_2022_TOKEN_ACCOUNT_DISCRIMINATOR: u8 = 0x02;
+#[cfg(feature = "token2022")]
 pub struct Mint2022Account;
+#[cfg(feature = "token2022")]
 impl AccountCheck for Mint2022Account {
     fn check(account: &AccountView) -> Result<(), ProgramError> {
-        if !account.owned_by(&TOKEN_2022_PROGRAM_ID.into()) {
+        if !is_token_2022(account) {
             return Err(ProgramError::IllegalOwner);
         }
         let data = account.try_borrow()?;
@@ -206,6 +328,7 @@ impl AccountCheck for Mint2022Account {
         Ok(())
     }
 }
+#[cfg(feature = "token2022")]
 impl MintInit for Mint2022Account {
     fn init(
         account: &AccountView,
@@ -254,10 +377,12 @@ impl MintInit for Mint2022Account {
         }
     }
 }
+#[cfg(feature = "token2022")]
 pub struct TokenAccount2022Account;
+#[cfg(feature = "token2022")]
 impl AccountCheck for TokenAccount2022Account {
     fn check(account: &AccountView) -> Result<(), ProgramError> {
-        if !account.owned_by(&TOKEN_2022_PROGRAM_ID.into()) {
+        if !is_token_2022(account) {
             return Err(ProgramError::IllegalOwner);
         }
         let data = account.try_borrow()?;
@@ -274,6 +399,7 @@ impl AccountCheck for TokenAccount2022Account {
         Ok(())
     }
 }
+#[cfg(feature = "token2022")]
 impl AccountInit for TokenAccount2022Account {
     fn init_if_needed(
         account: &AccountView,
@@ -294,13 +420,13 @@ impl AccountInit for TokenAccount2022Account {
         rent: &AccountView,
         owner: &Address,
     ) -> ProgramResult {
-        let lamports = Rent::from_account_view(rent)?
-            .try_minimum_balance(pinocchio_token::state::TokenAccount::LEN)?;
+        let space = token_account_2022_len(&mint.try_borrow()?);
+        let lamports = Rent::from_account_view(rent)?.try_minimum_balance(space)?;
         CreateAccount {
             from: payer,
             to: account,
             lamports,
-            space: pinocchio_token::state::TokenAccount::LEN as u64,
+            space: space as u64,
             owner: &TOKEN_2022_PROGRAM_ID.into(),
         }
         .invoke()?;
@@ -313,10 +439,39 @@ impl AccountInit for TokenAccount2022Account {
     }
 }
 
+#[cfg(feature = "token2022")]
+const MULTISIG_LEN: usize = 355;
+#[cfg(feature = "token2022")]
+const ACCOUNT_EXTENSIONS: [(u16, usize); 5] = [
+    (EXTENSION_TRANSFER_FEE_CONFIG, 8),
+    (EXTENSION_NON_TRANSFERABLE, 0),
+    (EXTENSION_TRANSFER_HOOK, 1),
+    (EXTENSION_CONFIDENTIAL_TRANSFER_FEE_CONFIG, 64),
+    (EXTENSION_PAUSABLE, 0),
+];
+
+#[cfg(feature = "token2022")]
+pub fn token_account_2022_len(mint: &[u8]) -> usize {
+    let extensions = ACCOUNT_EXTENSIONS
+        .iter()
+        .filter(|(mint_extension, _)| find_extension(mint, *mint_extension).is_some())
+        .map(|(_, len)| TOKEN_2022_EXTENSION_HEADER_LEN + len)
+        .sum::<usize>();
+    if extensions == 0 {
+        return pinocchio_token::state::TokenAccount::LEN;
+    }
+    let len = TOKEN_2022_EXTENSIONS_OFFSET + extensions;
+    if len == MULTISIG_LEN {
+        len + size_of::<u16>()
+    } else {
+        len
+    }
+}
+
 pub struct MintInterface;
 impl AccountCheck for MintInterface {
     fn check(account: &AccountView) -> Result<(), ProgramError> {
-        let is_token_2022 = account.owned_by(&TOKEN_2022_PROGRAM_ID.into());
+        let is_token_2022 = is_token_2022(account);
         let is_spl_token = account.owned_by(&pinocchio_token::ID);
         if !is_token_2022 && !is_spl_token {
             return Err(ProgramError::IllegalOwner);
@@ -341,7 +496,7 @@ impl AccountCheck for MintInterface {
 pub struct TokenAccountInterface;
 impl AccountCheck for TokenAccountInterface {
     fn check(account: &AccountView) -> Result<(), ProgramError> {
-        let is_owned_by_token_2022 = account.owned_by(&TOKEN_2022_PROGRAM_ID.into());
+        let is_owned_by_token_2022 = is_token_2022(account);
         let is_owned_by_spl_token = account.owned_by(&pinocchio_token::ID);
         if !is_owned_by_spl_token && !is_owned_by_token_2022 {
             return Err(ProgramError::IllegalOwner);
@@ -366,6 +521,506 @@ impl AccountCheck for TokenAccountInterface {
     }
 }
 
+const TOKEN_2022_EXTENSIONS_OFFSET: usize = TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET + 1;
+const TOKEN_2022_EXTENSION_HEADER_LEN: usize = 4;
+pub const EXTENSION_TRANSFER_FEE_CONFIG: u16 = 1;
+pub const EXTENSION_MINT_CLOSE_AUTHORITY: u16 = 3;
+pub const EXTENSION_MEMO_TRANSFER: u16 = 8;
+pub const EXTENSION_NON_TRANSFERABLE: u16 = 9;
+pub const EXTENSION_INTEREST_BEARING_CONFIG: u16 = 10;
+pub const EXTENSION_PERMANENT_DELEGATE: u16 = 12;
+pub const EXTENSION_TRANSFER_HOOK: u16 = 14;
+pub const EXTENSION_CONFIDENTIAL_TRANSFER_FEE_CONFIG: u16 = 16;
+pub const EXTENSION_METADATA_POINTER: u16 = 18;
+pub const EXTENSION_GROUP_MEMBER_POINTER: u16 = 22;
+pub const EXTENSION_TOKEN_GROUP_MEMBER: u16 = 23;
+pub const EXTENSION_SCALED_UI_AMOUNT: u16 = 25;
+pub const EXTENSION_PAUSABLE: u16 = 26;
+
+pub fn find_extension(data: &[u8], extension_type: u16) -> Option<&[u8]> {
+    let mut offset = TOKEN_2022_EXTENSIONS_OFFSET;
+    while let Some(header) = read_bytes::<TOKEN_2022_EXTENSION_HEADER_LEN>(data, offset) {
+        let ty = u16::from_le_bytes([header[0], header[1]]);
+        let len = u16::from_le_bytes([header[2], header[3]]) as usize;
+        let start = offset + TOKEN_2022_EXTENSION_HEADER_LEN;
+        let value = data.get(start..start + len).filter(|_| ty != 0)?;
+        if ty == extension_type {
+            return Some(value);
+        }
+        offset = start + len;
+    }
+    None
+}
+
+fn has_authority_extension(data: &[u8], extension_type: u16) -> bool {
+    find_extension(data, extension_type).is_some_and(|authority| authority.iter().any(|b| *b != 0))
+}
+
+fn is_paused_extension(data: &[u8]) -> bool {
+    find_extension(data, EXTENSION_PAUSABLE).is_some_and(|pausable| pausable.get(32) == Some(&1))
+}
+
+pub struct GroupMember {
+    pub group: Address,
+    pub member_number: u64,
+    pub metadata: Address,
+}
+
+pub struct GroupMemberMint;
+impl GroupMemberMint {
+    pub fn parse(mint: &Address, data: &[u8]) -> Result<Option<GroupMember>, ProgramError> {
+        let Some(pointer) = find_extension(data, EXTENSION_GROUP_MEMBER_POINTER) else {
+            return Ok(None);
+        };
+        if pointer.len() != 64 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if pointer.get(32..64) != Some(mint.as_ref()) {
+            return Ok(None);
+        }
+        let member = find_extension(data, EXTENSION_TOKEN_GROUP_MEMBER)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        if member.len() != 72 || member.get(0..32) != Some(mint.as_ref()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let metadata = match find_extension(data, EXTENSION_METADATA_POINTER) {
+            Some(pointer) if pointer.len() == 64 => {
+                Address::new_from_array(account_bytes(pointer, 32)?)
+            }
+            Some(_) => return Err(ProgramError::InvalidAccountData),
+            None => Address::new_from_array([0; 32]),
+        };
+        Ok(Some(GroupMember {
+            group: Address::new_from_array(account_bytes(member, 32)?),
+            member_number: u64::from_le_bytes(account_bytes(member, 64)?),
+            metadata,
+        }))
+    }
+    pub fn membership(mint: &AccountView) -> Result<Option<GroupMember>, ProgramError> {
+        if !is_token_2022(mint) {
+            return Ok(None);
+        }
+        Self::parse(mint.address(), &mint.try_borrow()?)
+    }
+    pub fn emit(escrow: &Address, mint: &AccountView) -> ProgramResult {
+        if !cfg!(feature = "nft") {
+            return Ok(());
+        }
+        if let Some(member) = Self::membership(mint)? {
+            crate::events::GroupMemberEvent {
+                escrow: *escrow,
+                mint: *mint.address(),
+                group: member.group,
+                metadata: member.metadata,
+                member_number: member.member_number,
+            }
+            .emit();
+        }
+        Ok(())
+    }
+}
+
+pub trait MintPolicyCheck {
+    fn check(account: &AccountView, flags: u8) -> Result<(), ProgramError>;
+}
+pub struct MintPolicy;
+impl MintPolicy {
+    #[inline(always)]
+    pub fn check_pair(mint_a: &AccountView, mint_b: &AccountView, flags: u8) -> ProgramResult {
+        Self::check(mint_a, flags)?;
+        Self::check(mint_b, flags)
+    }
+    pub fn is_paused(account: &AccountView) -> Result<bool, ProgramError> {
+        if !is_token_2022(account) {
+            return Ok(false);
+        }
+        let data = account.try_borrow()?;
+        Ok(is_paused_extension(&data))
+    }
+    #[inline(always)]
+    pub fn check_unpaused(mint_a: &AccountView, mint_b: &AccountView) -> ProgramResult {
+        if Self::is_paused(mint_a)? || Self::is_paused(mint_b)? {
+            return Err(EscrowError::MintPaused.into());
+        }
+        Ok(())
+    }
+}
+impl MintPolicyCheck for MintPolicy {
+    fn check(account: &AccountView, flags: u8) -> Result<(), ProgramError> {
+        if !is_token_2022(account) {
+            return Ok(());
+        }
+        let data = account.try_borrow()?;
+        if find_extension(&data, EXTENSION_NON_TRANSFERABLE).is_some() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if is_paused_extension(&data) {
+            return Err(EscrowError::MintPaused.into());
+        }
+        if flags & crate::state::Escrow::ALLOW_PERMANENT_DELEGATE == 0
+            && has_authority_extension(&data, EXTENSION_PERMANENT_DELEGATE)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if flags & crate::state::Escrow::ALLOW_MINT_CLOSE_AUTHORITY == 0
+            && has_authority_extension(&data, EXTENSION_MINT_CLOSE_AUTHORITY)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+}
+
+pub const UI_SCALE_ONE: u128 = 1_000_000_000_000;
+const SECONDS_PER_YEAR: i128 = 31_556_736;
+const MAX_UI_EXPONENT: i128 = 20 * UI_SCALE_ONE as i128;
+
+fn exp_scaled(exponent: i128) -> Option<u128> {
+    let x = exponent.unsigned_abs();
+    if x > MAX_UI_EXPONENT as u128 {
+        return None;
+    }
+    let mut sum = UI_SCALE_ONE;
+    let mut term = UI_SCALE_ONE;
+    for k in 1..64u128 {
+        term = term * x / (UI_SCALE_ONE * k);
+        if term == 0 {
+            break;
+        }
+        sum += term;
+    }
+    Some(if exponent < 0 {
+        UI_SCALE_ONE * UI_SCALE_ONE / sum
+    } else {
+        sum
+    })
+}
+
+pub struct UiAmount;
+impl UiAmount {
+    pub fn interest_scale(config: &[u8], unix_timestamp: i64) -> Result<u128, ProgramError> {
+        if config.len() != 52 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let initialization_timestamp = i64::from_le_bytes(account_bytes(config, 32)?);
+        let pre_update_average_rate = i16::from_le_bytes(account_bytes(config, 40)?);
+        let last_update_timestamp = i64::from_le_bytes(account_bytes(config, 42)?);
+        let current_rate = i16::from_le_bytes(account_bytes(config, 50)?);
+        let pre_update_timespan =
+            last_update_timestamp.saturating_sub(initialization_timestamp) as i128;
+        let post_update_timespan = unix_timestamp.saturating_sub(last_update_timestamp) as i128;
+        let exponent = (pre_update_average_rate as i128 * pre_update_timespan
+            + current_rate as i128 * post_update_timespan)
+            * UI_SCALE_ONE as i128
+            / (SECONDS_PER_YEAR * 10_000);
+        exp_scaled(exponent).ok_or(ProgramError::ArithmeticOverflow)
+    }
+    pub fn multiplier_scale(config: &[u8], unix_timestamp: i64) -> Result<u128, ProgramError> {
+        if config.len() != 56 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let multiplier = f64::from_le_bytes(account_bytes(config, 32)?);
+        let effective_timestamp = i64::from_le_bytes(account_bytes(config, 40)?);
+        let new_multiplier = f64::from_le_bytes(account_bytes(config, 48)?);
+        let multiplier = if unix_timestamp >= effective_timestamp {
+            new_multiplier
+        } else {
+            multiplier
+        };
+        let scale = (multiplier * UI_SCALE_ONE as f64) as u128;
+        if scale == 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(scale)
+    }
+    pub fn scale(mint: &AccountView, unix_timestamp: i64) -> Result<u128, ProgramError> {
+        if !is_token_2022(mint) {
+            return Ok(UI_SCALE_ONE);
+        }
+        let data = mint.try_borrow()?;
+        let interest = match find_extension(&data, EXTENSION_INTEREST_BEARING_CONFIG) {
+            Some(config) => Self::interest_scale(config, unix_timestamp)?,
+            None => UI_SCALE_ONE,
+        };
+        match find_extension(&data, EXTENSION_SCALED_UI_AMOUNT) {
+            Some(config) => interest
+                .checked_mul(Self::multiplier_scale(config, unix_timestamp)?)
+                .map(|scale| scale / UI_SCALE_ONE)
+                .filter(|scale| *scale != 0)
+                .ok_or(ProgramError::ArithmeticOverflow),
+            None => Ok(interest),
+        }
+    }
+    #[inline(always)]
+    pub fn to_ui(amount: u64, scale: u128) -> u64 {
+        (amount as u128 * scale / UI_SCALE_ONE).min(u64::MAX as u128) as u64
+    }
+    #[inline(always)]
+    pub fn from_ui(ui_amount: u64, scale: u128) -> Result<u64, ProgramError> {
+        (ui_amount as u128 * UI_SCALE_ONE)
+            .div_ceil(scale)
+            .try_into()
+            .map_err(|_| ProgramError::ArithmeticOverflow)
+    }
+}
+
+pub const PRICE_SCALE_ONE: u128 = 1_000_000_000;
+
+pub struct PriceBounds;
+impl PriceBounds {
+    pub fn ui_price(receive: u64, amount: u64, decimals_a: u8, decimals_b: u8) -> Option<u128> {
+        let (numerator, denominator) = if decimals_a >= decimals_b {
+            (
+                10u128.checked_pow((decimals_a - decimals_b) as u32)?,
+                amount as u128,
+            )
+        } else {
+            (
+                1,
+                10u128
+                    .checked_pow((decimals_b - decimals_a) as u32)?
+                    .checked_mul(amount as u128)?,
+            )
+        };
+        (receive as u128)
+            .checked_mul(numerator)?
+            .checked_mul(PRICE_SCALE_ONE)?
+            .checked_div(denominator)
+    }
+    pub fn check(
+        mint_a: &AccountView,
+        mint_b: &AccountView,
+        receive: u64,
+        amount: u64,
+        min_price: u64,
+        max_price: u64,
+    ) -> ProgramResult {
+        if min_price == 0 && max_price == 0 {
+            return Ok(());
+        }
+        let price = Self::ui_price(
+            receive,
+            amount,
+            TokenInterface::get_decimals(mint_a)?,
+            TokenInterface::get_decimals(mint_b)?,
+        )
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+        if price < min_price as u128 || (max_price != 0 && price > max_price as u128) {
+            return Err(EscrowError::PriceOutOfBounds.into());
+        }
+        Ok(())
+    }
+}
+
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+const TOKEN_ACCOUNT_DELEGATE_OFFSET: usize = 72;
+const TOKEN_ACCOUNT_STATE_OFFSET: usize = 108;
+const TOKEN_ACCOUNT_CLOSE_AUTHORITY_OFFSET: usize = 129;
+const TOKEN_ACCOUNT_STATE_FROZEN: u8 = 2;
+const MINT_DECIMALS_OFFSET: usize = 44;
+const TRANSFER_CHECKED_DISCRIMINATOR: u8 = 12;
+const CLOSE_ACCOUNT_DISCRIMINATOR: u8 = 9;
+const BURN_CHECKED_DISCRIMINATOR: u8 = 15;
+const SET_AUTHORITY_DISCRIMINATOR: u8 = 6;
+const AUTHORITY_TYPE_ACCOUNT_OWNER: u8 = 2;
+
+pub struct TokenInterface;
+impl TokenInterface {
+    #[inline(always)]
+    pub fn program_id(account: &AccountView) -> Result<Address, ProgramError> {
+        if account.owned_by(&pinocchio_token::ID) {
+            Ok(pinocchio_token::ID)
+        } else if is_token_2022(account) {
+            Ok(TOKEN_2022_PROGRAM_ID.into())
+        } else {
+            Err(ProgramError::IncorrectProgramId)
+        }
+    }
+    pub fn get_amount(account: &AccountView) -> Result<u64, ProgramError> {
+        Self::program_id(account)?;
+        let data = account.try_borrow()?;
+        if data.len() < pinocchio_token::state::TokenAccount::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(u64::from_le_bytes(account_bytes(
+            &data,
+            TOKEN_ACCOUNT_AMOUNT_OFFSET,
+        )?))
+    }
+    pub fn get_mint(account: &AccountView) -> Result<Address, ProgramError> {
+        Self::program_id(account)?;
+        let data = account.try_borrow()?;
+        if data.len() < pinocchio_token::state::TokenAccount::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Address::new_from_array(account_bytes(&data, 0)?))
+    }
+    pub fn get_owner(account: &AccountView) -> Result<Address, ProgramError> {
+        Self::program_id(account)?;
+        let data = account.try_borrow()?;
+        if data.len() < pinocchio_token::state::TokenAccount::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Address::new_from_array(account_bytes(&data, 32)?))
+    }
+    pub fn get_delegate(account: &AccountView) -> Result<Option<Address>, ProgramError> {
+        if !Self::has_delegate(account)? {
+            return Ok(None);
+        }
+        let data = account.try_borrow()?;
+        Ok(Some(Address::new_from_array(account_bytes(
+            &data,
+            TOKEN_ACCOUNT_DELEGATE_OFFSET + 4,
+        )?)))
+    }
+    pub fn has_delegate(account: &AccountView) -> Result<bool, ProgramError> {
+        Self::has_option(account, TOKEN_ACCOUNT_DELEGATE_OFFSET)
+    }
+    pub fn has_close_authority(account: &AccountView) -> Result<bool, ProgramError> {
+        Self::has_option(account, TOKEN_ACCOUNT_CLOSE_AUTHORITY_OFFSET)
+    }
+    pub fn is_frozen(account: &AccountView) -> Result<bool, ProgramError> {
+        Self::program_id(account)?;
+        let data = account.try_borrow()?;
+        if data.len() < pinocchio_token::state::TokenAccount::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(data.get(TOKEN_ACCOUNT_STATE_OFFSET) == Some(&TOKEN_ACCOUNT_STATE_FROZEN))
+    }
+    pub fn requires_memo(account: &AccountView) -> Result<bool, ProgramError> {
+        if !is_token_2022(account) {
+            return Ok(false);
+        }
+        let data = account.try_borrow()?;
+        Ok(find_extension(&data, EXTENSION_MEMO_TRANSFER)
+            .is_some_and(|memo| memo.first() == Some(&1)))
+    }
+    fn has_option(account: &AccountView, offset: usize) -> Result<bool, ProgramError> {
+        Self::program_id(account)?;
+        let data = account.try_borrow()?;
+        if data.len() < pinocchio_token::state::TokenAccount::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(account_bytes::<4>(&data, offset)?.iter().any(|b| *b != 0))
+    }
+    pub fn get_decimals(mint: &AccountView) -> Result<u8, ProgramError> {
+        Self::program_id(mint)?;
+        let data = mint.try_borrow()?;
+        if data.len() < pinocchio_token::state::Mint::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        data.get(MINT_DECIMALS_OFFSET)
+            .copied()
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+    pub fn transfer_checked(
+        from: &AccountView,
+        mint: &AccountView,
+        to: &AccountView,
+        authority: &AccountView,
+        amount: u64,
+        signers: &[Signer],
+    ) -> ProgramResult {
+        let program_id = Self::program_id(from)?;
+        let decimals = Self::get_decimals(mint)?;
+        let mut data = [0u8; 10];
+        data[0] = TRANSFER_CHECKED_DISCRIMINATOR;
+        data[1..9].copy_from_slice(&amount.to_le_bytes());
+        data[9] = decimals;
+        let instruction_accounts = [
+            InstructionAccount::writable(from.address()),
+            InstructionAccount::readonly(mint.address()),
+            InstructionAccount::writable(to.address()),
+            InstructionAccount::readonly_signer(authority.address()),
+        ];
+        invoke_signed(
+            &InstructionView {
+                program_id: &program_id,
+                accounts: &instruction_accounts,
+                data: &data,
+            },
+            &[from, mint, to, authority],
+            signers,
+        )
+    }
+    pub fn burn_checked(
+        account: &AccountView,
+        mint: &AccountView,
+        authority: &AccountView,
+        amount: u64,
+        signers: &[Signer],
+    ) -> ProgramResult {
+        let program_id = Self::program_id(account)?;
+        let decimals = Self::get_decimals(mint)?;
+        let mut data = [0u8; 10];
+        data[0] = BURN_CHECKED_DISCRIMINATOR;
+        data[1..9].copy_from_slice(&amount.to_le_bytes());
+        data[9] = decimals;
+        let instruction_accounts = [
+            InstructionAccount::writable(account.address()),
+            InstructionAccount::writable(mint.address()),
+            InstructionAccount::readonly_signer(authority.address()),
+        ];
+        invoke_signed(
+            &InstructionView {
+                program_id: &program_id,
+                accounts: &instruction_accounts,
+                data: &data,
+            },
+            &[account, mint, authority],
+            signers,
+        )
+    }
+    pub fn set_owner(
+        account: &AccountView,
+        authority: &AccountView,
+        new_owner: &Address,
+        signers: &[Signer],
+    ) -> ProgramResult {
+        let program_id = Self::program_id(account)?;
+        let mut data = [0u8; 35];
+        data[0] = SET_AUTHORITY_DISCRIMINATOR;
+        data[1] = AUTHORITY_TYPE_ACCOUNT_OWNER;
+        data[2] = 1;
+        data[3..35].copy_from_slice(new_owner.as_ref());
+        let instruction_accounts = [
+            InstructionAccount::writable(account.address()),
+            InstructionAccount::readonly_signer(authority.address()),
+        ];
+        invoke_signed(
+            &InstructionView {
+                program_id: &program_id,
+                accounts: &instruction_accounts,
+                data: &data,
+            },
+            &[account, authority],
+            signers,
+        )
+    }
+    pub fn close_account(
+        account: &AccountView,
+        destination: &AccountView,
+        authority: &AccountView,
+        signers: &[Signer],
+    ) -> ProgramResult {
+        let program_id = Self::program_id(account)?;
+        let instruction_accounts = [
+            InstructionAccount::writable(account.address()),
+            InstructionAccount::writable(destination.address()),
+            InstructionAccount::readonly_signer(authority.address()),
+        ];
+        invoke_signed(
+            &InstructionView {
+                program_id: &program_id,
+                accounts: &instruction_accounts,
+                data: &[CLOSE_ACCOUNT_DISCRIMINATOR],
+            },
+            &[account, destination, authority],
+            signers,
+        )
+    }
+}
+
 pub trait AssociatedTokenAccountCheck {
     fn check(
         account: &AccountView,
@@ -382,7 +1037,7 @@ impl AssociatedTokenAccountCheck for AssociatedTokenAccount {
         mint: &AccountView,
         token_program: &AccountView,
     ) -> Result<(), ProgramError> {
-        TokenAccount::check(account)?;
+        TokenAccountInterface::check(account)?;
         if Address::find_program_address(
             &[
                 authority.address().as_ref(),
@@ -399,6 +1054,139 @@ impl AssociatedTokenAccountCheck for AssociatedTokenAccount {
         Ok(())
     }
 }
+pub struct EscrowVault;
+impl AssociatedTokenAccountCheck for EscrowVault {
+    fn check(
+        account: &AccountView,
+        authority: &AccountView,
+        mint: &AccountView,
+        token_program: &AccountView,
+    ) -> Result<(), ProgramError> {
+        let vault = {
+            let data = authority.try_borrow()?;
+            crate::state::Escrow::load(&data)?.vault
+        };
+        if cfg!(feature = "strict") || vault.eq(&Address::default()) {
+            AssociatedTokenAccount::check(account, authority, mint, token_program)?;
+        } else {
+            TokenAccountInterface::check(account)?;
+            if vault.ne(account.address()) {
+                return Err(ProgramError::InvalidArgument);
+            }
+        }
+        #[cfg(feature = "strict")]
+        if TokenInterface::has_delegate(account)? || TokenInterface::has_close_authority(account)? {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct MakerAccount<'a>(&'a AccountView);
+impl<'a> MakerAccount<'a> {
+    pub fn signer(account: &'a AccountView) -> Result<Self, ProgramError> {
+        SignerAccount::check(account)?;
+        Ok(Self(account))
+    }
+    pub fn of_escrow(account: &'a AccountView, escrow: EscrowPda) -> Result<Self, ProgramError> {
+        let data = escrow.try_borrow()?;
+        if crate::state::Escrow::load(&data)?
+            .maker
+            .ne(account.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Self(account))
+    }
+    pub fn account(self) -> &'a AccountView {
+        self.0
+    }
+}
+impl Deref for MakerAccount<'_> {
+    type Target = AccountView;
+    fn deref(&self) -> &AccountView {
+        self.0
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct EscrowPda<'a>(&'a AccountView);
+impl<'a> EscrowPda<'a> {
+    pub fn check(account: &'a AccountView) -> Result<Self, ProgramError> {
+        ProgramAccount::check(account)?;
+        Ok(Self(account))
+    }
+    pub fn uninitialized(account: &'a AccountView) -> Result<Self, ProgramError> {
+        if !account.is_data_empty() || account.owned_by(&crate::ID) {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        SystemAccount::check(account)?;
+        Ok(Self(account))
+    }
+    pub fn account(self) -> &'a AccountView {
+        self.0
+    }
+}
+impl Deref for EscrowPda<'_> {
+    type Target = AccountView;
+    fn deref(&self) -> &AccountView {
+        self.0
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct VaultAccount<'a>(&'a AccountView);
+impl<'a> VaultAccount<'a> {
+    pub fn check(
+        account: &'a AccountView,
+        escrow: EscrowPda,
+        mint: &AccountView,
+        token_program: &AccountView,
+    ) -> Result<Self, ProgramError> {
+        EscrowVault::check(account, &escrow, mint, token_program)?;
+        Ok(Self(account))
+    }
+    pub fn uninitialized(
+        account: &'a AccountView,
+        escrow: EscrowPda,
+        mint: &AccountView,
+        token_program: &AccountView,
+    ) -> Result<Self, ProgramError> {
+        let (vault_key, _) = Address::find_program_address(
+            &[
+                escrow.address().as_ref(),
+                token_program.address().as_ref(),
+                mint.address().as_ref(),
+            ],
+            &pinocchio_associated_token_account::ID,
+        );
+        if account.address().ne(&vault_key) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if account.is_data_empty() {
+            SystemAccount::check(account)?;
+            return Ok(Self(account));
+        }
+        if OwnedTokenAccount::check(account, &escrow, mint).is_err()
+            || TokenInterface::get_amount(account)? != 0
+            || TokenInterface::has_delegate(account)?
+            || TokenInterface::has_close_authority(account)?
+        {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        Ok(Self(account))
+    }
+    pub fn account(self) -> &'a AccountView {
+        self.0
+    }
+}
+impl Deref for VaultAccount<'_> {
+    type Target = AccountView;
+    fn deref(&self) -> &AccountView {
+        self.0
+    }
+}
 pub trait AssociatedTokenAccountInit {
     fn init(
         account: &AccountView,
@@ -462,8 +1250,8 @@ impl AssociatedTokenAccountInit for AssociatedTokenAccount {
         system_program: &AccountView,
         token_program: &AccountView,
     ) -> ProgramResult {
-        match Self::check(account, payer, mint, token_program) {
-            Ok(_) => Ok(()),
+        match Self::check(account, owner, mint, token_program) {
+            Ok(_) => OwnedTokenAccount::check(account, owner, mint),
             Err(_) => Self::init(account, mint, payer, owner, system_program, token_program),
         }
     }
@@ -495,8 +1283,8 @@ impl AssociatedTokenAccountInit for AssociatedTokenAccount {
         token_program: &AccountView,
         signer: &[Signer],
     ) -> ProgramResult {
-        match Self::check(account, payer, mint, token_program) {
-            Ok(_) => Ok(()),
+        match Self::check(account, owner, mint, token_program) {
+            Ok(_) => OwnedTokenAccount::check(account, owner, mint),
             Err(_) => Self::init_signed(
                 account,
                 mint,
@@ -516,14 +1304,583 @@ impl AccountCheck for ProgramAccount {
         if !account.owned_by(&crate::ID) {
             return Err(ProgramError::InvalidAccountOwner);
         }
-        if account.data_len().ne(&crate::state::Escrow::LEN) {
+        let data = account.try_borrow()?;
+        if data.first() == Some(&CLOSED_ACCOUNT_DISCRIMINATOR) {
             return Err(ProgramError::InvalidAccountData);
         }
-        Ok(())
-    }
+        if data.len().ne(&crate::state::Escrow::LEN) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if data.first() != Some(&crate::state::Escrow::DISCRIMINATOR)
+            || data.get(1) != Some(&crate::state::Escrow::VERSION)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+}
+pub type BidAccount = And<OwnedBy<EscrowProgram>, LenEq<{ crate::state::AuctionBid::LEN }>>;
+pub type CounterOfferAccount =
+    And<OwnedBy<EscrowProgram>, LenEq<{ crate::state::CounterOffer::LEN }>>;
+pub type ConfigAccount = And<OwnedBy<EscrowProgram>, LenEq<{ crate::state::Config::LEN }>>;
+pub fn check_program_pda(account: &AccountView, seeds: &[&[u8]]) -> ProgramResult {
+    let key = Address::create_program_address(seeds, &crate::ID)
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+    if key.ne(account.address()) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    Ok(())
+}
+pub struct ConditionAccount;
+impl ConditionAccount {
+    pub fn check_met(
+        condition: &AccountView,
+        account: &AccountView,
+        escrow: &AccountView,
+    ) -> ProgramResult {
+        if !condition.owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if condition.data_len().ne(&crate::state::EscrowCondition::LEN) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let data = condition.try_borrow()?;
+        let condition = crate::state::EscrowCondition::load(&data)?;
+        if condition.escrow.ne(escrow.address()) || condition.account.ne(account.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !account.owned_by(&condition.program) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if !condition.is_met(&account.try_borrow()?) {
+            return Err(EscrowError::ConditionNotMet.into());
+        }
+        Ok(())
+    }
+}
+pub struct ValueFloorAccount;
+impl ValueFloorAccount {
+    pub fn check_met(
+        value_floor: &AccountView,
+        oracle: &AccountView,
+        escrow: &AccountView,
+        mint_a: &AccountView,
+        mint_b: &AccountView,
+        amount: u64,
+        receive: u64,
+    ) -> ProgramResult {
+        if !value_floor.owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if value_floor.data_len().ne(&crate::state::ValueFloor::LEN) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let data = value_floor.try_borrow()?;
+        let value_floor = crate::state::ValueFloor::load(&data)?;
+        if value_floor.escrow.ne(escrow.address()) || value_floor.oracle.ne(oracle.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !oracle.owned_by(&value_floor.program) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        let oracle_data = oracle.try_borrow()?;
+        if value_floor.max_age != 0 {
+            let timestamp = i64::from_le_bytes(account_bytes(
+                &oracle_data,
+                value_floor.timestamp_offset as usize,
+            )?);
+            if Clock::get()?.unix_timestamp.saturating_sub(timestamp) > value_floor.max_age as i64 {
+                return Err(EscrowError::StaleOracle.into());
+            }
+        }
+        let oracle_price = value_floor
+            .scaled_price(
+                u64::from_le_bytes(account_bytes(
+                    &oracle_data,
+                    value_floor.price_offset as usize,
+                )?),
+                PRICE_SCALE_ONE,
+            )
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let ui_price = PriceBounds::ui_price(
+            receive,
+            amount,
+            TokenInterface::get_decimals(mint_a)?,
+            TokenInterface::get_decimals(mint_b)?,
+        )
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+        if !value_floor.is_met(ui_price, oracle_price) {
+            return Err(EscrowError::BelowValueFloor.into());
+        }
+        Ok(())
+    }
+}
+pub struct AllowlistAccount;
+impl AllowlistAccount {
+    pub const PREFIX: &'static [u8] = b"allowlist";
+    pub fn check(pass: &AccountView, program: &Address, taker: &AccountView) -> ProgramResult {
+        let (pass_key, _) =
+            Address::find_program_address(&[Self::PREFIX, taker.address().as_ref()], program);
+        if pass_key.ne(pass.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if !pass.owned_by(program) || pass.is_data_empty() {
+            return Err(EscrowError::TakerNotAllowed.into());
+        }
+        Ok(())
+    }
+}
+pub struct AddressListAccount;
+impl AddressListAccount {
+    pub fn check(account: &AccountView, prefix: &[u8]) -> ProgramResult {
+        if !account.owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        let (list_key, _) = Address::find_program_address(&[prefix], &crate::ID);
+        if list_key.ne(account.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        Ok(())
+    }
+    pub fn init_if_needed(list: &AccountView, payer: &AccountView, prefix: &[u8]) -> ProgramResult {
+        if !list.is_data_empty() {
+            return Self::check(list, prefix);
+        }
+        let (list_key, bump) = Address::find_program_address(&[prefix], &crate::ID);
+        if list_key.ne(list.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let bump_binding = [bump];
+        let seeds = [Seed::from(prefix), Seed::from(&bump_binding)];
+        create_account_with_minimum_balance_signed(
+            list,
+            crate::state::AddressList::LEN,
+            &crate::ID,
+            payer,
+            None,
+            &[Signer::from(&seeds)],
+        )?;
+        let mut data = list.try_borrow_mut()?;
+        crate::state::AddressList::load_mut(data.as_mut())?.bump = bump_binding;
+        Ok(())
+    }
+    pub fn insert(list: &AccountView, payer: &AccountView, address: &Address) -> ProgramResult {
+        let (len, index) = {
+            let data = list.try_borrow()?;
+            let entries = crate::state::AddressList::entries(&data)?;
+            match entries.binary_search(address) {
+                Ok(_) => return Err(ProgramError::InvalidArgument),
+                Err(index) => (entries.len(), index),
+            }
+        };
+
+        let space = crate::state::AddressList::space(len + 1);
+        let lamports = Rent::get()?
+            .try_minimum_balance(space)?
+            .saturating_sub(list.lamports());
+        if lamports > 0 {
+            Transfer {
+                from: payer,
+                to: list,
+                lamports,
+            }
+            .invoke()?;
+        }
+        list.resize(space)?;
+
+        let mut data = list.try_borrow_mut()?;
+        let start = crate::state::AddressList::space(index);
+        data.copy_within(
+            start..crate::state::AddressList::space(len),
+            start + size_of::<Address>(),
+        );
+        data.get_mut(start..start + size_of::<Address>())
+            .ok_or(ProgramError::AccountDataTooSmall)?
+            .copy_from_slice(address.as_ref());
+        crate::state::AddressList::load_mut(&mut data[..crate::state::AddressList::LEN])?.len += 1;
+        Ok(())
+    }
+    pub fn remove(
+        list: &AccountView,
+        recipient: &AccountView,
+        address: &Address,
+    ) -> Result<usize, ProgramError> {
+        let (len, index) = {
+            let data = list.try_borrow()?;
+            let entries = crate::state::AddressList::entries(&data)?;
+            match entries.binary_search(address) {
+                Ok(index) => (entries.len(), index),
+                Err(_) => return Err(ProgramError::InvalidArgument),
+            }
+        };
+
+        {
+            let mut data = list.try_borrow_mut()?;
+            let start = crate::state::AddressList::space(index);
+            data.copy_within(
+                start + size_of::<Address>()..crate::state::AddressList::space(len),
+                start,
+            );
+            crate::state::AddressList::load_mut(&mut data[..crate::state::AddressList::LEN])?
+                .len -= 1;
+        }
+        let space = crate::state::AddressList::space(len - 1);
+        list.resize(space)?;
+
+        let surplus = list
+            .lamports()
+            .saturating_sub(Rent::get()?.try_minimum_balance(space)?);
+        if surplus > 0 {
+            list.set_lamports(list.lamports() - surplus);
+            let recipient_lamports = recipient
+                .lamports()
+                .checked_add(surplus)
+                .ok_or(EscrowError::LamportOverflow)?;
+            recipient.set_lamports(recipient_lamports);
+        }
+        Ok(len - 1)
+    }
+}
+
+pub struct DenylistAccount;
+impl AccountCheck for DenylistAccount {
+    fn check(account: &AccountView) -> Result<(), ProgramError> {
+        AddressListAccount::check(account, crate::state::AddressList::DENYLIST_PREFIX)
+    }
+}
+impl DenylistAccount {
+    pub fn is_enabled(config: &AccountView) -> Result<bool, ProgramError> {
+        let data = config.try_borrow()?;
+        Ok(crate::state::Config::load(&data)?.has_denylist())
+    }
+    pub fn check_parties(denylist: &AccountView, parties: &[&Address]) -> ProgramResult {
+        Self::check(denylist)?;
+        let data = denylist.try_borrow()?;
+        let entries = crate::state::AddressList::entries(&data)?;
+        if parties
+            .iter()
+            .any(|party| entries.binary_search(party).is_ok())
+        {
+            return Err(EscrowError::Denylisted.into());
+        }
+        Ok(())
+    }
+}
+
+pub const TRANSACTION_LEVEL_STACK_HEIGHT: u64 = 1;
+
+#[inline(always)]
+pub fn stack_height() -> u64 {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        pinocchio::syscalls::sol_get_stack_height()
+    }
+    #[cfg(not(target_os = "solana"))]
+    TRANSACTION_LEVEL_STACK_HEIGHT
+}
+
+pub struct RouterListAccount;
+impl AccountCheck for RouterListAccount {
+    fn check(account: &AccountView) -> Result<(), ProgramError> {
+        AddressListAccount::check(account, crate::state::AddressList::ROUTER_LIST_PREFIX)
+    }
+}
+
+pub struct CallerPolicy;
+impl CallerPolicy {
+    pub fn mode(config: &AccountView) -> Result<u8, ProgramError> {
+        let data = config.try_borrow()?;
+        Ok(crate::state::Config::load(&data)?.caller_policy)
+    }
+    pub fn check<'a>(
+        config: &AccountView,
+        accounts: &mut core::slice::Iter<'a, AccountView>,
+    ) -> ProgramResult {
+        let routers = match Self::mode(config)? {
+            crate::state::Config::CALLER_ANY => return Ok(()),
+            crate::state::Config::CALLER_ROUTERS => {
+                Some((next_account(accounts)?, next_account(accounts)?))
+            }
+            _ => None,
+        };
+        let height = stack_height();
+        if height == TRANSACTION_LEVEL_STACK_HEIGHT {
+            return Ok(());
+        }
+        let Some((instructions, router_list)) = routers else {
+            return Err(EscrowError::CallerNotAllowed.into());
+        };
+        if height != TRANSACTION_LEVEL_STACK_HEIGHT + 1 {
+            return Err(EscrowError::CallerNotAllowed.into());
+        }
+        RouterListAccount::check(router_list)?;
+        let instructions = Instructions::try_from(instructions)?;
+        let current =
+            instructions.load_instruction_at(instructions.load_current_index() as usize)?;
+        let data = router_list.try_borrow()?;
+        if crate::state::AddressList::entries(&data)?
+            .binary_search(current.get_program_id())
+            .is_err()
+        {
+            return Err(EscrowError::CallerNotAllowed.into());
+        }
+        Ok(())
+    }
+}
+
+pub struct MintListAccount;
+impl AccountCheck for MintListAccount {
+    fn check(account: &AccountView) -> Result<(), ProgramError> {
+        AddressListAccount::check(account, crate::state::AddressList::MINT_LIST_PREFIX)
+    }
+}
+impl MintListAccount {
+    pub fn mode(config: &AccountView) -> Result<u8, ProgramError> {
+        let data = config.try_borrow()?;
+        Ok(crate::state::Config::load(&data)?.mint_list)
+    }
+    pub fn check_pair(
+        mint_list: &AccountView,
+        mode: u8,
+        mint_a: &AccountView,
+        mint_b: &AccountView,
+    ) -> ProgramResult {
+        Self::check(mint_list)?;
+        let data = mint_list.try_borrow()?;
+        let entries = crate::state::AddressList::entries(&data)?;
+        let listed = |mint: &AccountView| entries.binary_search(mint.address()).is_ok();
+        let allowed = match mode {
+            crate::state::Config::MINT_LIST_ALLOW => listed(mint_a) && listed(mint_b),
+            _ => !listed(mint_a) && !listed(mint_b),
+        };
+        if !allowed {
+            return Err(EscrowError::MintNotAllowed.into());
+        }
+        Ok(())
+    }
+}
+
+pub struct MakerIndexAccount;
+impl MakerIndexAccount {
+    pub fn max_open_escrows(config: &AccountView) -> Result<u16, ProgramError> {
+        let data = config.try_borrow()?;
+        Ok(crate::state::Config::load(&data)?.max_open_escrows)
+    }
+    pub fn release(
+        escrow: &crate::state::Escrow,
+        maker_index: Option<&AccountView>,
+    ) -> ProgramResult {
+        if !escrow.is_indexed() {
+            return Ok(());
+        }
+        let maker_index = maker_index.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if !maker_index.owned_by(&crate::ID)
+            || maker_index.data_len().ne(&crate::state::MakerNonce::LEN)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut data = maker_index.try_borrow_mut()?;
+        let nonce = crate::state::MakerNonce::load_mut(data.as_mut())?;
+        if nonce.maker.ne(&escrow.creator) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        check_program_pda(
+            maker_index,
+            &[
+                crate::state::MakerNonce::PREFIX,
+                nonce.maker.as_ref(),
+                &nonce.bump,
+            ],
+        )?;
+        nonce.release();
+        Ok(())
+    }
+}
+pub struct OrderNonceAccount;
+impl OrderNonceAccount {
+    pub fn init_if_needed(
+        account: &AccountView,
+        maker: &Address,
+        payer: &AccountView,
+    ) -> ProgramResult {
+        let (nonce_key, bump) = Address::find_program_address(
+            &[crate::state::OrderNonce::PREFIX, maker.as_ref()],
+            &crate::ID,
+        );
+        if account.address().ne(&nonce_key) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if account.is_data_empty() {
+            let bump_binding = [bump];
+            let seeds = [
+                Seed::from(crate::state::OrderNonce::PREFIX),
+                Seed::from(maker.as_ref()),
+                Seed::from(&bump_binding),
+            ];
+            create_account_with_minimum_balance_signed(
+                account,
+                crate::state::OrderNonce::LEN,
+                &crate::ID,
+                payer,
+                None,
+                &[Signer::from(&seeds)],
+            )?;
+            let mut data = account.try_borrow_mut()?;
+            crate::state::OrderNonce::load_mut(data.as_mut())?.set_inner(*maker, bump_binding);
+        } else if !account.owned_by(&crate::ID)
+            || account.data_len().ne(&crate::state::OrderNonce::LEN)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        RentExemptAccount::check(account)
+    }
+}
+pub struct StatsAccount;
+impl StatsAccount {
+    pub fn is_stats(account: &AccountView) -> bool {
+        account.owned_by(&crate::ID)
+            && account.data_len() == crate::state::EscrowStats::LEN
+            && account
+                .try_borrow()
+                .is_ok_and(|data| data.first() == Some(&crate::state::EscrowStats::DISCRIMINATOR))
+    }
+    pub fn check(account: &AccountView) -> ProgramResult {
+        if !Self::is_stats(account) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+    pub fn record_created(account: &AccountView) -> ProgramResult {
+        let mut data = account.try_borrow_mut()?;
+        crate::state::EscrowStats::load_mut(data.as_mut())?.record_created();
+        Ok(())
+    }
+    pub fn record_fill(
+        account: &AccountView,
+        escrow: &crate::state::Escrow,
+        amount_a: u64,
+        amount_b: u64,
+    ) -> ProgramResult {
+        let mut data = account.try_borrow_mut()?;
+        crate::state::EscrowStats::load_mut(data.as_mut())?.record_fill(
+            &escrow.mint_a,
+            amount_a,
+            &escrow.mint_b,
+            amount_b,
+        );
+        Ok(())
+    }
+}
+pub struct ProceedsSplitAccount;
+impl ProceedsSplitAccount {
+    pub fn check(
+        split: &AccountView,
+        escrow: &AccountView,
+        recipients: &[AccountView],
+    ) -> ProgramResult {
+        if !split.owned_by(&crate::ID) || split.data_len().ne(&crate::state::ProceedsSplit::LEN) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let data = split.try_borrow()?;
+        let split = crate::state::ProceedsSplit::load(&data)?;
+        if split.escrow.ne(escrow.address())
+            || split.recipients().len() != recipients.len()
+            || split
+                .recipients()
+                .iter()
+                .zip(recipients)
+                .any(|(recipient, account)| recipient.ne(account.address()))
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+    pub fn close(
+        split: &AccountView,
+        escrow: &AccountView,
+        destination: &AccountView,
+    ) -> ProgramResult {
+        if !split.owned_by(&crate::ID) || split.data_len().ne(&crate::state::ProceedsSplit::LEN) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        {
+            let data = split.try_borrow()?;
+            if crate::state::ProceedsSplit::load(&data)?
+                .escrow
+                .ne(escrow.address())
+            {
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+        ProgramAccount::close(split, destination)
+    }
+}
+pub struct ReleaseApprovalsAccount;
+impl AccountCheck for ReleaseApprovalsAccount {
+    fn check(account: &AccountView) -> Result<(), ProgramError> {
+        if !account.owned_by(&crate::ID)
+            || account.data_len().ne(&crate::state::ReleaseApprovals::LEN)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+}
+impl ReleaseApprovalsAccount {
+    pub fn check_released(approvals: &AccountView, escrow: &AccountView) -> ProgramResult {
+        Self::check(approvals)?;
+        let data = approvals.try_borrow()?;
+        let approvals = crate::state::ReleaseApprovals::load(&data)?;
+        if approvals.escrow.ne(escrow.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !approvals.is_released() {
+            return Err(EscrowError::ReleaseNotApproved.into());
+        }
+        Ok(())
+    }
+    pub fn close(
+        approvals: &AccountView,
+        escrow: &AccountView,
+        destination: &AccountView,
+    ) -> ProgramResult {
+        Self::check(approvals)?;
+        {
+            let data = approvals.try_borrow()?;
+            if crate::state::ReleaseApprovals::load(&data)?
+                .escrow
+                .ne(escrow.address())
+            {
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+        ProgramAccount::close(approvals, destination)
+    }
+}
+pub fn close_escrow_extensions<'a>(
+    escrow: &crate::state::Escrow,
+    escrow_account: &AccountView,
+    accounts: &mut impl Iterator<Item = &'a AccountView>,
+    destination: &AccountView,
+) -> ProgramResult {
+    if escrow.has_split() {
+        let split = accounts.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+        ProceedsSplitAccount::close(split, escrow_account, destination)?;
+    }
+    if escrow.is_release_gated() {
+        let approvals = accounts.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+        ReleaseApprovalsAccount::close(approvals, escrow_account, destination)?;
+    }
+    Ok(())
+}
+pub fn next_seq(config: &AccountView) -> Result<u64, ProgramError> {
+    let mut data = config.try_borrow_mut()?;
+    crate::state::Config::load_mut(data.as_mut())?
+        .next_seq()
+        .ok_or(ProgramError::ArithmeticOverflow)
 }
 pub trait ProgramAccountInit {
-    fn init<'a, T: Sized>(
+    fn init<T: Sized>(
         payer: &AccountView,
         account: &AccountView,
         rent: &AccountView,
@@ -532,7 +1889,7 @@ pub trait ProgramAccountInit {
     ) -> ProgramResult;
 }
 impl ProgramAccountInit for ProgramAccount {
-    fn init<'a, T: Sized>(
+    fn init<T: Sized>(
         payer: &AccountView,
         account: &AccountView,
         rent: &AccountView,
@@ -551,6 +1908,749 @@ impl ProgramAccountInit for ProgramAccount {
         Ok(())
     }
 }
+pub trait OwnedTokenAccountCheck {
+    fn check(
+        account: &AccountView,
+        owner: &AccountView,
+        mint: &AccountView,
+    ) -> Result<(), ProgramError>;
+}
+pub struct OwnedTokenAccount;
+impl OwnedTokenAccountCheck for OwnedTokenAccount {
+    fn check(
+        account: &AccountView,
+        owner: &AccountView,
+        mint: &AccountView,
+    ) -> Result<(), ProgramError> {
+        TokenAccountInterface::check(account)?;
+        if TokenInterface::get_mint(account)?.ne(mint.address())
+            || TokenInterface::get_owner(account)?.ne(owner.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+}
+
+pub struct DelegatedTokenAccount;
+impl OwnedTokenAccountCheck for DelegatedTokenAccount {
+    fn check(
+        account: &AccountView,
+        delegate: &AccountView,
+        mint: &AccountView,
+    ) -> Result<(), ProgramError> {
+        TokenAccountInterface::check(account)?;
+        if TokenInterface::get_mint(account)?.ne(mint.address())
+            || TokenInterface::get_delegate(account)?.as_ref() != Some(delegate.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+}
+
+pub struct SourceTokenAccount;
+impl OwnedTokenAccountCheck for SourceTokenAccount {
+    fn check(
+        account: &AccountView,
+        authority: &AccountView,
+        mint: &AccountView,
+    ) -> Result<(), ProgramError> {
+        if TokenInterface::get_owner(account)?.eq(authority.address()) {
+            OwnedTokenAccount::check(account, authority, mint)
+        } else {
+            DelegatedTokenAccount::check(account, authority, mint)
+        }
+    }
+}
+
+pub fn init_destination_if_needed(
+    account: &AccountView,
+    mint: &AccountView,
+    payer: &AccountView,
+    owner: &AccountView,
+    system_program: &AccountView,
+    token_program: &AccountView,
+) -> ProgramResult {
+    if account.is_data_empty() {
+        AssociatedTokenAccount::init(account, mint, payer, owner, system_program, token_program)?;
+    } else {
+        OwnedTokenAccount::check(account, owner, mint)?;
+    }
+    #[cfg(feature = "strict")]
+    AssociatedTokenAccount::check(account, owner, mint, token_program)?;
+    Ok(())
+}
+
+pub fn init_proceeds_if_needed(
+    escrow: &crate::state::Escrow,
+    account: &AccountView,
+    mint: &AccountView,
+    payer: &AccountView,
+    maker: &AccountView,
+    system_program: &AccountView,
+    token_program: &AccountView,
+) -> ProgramResult {
+    if escrow.mint_b.ne(mint.address()) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if escrow.has_proceeds() {
+        if escrow.proceeds.ne(account.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        return Ok(());
+    }
+    init_destination_if_needed(account, mint, payer, maker, system_program, token_program)?;
+    #[cfg(not(feature = "strict"))]
+    AssociatedTokenAccount::check(account, maker, mint, token_program)?;
+    OwnedTokenAccount::check(account, maker, mint)
+}
+
+pub fn rent_destination<'a>(
+    escrow: &crate::state::Escrow,
+    config: &AccountView,
+    maker: &'a AccountView,
+    taker: &'a AccountView,
+    treasury: Option<&'a AccountView>,
+) -> Result<&'a AccountView, ProgramError> {
+    if escrow.flags & crate::state::Escrow::RENT_TO_TAKER != 0 {
+        return Ok(taker);
+    }
+    if escrow.flags & crate::state::Escrow::RENT_TO_TREASURY != 0 {
+        let treasury = treasury.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let data = config.try_borrow()?;
+        if crate::state::Config::load(&data)?
+            .treasury
+            .ne(treasury.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        return Ok(treasury);
+    }
+    Ok(maker)
+}
+
+pub const MEMO_PROGRAM_ID: Address =
+    pinocchio::address::address!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+pub fn parse_memo(data: &[u8]) -> Result<(&[u8], &[u8]), ProgramError> {
+    let (len, rest) = data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let (memo, rest) = rest
+        .split_at_checked(*len as usize)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    if core::str::from_utf8(memo).is_err() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    Ok((memo, rest))
+}
+
+pub struct MemoProgram;
+impl MemoProgram {
+    pub fn invoke_if_required(
+        program: Option<&AccountView>,
+        memo: Option<&[u8]>,
+        destination: &AccountView,
+    ) -> ProgramResult {
+        if !TokenInterface::requires_memo(destination)? {
+            return Ok(());
+        }
+        let (Some(program), Some(memo)) = (program, memo) else {
+            return Err(EscrowError::MemoRequired.into());
+        };
+        if program.address().ne(&MEMO_PROGRAM_ID) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        invoke::<0>(
+            &InstructionView {
+                program_id: &MEMO_PROGRAM_ID,
+                accounts: &[],
+                data: memo,
+            },
+            &[],
+        )
+    }
+}
+
+#[cfg(feature = "wormhole")]
+pub const WORMHOLE_PROGRAM_ID: Address = match option_env!("WORMHOLE_PROGRAM_ID") {
+    Some(id) => Address::from_str_const(id),
+    None => pinocchio::address::address!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth"),
+};
+#[cfg(feature = "wormhole")]
+const WORMHOLE_POST_MESSAGE: u8 = 1;
+#[cfg(feature = "wormhole")]
+const WORMHOLE_CONSISTENCY_FINALIZED: u8 = 1;
+#[cfg(feature = "wormhole")]
+const WORMHOLE_BRIDGE_FEE_OFFSET: usize = 16;
+
+#[cfg(feature = "wormhole")]
+pub struct WormholeFill {
+    pub escrow: Address,
+    pub maker: Address,
+    pub taker: Address,
+    pub mint_a: Address,
+    pub mint_b: Address,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+#[cfg(feature = "wormhole")]
+impl WormholeFill {
+    pub const PAYLOAD_ID: u8 = 1;
+    pub const LEN: usize = 1 + size_of::<Address>() * 5 + size_of::<u64>() * 2;
+
+    pub fn payload(&self) -> [u8; Self::LEN] {
+        let mut payload = [0u8; Self::LEN];
+        payload[0] = Self::PAYLOAD_ID;
+        for (chunk, address) in payload[1..161].chunks_exact_mut(32).zip([
+            &self.escrow,
+            &self.maker,
+            &self.taker,
+            &self.mint_a,
+            &self.mint_b,
+        ]) {
+            chunk.copy_from_slice(address.as_ref());
+        }
+        payload[161..169].copy_from_slice(&self.amount_a.to_be_bytes());
+        payload[169..177].copy_from_slice(&self.amount_b.to_be_bytes());
+        payload
+    }
+}
+
+#[cfg(feature = "wormhole")]
+pub struct WormholeAccounts<'a> {
+    pub program: &'a AccountView,
+    pub bridge: &'a AccountView,
+    pub message: &'a AccountView,
+    pub emitter: &'a AccountView,
+    pub sequence: &'a AccountView,
+    pub fee_collector: &'a AccountView,
+    pub clock: &'a AccountView,
+    pub rent: &'a AccountView,
+}
+#[cfg(feature = "wormhole")]
+impl<'a> WormholeAccounts<'a> {
+    pub const EMITTER_SEED: &'static [u8] = b"emitter";
+    pub const MESSAGE_SEED: &'static [u8] = b"wormhole_message";
+
+    pub fn parse(iter: &mut core::slice::Iter<'a, AccountView>) -> Result<Self, ProgramError> {
+        let accounts = Self {
+            program: next_account(iter)?,
+            bridge: next_account(iter)?,
+            message: next_account(iter)?,
+            emitter: next_account(iter)?,
+            sequence: next_account(iter)?,
+            fee_collector: next_account(iter)?,
+            clock: next_account(iter)?,
+            rent: next_account(iter)?,
+        };
+        if accounts.program.address().ne(&WORMHOLE_PROGRAM_ID) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if !accounts.bridge.owned_by(&WORMHOLE_PROGRAM_ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(accounts)
+    }
+
+    fn fee(&self) -> Result<u64, ProgramError> {
+        let data = self.bridge.try_borrow()?;
+        Ok(u64::from_le_bytes(account_bytes(
+            &data,
+            WORMHOLE_BRIDGE_FEE_OFFSET,
+        )?))
+    }
+
+    pub fn post_fill(
+        &self,
+        fill: &WormholeFill,
+        seq: u64,
+        payer: &AccountView,
+        system_program: &AccountView,
+    ) -> ProgramResult {
+        let (emitter_key, emitter_bump) =
+            Address::find_program_address(&[Self::EMITTER_SEED], &crate::ID);
+        if self.emitter.address().ne(&emitter_key) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let seq_binding = seq.to_le_bytes();
+        let (message_key, message_bump) =
+            Address::find_program_address(&[Self::MESSAGE_SEED, &seq_binding], &crate::ID);
+        if self.message.address().ne(&message_key) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let fee = self.fee()?;
+        if fee != 0 {
+            Transfer {
+                from: payer,
+                to: self.fee_collector,
+                lamports: fee,
+            }
+            .invoke()?;
+        }
+
+        let mut data = [0u8; 1 + 4 + 4 + WormholeFill::LEN + 1];
+        data[0] = WORMHOLE_POST_MESSAGE;
+        data[5..9].copy_from_slice(&(WormholeFill::LEN as u32).to_le_bytes());
+        data[9..9 + WormholeFill::LEN].copy_from_slice(&fill.payload());
+        data[9 + WormholeFill::LEN] = WORMHOLE_CONSISTENCY_FINALIZED;
+        let instruction_accounts = [
+            InstructionAccount::writable(self.bridge.address()),
+            InstructionAccount::writable_signer(self.message.address()),
+            InstructionAccount::readonly_signer(self.emitter.address()),
+            InstructionAccount::writable(self.sequence.address()),
+            InstructionAccount::writable_signer(payer.address()),
+            InstructionAccount::writable(self.fee_collector.address()),
+            InstructionAccount::readonly(self.clock.address()),
+            InstructionAccount::readonly(system_program.address()),
+            InstructionAccount::readonly(self.rent.address()),
+        ];
+        let emitter_bump = [emitter_bump];
+        let emitter_seeds = [Seed::from(Self::EMITTER_SEED), Seed::from(&emitter_bump)];
+        let message_bump = [message_bump];
+        let message_seeds = [
+            Seed::from(Self::MESSAGE_SEED),
+            Seed::from(&seq_binding),
+            Seed::from(&message_bump),
+        ];
+        invoke_signed(
+            &InstructionView {
+                program_id: &WORMHOLE_PROGRAM_ID,
+                accounts: &instruction_accounts,
+                data: &data,
+            },
+            &[
+                self.bridge,
+                self.message,
+                self.emitter,
+                self.sequence,
+                payer,
+                self.fee_collector,
+                self.clock,
+                system_program,
+                self.rent,
+            ],
+            &[Signer::from(&message_seeds), Signer::from(&emitter_seeds)],
+        )
+    }
+}
+
+pub const TOKEN_METADATA_PROGRAM_ID: Address =
+    pinocchio::address::address!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+pub const MAX_ROYALTY_CREATORS: usize = 5;
+const METADATA_KEY_V1: u8 = 4;
+const METADATA_MINT_OFFSET: usize = 33;
+const METADATA_NAME_OFFSET: usize = 65;
+const METADATA_CREATOR_LEN: usize = 34;
+const METADATA_COLLECTION_LEN: usize = 33;
+const BASIS_POINTS: u128 = 10_000;
+
+#[derive(Clone, Copy, Default)]
+pub struct RoyaltyCreator {
+    pub address: Address,
+    pub share: u8,
+}
+
+pub struct Royalty {
+    pub seller_fee_basis_points: u16,
+    creators: [RoyaltyCreator; MAX_ROYALTY_CREATORS],
+    len: usize,
+}
+impl Royalty {
+    pub fn creators(&self) -> &[RoyaltyCreator] {
+        &self.creators[..self.len]
+    }
+    pub fn amount(&self, total: u64) -> u64 {
+        (total as u128 * self.seller_fee_basis_points as u128 / BASIS_POINTS) as u64
+    }
+    pub fn share(&self, creator: &RoyaltyCreator, total: u64) -> u64 {
+        (self.amount(total) as u128 * creator.share as u128 / 100) as u64
+    }
+}
+
+pub struct MetadataAccount;
+impl MetadataAccount {
+    fn check(metadata: &AccountView, mint: &AccountView) -> ProgramResult {
+        if !metadata.owned_by(&TOKEN_METADATA_PROGRAM_ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        let (metadata_key, _) = Address::find_program_address(
+            &[
+                b"metadata",
+                TOKEN_METADATA_PROGRAM_ID.as_ref(),
+                mint.address().as_ref(),
+            ],
+            &TOKEN_METADATA_PROGRAM_ID,
+        );
+        if metadata_key.ne(metadata.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let data = metadata.try_borrow()?;
+        if data.len() < METADATA_NAME_OFFSET
+            || data.first() != Some(&METADATA_KEY_V1)
+            || data.get(METADATA_MINT_OFFSET..METADATA_NAME_OFFSET) != Some(mint.address().as_ref())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+    fn seller_fee_offset(data: &[u8]) -> Result<usize, ProgramError> {
+        let mut offset = METADATA_NAME_OFFSET;
+        for _ in 0..3 {
+            let len = read_u32(data, offset)? as usize;
+            offset = offset
+                .checked_add(4 + len)
+                .ok_or(ProgramError::InvalidAccountData)?;
+        }
+        Ok(offset)
+    }
+    fn option_len(data: &[u8], offset: usize, len: usize) -> Result<usize, ProgramError> {
+        match data.get(offset) {
+            Some(0) => Ok(1),
+            Some(1) => Ok(1 + len),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+    pub fn royalty(metadata: &AccountView, mint: &AccountView) -> Result<Royalty, ProgramError> {
+        Self::check(metadata, mint)?;
+        let data = metadata.try_borrow()?;
+        let offset = Self::seller_fee_offset(&data)?;
+        let seller_fee_basis_points = u16::from_le_bytes(account_bytes(&data, offset)?);
+        if seller_fee_basis_points as u128 > BASIS_POINTS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut royalty = Royalty {
+            seller_fee_basis_points,
+            creators: [RoyaltyCreator::default(); MAX_ROYALTY_CREATORS],
+            len: 0,
+        };
+        if *data
+            .get(offset + 2)
+            .ok_or(ProgramError::InvalidAccountData)?
+            == 0
+        {
+            return Ok(royalty);
+        }
+        let len = read_u32(&data, offset + 3)? as usize;
+        if len > MAX_ROYALTY_CREATORS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        for (i, creator) in royalty.creators.iter_mut().take(len).enumerate() {
+            let start = offset + 7 + i * METADATA_CREATOR_LEN;
+            let bytes = data
+                .get(start..start + METADATA_CREATOR_LEN)
+                .ok_or(ProgramError::InvalidAccountData)?;
+            creator.address = Address::new_from_array(account_bytes(bytes, 0)?);
+            creator.share = *bytes.get(33).ok_or(ProgramError::InvalidAccountData)?;
+        }
+        royalty.len = len;
+        Ok(royalty)
+    }
+    pub fn verified_collection(
+        metadata: &AccountView,
+        mint: &AccountView,
+    ) -> Result<Option<Address>, ProgramError> {
+        Self::check(metadata, mint)?;
+        let data = metadata.try_borrow()?;
+        let mut offset = Self::seller_fee_offset(&data)? + 2;
+        offset += match data.get(offset) {
+            Some(1) => 5 + read_u32(&data, offset + 1)? as usize * METADATA_CREATOR_LEN,
+            Some(0) => 1,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        offset += 2;
+        offset += Self::option_len(&data, offset, 1)?;
+        offset += Self::option_len(&data, offset, 1)?;
+        if Self::option_len(&data, offset, METADATA_COLLECTION_LEN)? == 1
+            || data.get(offset + 1) != Some(&1)
+        {
+            return Ok(None);
+        }
+        Ok(Some(Address::new_from_array(account_bytes(
+            &data,
+            offset + 2,
+        )?)))
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, ProgramError> {
+    Ok(u32::from_le_bytes(account_bytes(data, offset)?))
+}
+
+pub const ED25519_PROGRAM_ID: Address = Address::new_from_array([
+    0x03, 0x7d, 0x46, 0xd6, 0x7c, 0x93, 0xfb, 0xbe, 0x12, 0xf9, 0x42, 0x8f, 0x83, 0x8d, 0x40, 0xff,
+    0x05, 0x70, 0x74, 0x49, 0x27, 0xf4, 0x8a, 0x64, 0xfc, 0xca, 0x70, 0x44, 0x80, 0x00, 0x00, 0x00,
+]);
+const ED25519_HEADER_LEN: usize = 2;
+const ED25519_OFFSETS_LEN: usize = 14;
+const ED25519_SIGNATURE_LEN: usize = 64;
+
+pub fn verify_ed25519_instruction(
+    instructions: &AccountView,
+    signer: &Address,
+    message: &[u8],
+) -> ProgramResult {
+    let instructions = Instructions::try_from(instructions)?;
+    let instruction = instructions.get_instruction_relative(-1)?;
+    if instruction.get_program_id().ne(&ED25519_PROGRAM_ID) {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let data = instruction.get_instruction_data();
+    if data.first() != Some(&1) {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let offsets = instruction_bytes::<ED25519_OFFSETS_LEN>(data, ED25519_HEADER_LEN)?;
+    let read = |i: usize| u16::from_le_bytes([offsets[i], offsets[i + 1]]);
+    let (signature_offset, signature_index) = (read(0) as usize, read(2));
+    let (public_key_offset, public_key_index) = (read(4) as usize, read(6));
+    let (message_offset, message_size, message_index) =
+        (read(8) as usize, read(10) as usize, read(12));
+    if signature_index != u16::MAX || public_key_index != u16::MAX || message_index != u16::MAX {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if data.len() < signature_offset + ED25519_SIGNATURE_LEN {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let public_key = data
+        .get(public_key_offset..public_key_offset + size_of::<Address>())
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    if public_key.ne(signer.as_ref()) || signed_message.ne(message) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    Ok(())
+}
+
+enum EscrowNamespace<'a> {
+    Seed([u8; 8]),
+    Nonce([u8; 8]),
+    Pair(&'a Address, &'a Address),
+}
+
+pub struct EscrowSeeds<'a> {
+    maker: &'a Address,
+    namespace: EscrowNamespace<'a>,
+    bump: [u8; 1],
+}
+impl<'a> EscrowSeeds<'a> {
+    pub const PREFIX: &'static [u8] = b"escrow";
+    pub const PAIR_PREFIX: &'static [u8] = b"escrow_pair";
+    pub const NONCE_PREFIX: &'static [u8] = b"escrow_nonce";
+
+    #[inline(always)]
+    pub fn new(maker: &'a Address, seed: u64, bump: [u8; 1]) -> Self {
+        Self {
+            maker,
+            namespace: EscrowNamespace::Seed(seed.to_le_bytes()),
+            bump,
+        }
+    }
+    #[inline(always)]
+    pub fn new_nonce(maker: &'a Address, nonce: u64, bump: [u8; 1]) -> Self {
+        Self {
+            maker,
+            namespace: EscrowNamespace::Nonce(nonce.to_le_bytes()),
+            bump,
+        }
+    }
+    #[inline(always)]
+    pub fn new_pair(
+        maker: &'a Address,
+        mint_a: &'a Address,
+        mint_b: &'a Address,
+        bump: [u8; 1],
+    ) -> Self {
+        Self {
+            maker,
+            namespace: EscrowNamespace::Pair(mint_a, mint_b),
+            bump,
+        }
+    }
+    #[inline(always)]
+    pub fn from_escrow(escrow: &'a crate::state::Escrow) -> Self {
+        match escrow.namespace {
+            crate::state::Escrow::NAMESPACE_PAIR => {
+                Self::new_pair(&escrow.creator, &escrow.mint_a, &escrow.mint_b, escrow.bump)
+            }
+            crate::state::Escrow::NAMESPACE_NONCE => {
+                Self::new_nonce(&escrow.creator, escrow.seed, escrow.bump)
+            }
+            _ => Self::new(&escrow.creator, escrow.seed, escrow.bump),
+        }
+    }
+    pub fn find(maker: &'a Address, seed: u64) -> Self {
+        Self::new(maker, seed, [0]).with_canonical_bump()
+    }
+    pub fn find_nonce(maker: &'a Address, nonce: u64) -> Self {
+        Self::new_nonce(maker, nonce, [0]).with_canonical_bump()
+    }
+    pub fn find_pair(maker: &'a Address, mint_a: &'a Address, mint_b: &'a Address) -> Self {
+        Self::new_pair(maker, mint_a, mint_b, [0]).with_canonical_bump()
+    }
+    fn with_canonical_bump(mut self) -> Self {
+        let (parts, len) = self.parts();
+        let (_, bump) =
+            Address::find_program_address(parts.get(..len - 1).unwrap_or_default(), &crate::ID);
+        self.bump = [bump];
+        self
+    }
+    fn parts(&self) -> ([&[u8]; 5], usize) {
+        match &self.namespace {
+            EscrowNamespace::Seed(seed) => (
+                [Self::PREFIX, self.maker.as_ref(), seed, &self.bump, &[]],
+                4,
+            ),
+            EscrowNamespace::Nonce(nonce) => (
+                [
+                    Self::NONCE_PREFIX,
+                    self.maker.as_ref(),
+                    nonce,
+                    &self.bump,
+                    &[],
+                ],
+                4,
+            ),
+            EscrowNamespace::Pair(mint_a, mint_b) => (
+                [
+                    Self::PAIR_PREFIX,
+                    self.maker.as_ref(),
+                    mint_a.as_ref(),
+                    mint_b.as_ref(),
+                    &self.bump,
+                ],
+                5,
+            ),
+        }
+    }
+    #[inline(always)]
+    pub fn bump(&self) -> u8 {
+        self.bump[0]
+    }
+    pub fn address(&self) -> Result<Address, ProgramError> {
+        let (parts, len) = self.parts();
+        Address::create_program_address(parts.get(..len).unwrap_or_default(), &crate::ID)
+            .map_err(|_| ProgramError::InvalidSeeds)
+    }
+    #[inline(always)]
+    pub fn seeds(&self) -> EscrowSignerSeeds<'_> {
+        let (parts, len) = self.parts();
+        EscrowSignerSeeds {
+            seeds: parts.map(Seed::from),
+            len,
+        }
+    }
+}
+
+pub struct EscrowSignerSeeds<'a> {
+    seeds: [Seed<'a>; 5],
+    len: usize,
+}
+impl<'a, 'b> From<&'b EscrowSignerSeeds<'a>> for Signer<'a, 'b> {
+    #[inline(always)]
+    fn from(value: &'b EscrowSignerSeeds<'a>) -> Self {
+        Signer::from(value.seeds.get(..value.len).unwrap_or_default())
+    }
+}
+
+pub const STAKE_PROGRAM_ID: Address =
+    pinocchio::address::address!("Stake11111111111111111111111111111111111111");
+const STAKE_STATE_LEN: usize = 200;
+const STAKE_STATE_INITIALIZED: u32 = 1;
+const STAKE_STATE_STAKE: u32 = 2;
+const STAKE_AUTHORIZE_INSTRUCTION: u32 = 1;
+const STAKE_AUTHORIZE_STAKER: u32 = 0;
+const STAKE_AUTHORIZE_WITHDRAWER: u32 = 1;
+
+pub struct StakeAccount;
+impl StakeAccount {
+    pub fn check_transferable(
+        account: &AccountView,
+        withdrawer: &Address,
+        clock: &Clock,
+    ) -> ProgramResult {
+        if !account.owned_by(&STAKE_PROGRAM_ID) || account.data_len() != STAKE_STATE_LEN {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        let data = account.try_borrow()?;
+        let state = u32::from_le_bytes(account_bytes(&data, 0)?);
+        if state == STAKE_STATE_STAKE {
+            let deactivation_epoch = u64::from_le_bytes(account_bytes(&data, 172)?);
+            if deactivation_epoch >= clock.epoch {
+                return Err(ProgramError::InvalidAccountData);
+            }
+        } else if state != STAKE_STATE_INITIALIZED {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if Address::new_from_array(account_bytes(&data, 44)?).ne(withdrawer) {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+        let lockup_timestamp = i64::from_le_bytes(account_bytes(&data, 76)?);
+        let lockup_epoch = u64::from_le_bytes(account_bytes(&data, 84)?);
+        if lockup_timestamp > clock.unix_timestamp || lockup_epoch > clock.epoch {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+    pub fn authorize(
+        stake: &AccountView,
+        clock: &AccountView,
+        authority: &AccountView,
+        new_authority: &Address,
+        signers: &[Signer],
+    ) -> ProgramResult {
+        for role in [STAKE_AUTHORIZE_STAKER, STAKE_AUTHORIZE_WITHDRAWER] {
+            let mut data = [0u8; 40];
+            data[0..4].copy_from_slice(&STAKE_AUTHORIZE_INSTRUCTION.to_le_bytes());
+            data[4..36].copy_from_slice(new_authority.as_ref());
+            data[36..40].copy_from_slice(&role.to_le_bytes());
+            let instruction_accounts = [
+                InstructionAccount::writable(stake.address()),
+                InstructionAccount::readonly(clock.address()),
+                InstructionAccount::readonly_signer(authority.address()),
+            ];
+            invoke_signed(
+                &InstructionView {
+                    program_id: &STAKE_PROGRAM_ID,
+                    accounts: &instruction_accounts,
+                    data: &data,
+                },
+                &[stake, clock, authority],
+                signers,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub struct StakeProgram;
+impl ProgramId for StakeProgram {
+    const ID: Address = STAKE_PROGRAM_ID;
+}
+pub type StakeProgramAccount = AddressEq<StakeProgram>;
+
+pub struct ClockSysvarAccount;
+impl AccountCheck for ClockSysvarAccount {
+    fn check(account: &AccountView) -> Result<(), ProgramError> {
+        if account.address().ne(&pinocchio::sysvars::clock::CLOCK_ID) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(())
+    }
+}
+
+pub type StakeEscrowAccount =
+    And<OwnedBy<EscrowProgram>, LenEq<{ crate::state::StakeEscrow::LEN }>>;
+pub type AccountEscrowAccount =
+    And<OwnedBy<EscrowProgram>, LenEq<{ crate::state::AccountEscrow::LEN }>>;
+pub type CollectionOfferAccount =
+    And<OwnedBy<EscrowProgram>, LenEq<{ crate::state::CollectionOffer::LEN }>>;
+
+pub const CLOSED_ACCOUNT_DISCRIMINATOR: u8 = 0xff;
+
 pub trait AccountClose {
     fn close(account: &AccountView, destination: &AccountView) -> ProgramResult;
 }
@@ -558,10 +2658,16 @@ impl AccountClose for ProgramAccount {
     fn close(account: &AccountView, destination: &AccountView) -> ProgramResult {
         {
             let mut data = account.try_borrow_mut()?;
-            data[0] = 0xff;
+            data.fill(0);
+            if let Some(discriminator) = data.first_mut() {
+                *discriminator = CLOSED_ACCOUNT_DISCRIMINATOR;
+            }
         }
-        let dst_curr_lamports = destination.lamports();
-        destination.set_lamports(dst_curr_lamports + account.lamports());
+        let dst_lamports = destination
+            .lamports()
+            .checked_add(account.lamports())
+            .ok_or(EscrowError::LamportOverflow)?;
+        destination.set_lamports(dst_lamports);
         account.resize(1)?;
         account.close()
     }
@@ -1,7 +1,123 @@
+mod accept_counter;
+mod add_to_denylist;
+mod add_to_mint_list;
+mod add_to_router_list;
+#[cfg(feature = "dispute")]
+mod approve_release;
+mod auto_refund;
+#[cfg(feature = "auction")]
+mod bid;
+mod cancel_counter;
+mod cancel_order_nonce;
+mod claim;
+mod claim_proceeds;
+mod close_expired;
+mod counter;
+mod emit_event;
+mod fill_signed_order;
+mod freeze;
+mod get_escrow;
+mod initialize_config;
+mod initialize_stats;
+mod lock_proceeds;
 mod make;
+mod make_account_escrow;
+#[cfg(feature = "nft")]
+mod make_collection_offer;
+mod make_many;
+mod make_stake;
+mod migrate;
+#[cfg(feature = "auction")]
+mod outbid_refund;
 mod refund;
+mod refund_account_escrow;
+#[cfg(feature = "nft")]
+mod refund_collection_offer;
+mod refund_stake;
+mod remake;
+mod remove_from_denylist;
+mod remove_from_mint_list;
+mod remove_from_router_list;
+mod set_allowlist;
+mod set_caller_policy;
+mod set_condition;
+#[cfg(feature = "dispute")]
+mod set_release_signers;
+mod set_roll;
+mod set_value_floor;
+#[cfg(feature = "auction")]
+mod settle;
 mod take;
+mod take_account_escrow;
+#[cfg(feature = "nft")]
+mod take_collection_offer;
+mod take_many;
+mod take_roll;
+mod take_signed;
+mod take_stake;
+mod top_up;
+mod transfer_maker;
+mod version;
+mod withdraw;
 
+pub use accept_counter::*;
+pub use add_to_denylist::*;
+pub use add_to_mint_list::*;
+pub use add_to_router_list::*;
+#[cfg(feature = "dispute")]
+pub use approve_release::*;
+pub use auto_refund::*;
+#[cfg(feature = "auction")]
+pub use bid::*;
+pub use cancel_counter::*;
+pub use cancel_order_nonce::*;
+pub use claim::*;
+pub use claim_proceeds::*;
+pub use close_expired::*;
+pub use counter::*;
+pub use emit_event::*;
+pub use fill_signed_order::*;
+pub use freeze::*;
+pub use get_escrow::*;
+pub use initialize_config::*;
+pub use initialize_stats::*;
+pub use lock_proceeds::*;
 pub use make::*;
+pub use make_account_escrow::*;
+#[cfg(feature = "nft")]
+pub use make_collection_offer::*;
+pub use make_many::*;
+pub use make_stake::*;
+pub use migrate::*;
+#[cfg(feature = "auction")]
+pub use outbid_refund::*;
 pub use refund::*;
+pub use refund_account_escrow::*;
+#[cfg(feature = "nft")]
+pub use refund_collection_offer::*;
+pub use refund_stake::*;
+pub use remake::*;
+pub use remove_from_denylist::*;
+pub use remove_from_mint_list::*;
+pub use remove_from_router_list::*;
+pub use set_allowlist::*;
+pub use set_caller_policy::*;
+pub use set_condition::*;
+#[cfg(feature = "dispute")]
+pub use set_release_signers::*;
+pub use set_roll::*;
+pub use set_value_floor::*;
+#[cfg(feature = "auction")]
+pub use settle::*;
 pub use take::*;
+pub use take_account_escrow::*;
+#[cfg(feature = "nft")]
+pub use take_collection_offer::*;
+pub use take_many::*;
+pub use take_roll::*;
+pub use take_signed::*;
+pub use take_stake::*;
+pub use top_up::*;
+pub use transfer_maker::*;
+pub use version::*;
+pub use withdraw::*;
@@ -0,0 +1,222 @@
+use pinocchio::{
+    AccountView, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::{errors::EscrowError, helpers::*};
+
+pub struct AcceptCounterAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub mint_a: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub vault: &'a AccountView,
+    pub taker: &'a AccountView,
+    pub counter: &'a AccountView,
+    pub counter_vault: &'a AccountView,
+    pub taker_ata_a: &'a AccountView,
+    pub maker_ata_b: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for AcceptCounterAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let mint_a = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let vault = next_account(&mut iter)?;
+        let taker = next_account(&mut iter)?;
+        let counter = next_account(&mut iter)?;
+        let counter_vault = next_account(&mut iter)?;
+        let taker_ata_a = next_account(&mut iter)?;
+        let maker_ata_b = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_a)?;
+        MintInterface::check(mint_b)?;
+        EscrowVault::check(vault, escrow, mint_a, token_program)?;
+        CounterOfferAccount::check(counter)?;
+        AssociatedTokenAccount::check(counter_vault, counter, mint_b, token_program)?;
+        ConfigAccount::check(config)?;
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            maker,
+            escrow,
+            mint_a,
+            mint_b,
+            vault,
+            taker,
+            counter,
+            counter_vault,
+            taker_ata_a,
+            maker_ata_b,
+            system_program,
+            token_program,
+            config,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct AcceptCounter<'a> {
+    pub accounts: AcceptCounterAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for AcceptCounter<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = AcceptCounterAccounts::try_from(accounts)?;
+        init_destination_if_needed(
+            accounts.taker_ata_a,
+            accounts.mint_a,
+            accounts.maker,
+            accounts.taker,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> AcceptCounter<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &7;
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.escrow.try_borrow()?;
+        let escrow = crate::state::Escrow::load(&data)?;
+        #[cfg(feature = "strict")]
+        MintPolicy::check_pair(self.accounts.mint_a, self.accounts.mint_b, escrow.flags)?;
+        MintPolicy::check_unpaused(self.accounts.mint_a, self.accounts.mint_b)?;
+        let counter_data = self.accounts.counter.try_borrow()?;
+        let counter = crate::state::CounterOffer::load(&counter_data)?;
+        check_program_pda(
+            self.accounts.counter,
+            &[
+                b"counter",
+                counter.escrow.as_ref(),
+                counter.taker.as_ref(),
+                &counter.bump,
+            ],
+        )?;
+
+        if escrow.is_auction()
+            || escrow.is_standing()
+            || escrow.fills != 0
+            || escrow.has_split()
+            || escrow.has_burn()
+            || escrow.has_royalty()
+            || escrow.is_tracked()
+            || escrow.is_conditional()
+            || escrow.has_value_floor()
+            || escrow.is_release_gated()
+            || escrow.has_allowlist()
+            || escrow.has_cooldown()
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.is_frozen() {
+            return Err(EscrowError::EscrowFrozen.into());
+        }
+        if escrow.has_deadline() && escrow.is_expired(&Clock::get()?) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        if escrow.is_vesting() && escrow.vesting_start != 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.maker.ne(self.accounts.maker.address())
+            || escrow.mint_a.ne(self.accounts.mint_a.address())
+            || escrow.mint_b.ne(self.accounts.mint_b.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if counter.escrow.ne(self.accounts.escrow.address())
+            || counter.taker.ne(self.accounts.taker.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut optional_accounts = self.accounts.remaining_accounts.iter();
+        if DenylistAccount::is_enabled(self.accounts.config)? {
+            DenylistAccount::check_parties(
+                next_account(&mut optional_accounts)?,
+                &[self.accounts.taker.address(), self.accounts.maker.address()],
+            )?;
+        }
+        CallerPolicy::check(self.accounts.config, &mut optional_accounts)?;
+        let maker_index = optional_accounts.next();
+
+        init_proceeds_if_needed(
+            escrow,
+            self.accounts.maker_ata_b,
+            self.accounts.mint_b,
+            self.accounts.maker,
+            self.accounts.maker,
+            self.accounts.system_program,
+            self.accounts.token_program,
+        )?;
+
+        let escrow_seeds = EscrowSeeds::from_escrow(escrow);
+        let seeds = escrow_seeds.seeds();
+        let escrow_signer = Signer::from(&seeds);
+        let counter_bump_binding = counter.bump;
+        let counter_seeds = [
+            Seed::from(b"counter"),
+            Seed::from(counter.escrow.as_ref()),
+            Seed::from(counter.taker.as_ref()),
+            Seed::from(counter_bump_binding.as_ref()),
+        ];
+        let counter_signer = Signer::from(&counter_seeds);
+        let amount = TokenInterface::get_amount(self.accounts.vault)?;
+
+        TokenInterface::transfer_checked(
+            self.accounts.vault,
+            self.accounts.mint_a,
+            self.accounts.taker_ata_a,
+            self.accounts.escrow,
+            amount,
+            core::slice::from_ref(&escrow_signer),
+        )?;
+        TokenInterface::close_account(
+            self.accounts.vault,
+            self.accounts.maker,
+            self.accounts.escrow,
+            core::slice::from_ref(&escrow_signer),
+        )?;
+        TokenInterface::transfer_checked(
+            self.accounts.counter_vault,
+            self.accounts.mint_b,
+            self.accounts.maker_ata_b,
+            self.accounts.counter,
+            counter.receive,
+            core::slice::from_ref(&counter_signer),
+        )?;
+        TokenInterface::close_account(
+            self.accounts.counter_vault,
+            self.accounts.taker,
+            self.accounts.counter,
+            core::slice::from_ref(&counter_signer),
+        )?;
+
+        drop(counter_data);
+        MakerIndexAccount::release(escrow, maker_index)?;
+        drop(data);
+        next_seq(self.accounts.config)?;
+        ProgramAccount::close(self.accounts.counter, self.accounts.taker)?;
+        ProgramAccount::close(self.accounts.escrow, self.accounts.maker)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::helpers::*;
+
+pub struct AddToDenylistAccounts<'a> {
+    pub admin: &'a AccountView,
+    pub config: &'a AccountView,
+    pub denylist: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for AddToDenylistAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let admin = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let denylist = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(admin)?;
+        ConfigAccount::check(config)?;
+        if crate::state::Config::load(&config.try_borrow()?)?
+            .admin
+            .ne(admin.address())
+        {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+        SystemProgramAccount::check(system_program)?;
+
+        Ok(Self {
+            admin,
+            config,
+            denylist,
+            system_program,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct AddToDenylistInstructionData {
+    pub address: Address,
+}
+impl<'a> TryFrom<&'a [u8]> for AddToDenylistInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let address: [u8; 32] = data
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        Ok(Self {
+            address: Address::new_from_array(address),
+        })
+    }
+}
+
+pub struct AddToDenylist<'a> {
+    pub accounts: AddToDenylistAccounts<'a>,
+    pub instruction_data: AddToDenylistInstructionData,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for AddToDenylist<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = AddToDenylistAccounts::try_from(accounts)?;
+        let instruction_data = AddToDenylistInstructionData::try_from(data)?;
+        AddressListAccount::init_if_needed(
+            accounts.denylist,
+            accounts.admin,
+            crate::state::AddressList::DENYLIST_PREFIX,
+        )?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> AddToDenylist<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &24;
+    pub fn process(&mut self) -> ProgramResult {
+        AddressListAccount::insert(
+            self.accounts.denylist,
+            self.accounts.admin,
+            &self.instruction_data.address,
+        )?;
+
+        let mut data = self.accounts.config.try_borrow_mut()?;
+        crate::state::Config::load_mut(data.as_mut())?.set_denylist(true);
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::helpers::*;
+
+pub struct AddToMintListAccounts<'a> {
+    pub admin: &'a AccountView,
+    pub config: &'a AccountView,
+    pub mint_list: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for AddToMintListAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let admin = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let mint_list = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(admin)?;
+        ConfigAccount::check(config)?;
+        if crate::state::Config::load(&config.try_borrow()?)?
+            .admin
+            .ne(admin.address())
+        {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+        SystemProgramAccount::check(system_program)?;
+
+        Ok(Self {
+            admin,
+            config,
+            mint_list,
+            system_program,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct AddToMintListInstructionData {
+    pub mode: u8,
+    pub address: Address,
+}
+impl<'a> TryFrom<&'a [u8]> for AddToMintListInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (mode, address) = data
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        if *mode != crate::state::Config::MINT_LIST_ALLOW
+            && *mode != crate::state::Config::MINT_LIST_DENY
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let address: [u8; 32] = address
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        Ok(Self {
+            mode: *mode,
+            address: Address::new_from_array(address),
+        })
+    }
+}
+
+pub struct AddToMintList<'a> {
+    pub accounts: AddToMintListAccounts<'a>,
+    pub instruction_data: AddToMintListInstructionData,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for AddToMintList<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = AddToMintListAccounts::try_from(accounts)?;
+        let instruction_data = AddToMintListInstructionData::try_from(data)?;
+        AddressListAccount::init_if_needed(
+            accounts.mint_list,
+            accounts.admin,
+            crate::state::AddressList::MINT_LIST_PREFIX,
+        )?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> AddToMintList<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &26;
+    pub fn process(&mut self) -> ProgramResult {
+        {
+            let mut data = self.accounts.config.try_borrow_mut()?;
+            let config = crate::state::Config::load_mut(data.as_mut())?;
+            if config.mint_list != crate::state::Config::MINT_LIST_NONE
+                && config.mint_list != self.instruction_data.mode
+            {
+                return Err(ProgramError::InvalidArgument);
+            }
+            config.set_mint_list(self.instruction_data.mode);
+        }
+
+        AddressListAccount::insert(
+            self.accounts.mint_list,
+            self.accounts.admin,
+            &self.instruction_data.address,
+        )
+    }
+}
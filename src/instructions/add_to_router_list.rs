@@ -0,0 +1,90 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::helpers::*;
+
+pub struct AddToRouterListAccounts<'a> {
+    pub admin: &'a AccountView,
+    pub config: &'a AccountView,
+    pub router_list: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for AddToRouterListAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let admin = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let router_list = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(admin)?;
+        ConfigAccount::check(config)?;
+        if crate::state::Config::load(&config.try_borrow()?)?
+            .admin
+            .ne(admin.address())
+        {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+        SystemProgramAccount::check(system_program)?;
+
+        Ok(Self {
+            admin,
+            config,
+            router_list,
+            system_program,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct AddToRouterListInstructionData {
+    pub address: Address,
+}
+impl<'a> TryFrom<&'a [u8]> for AddToRouterListInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let address: [u8; 32] = data
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        Ok(Self {
+            address: Address::new_from_array(address),
+        })
+    }
+}
+
+pub struct AddToRouterList<'a> {
+    pub accounts: AddToRouterListAccounts<'a>,
+    pub instruction_data: AddToRouterListInstructionData,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for AddToRouterList<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = AddToRouterListAccounts::try_from(accounts)?;
+        let instruction_data = AddToRouterListInstructionData::try_from(data)?;
+        AddressListAccount::init_if_needed(
+            accounts.router_list,
+            accounts.admin,
+            crate::state::AddressList::ROUTER_LIST_PREFIX,
+        )?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> AddToRouterList<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &38;
+    pub fn process(&mut self) -> ProgramResult {
+        AddressListAccount::insert(
+            self.accounts.router_list,
+            self.accounts.admin,
+            &self.instruction_data.address,
+        )
+    }
+}
@@ -0,0 +1,55 @@
+use pinocchio::{AccountView, ProgramResult, error::ProgramError};
+
+use crate::helpers::*;
+
+pub struct ApproveReleaseAccounts<'a> {
+    pub signer: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub approvals: &'a AccountView,
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for ApproveReleaseAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let signer = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let approvals = next_account(&mut iter)?;
+
+        SignerAccount::check(signer)?;
+        ProgramAccount::check(escrow)?;
+        ReleaseApprovalsAccount::check(approvals)?;
+
+        Ok(Self {
+            signer,
+            escrow,
+            approvals,
+        })
+    }
+}
+
+pub struct ApproveRelease<'a> {
+    pub accounts: ApproveReleaseAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for ApproveRelease<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = ApproveReleaseAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> ApproveRelease<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &54;
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.approvals.try_borrow_mut()?;
+        let approvals = crate::state::ReleaseApprovals::load_mut(data.as_mut())?;
+        if approvals.escrow.ne(self.accounts.escrow.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !approvals.approve(self.accounts.signer.address()) {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+        Ok(())
+    }
+}
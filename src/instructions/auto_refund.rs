@@ -0,0 +1,57 @@
+use pinocchio::{AccountView, ProgramResult, error::ProgramError};
+
+use crate::{CloseExpired, CloseExpiredAccounts, helpers::*};
+
+pub struct AutoRefund<'a> {
+    pub accounts: CloseExpiredAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for AutoRefund<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let mint_a = next_account(&mut iter)?;
+        let vault = next_account(&mut iter)?;
+        let maker_ata_a = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_a)?;
+        EscrowVault::check(vault, escrow, mint_a, token_program)?;
+        AssociatedTokenAccount::check(maker_ata_a, maker, mint_a, token_program)?;
+        ConfigAccount::check(config)?;
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            accounts: CloseExpiredAccounts {
+                payer: maker,
+                maker,
+                escrow,
+                mint_a,
+                vault,
+                maker_ata_a,
+                system_program,
+                token_program,
+                config,
+                remaining_accounts,
+            },
+        })
+    }
+}
+
+impl<'a> AutoRefund<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &36;
+    pub fn process(&mut self) -> ProgramResult {
+        CloseExpired {
+            accounts: CloseExpiredAccounts { ..self.accounts },
+        }
+        .process()
+    }
+}
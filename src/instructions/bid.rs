@@ -0,0 +1,195 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_system::create_account_with_minimum_balance_signed;
+
+use crate::helpers::*;
+
+pub struct BidAccounts<'a> {
+    pub bidder: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub bidder_ata_b: &'a AccountView,
+    pub bid: &'a AccountView,
+    pub bid_vault: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for BidAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let bidder = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let bidder_ata_b = next_account(&mut iter)?;
+        let bid = next_account(&mut iter)?;
+        let bid_vault = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(bidder)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_b)?;
+        AssociatedTokenAccount::check(bidder_ata_b, bidder, mint_b, token_program)?;
+
+        if !bid.is_data_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        let (bid_vault_key, _) = Address::find_program_address(
+            &[
+                bid.address().as_ref(),
+                token_program.address().as_ref(),
+                mint_b.address().as_ref(),
+            ],
+            &pinocchio_associated_token_account::ID,
+        );
+        if bid_vault.address().ne(&bid_vault_key) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if !bid_vault.is_data_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        ConfigAccount::check(config)?;
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            bidder,
+            escrow,
+            mint_b,
+            bidder_ata_b,
+            bid,
+            bid_vault,
+            system_program,
+            token_program,
+            config,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct BidInstructionData {
+    pub amount: u64,
+}
+impl<'a> TryFrom<&'a [u8]> for BidInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let amount = u64::from_le_bytes(instruction_bytes(data, 0)?);
+        if amount == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self { amount })
+    }
+}
+
+pub struct Bid<'a> {
+    pub accounts: BidAccounts<'a>,
+    pub instruction_data: BidInstructionData,
+    pub bump: u8,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Bid<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = BidAccounts::try_from(accounts)?;
+        let instruction_data = BidInstructionData::try_from(data)?;
+        let (bid_key, bump) = Address::find_program_address(
+            &[
+                b"bid",
+                accounts.escrow.address().as_ref(),
+                accounts.bidder.address().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if bid_key.ne(accounts.bid.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let bump_binding = [bump];
+        let bid_seeds = [
+            Seed::from(b"bid"),
+            Seed::from(accounts.escrow.address().as_ref()),
+            Seed::from(accounts.bidder.address().as_ref()),
+            Seed::from(&bump_binding),
+        ];
+        let signers = [Signer::from(&bid_seeds)];
+        create_account_with_minimum_balance_signed(
+            accounts.bid,
+            crate::state::AuctionBid::LEN,
+            &crate::ID,
+            accounts.bidder,
+            None,
+            &signers,
+        )?;
+        AssociatedTokenAccount::init(
+            accounts.bid_vault,
+            accounts.mint_b,
+            accounts.bidder,
+            accounts.bid,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> Bid<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &3;
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.escrow.try_borrow_mut()?;
+        let escrow = crate::state::Escrow::load_mut(data.as_mut())?;
+
+        if !escrow.is_auction() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.mint_b.ne(self.accounts.mint_b.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.is_expired(&Clock::get()?) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let amount = self.instruction_data.amount;
+        if amount < escrow.receive || amount <= escrow.highest_bid {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        TokenInterface::transfer_checked(
+            self.accounts.bidder_ata_b,
+            self.accounts.mint_b,
+            self.accounts.bid_vault,
+            self.accounts.bidder,
+            amount,
+            &[],
+        )?;
+
+        let mut bid_data = self.accounts.bid.try_borrow_mut()?;
+        let bid = crate::state::AuctionBid::load_mut(bid_data.as_mut())?;
+        bid.set_inner(
+            *self.accounts.escrow.address(),
+            *self.accounts.bidder.address(),
+            amount,
+            [self.bump],
+        );
+        escrow.set_highest_bid(amount, *self.accounts.bidder.address());
+        escrow.set_last_update_seq(next_seq(self.accounts.config)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use pinocchio::{
+    AccountView, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+};
+
+use crate::helpers::*;
+
+pub struct CancelCounterAccounts<'a> {
+    pub taker: &'a AccountView,
+    pub counter: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub counter_vault: &'a AccountView,
+    pub taker_ata_b: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for CancelCounterAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let taker = next_account(&mut iter)?;
+        let counter = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let counter_vault = next_account(&mut iter)?;
+        let taker_ata_b = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(taker)?;
+        CounterOfferAccount::check(counter)?;
+        MintInterface::check(mint_b)?;
+        AssociatedTokenAccount::check(counter_vault, counter, mint_b, token_program)?;
+        AssociatedTokenAccount::check(taker_ata_b, taker, mint_b, token_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            taker,
+            counter,
+            mint_b,
+            counter_vault,
+            taker_ata_b,
+            token_program,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct CancelCounter<'a> {
+    pub accounts: CancelCounterAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for CancelCounter<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = CancelCounterAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> CancelCounter<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &8;
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.counter.try_borrow()?;
+        let counter = crate::state::CounterOffer::load(&data)?;
+        check_program_pda(
+            self.accounts.counter,
+            &[
+                b"counter",
+                counter.escrow.as_ref(),
+                counter.taker.as_ref(),
+                &counter.bump,
+            ],
+        )?;
+
+        if counter.taker.ne(self.accounts.taker.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let bump_binding = counter.bump;
+        let counter_seeds = [
+            Seed::from(b"counter"),
+            Seed::from(counter.escrow.as_ref()),
+            Seed::from(counter.taker.as_ref()),
+            Seed::from(bump_binding.as_ref()),
+        ];
+        let signer = Signer::from(&counter_seeds);
+        let amount = TokenInterface::get_amount(self.accounts.counter_vault)?;
+
+        TokenInterface::transfer_checked(
+            self.accounts.counter_vault,
+            self.accounts.mint_b,
+            self.accounts.taker_ata_b,
+            self.accounts.counter,
+            amount,
+            core::slice::from_ref(&signer),
+        )?;
+        TokenInterface::close_account(
+            self.accounts.counter_vault,
+            self.accounts.taker,
+            self.accounts.counter,
+            core::slice::from_ref(&signer),
+        )?;
+
+        drop(data);
+        ProgramAccount::close(self.accounts.counter, self.accounts.taker)?;
+        Ok(())
+    }
+}
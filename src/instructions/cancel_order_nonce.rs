@@ -0,0 +1,62 @@
+use blueshift_escrow_derive::Accounts;
+use pinocchio::{AccountView, ProgramResult, error::ProgramError};
+
+use crate::{errors::EscrowError, helpers::*};
+
+#[derive(Accounts)]
+pub struct CancelOrderNonceAccounts<'a> {
+    #[account(signer)]
+    pub maker: &'a AccountView,
+    pub order_nonce: &'a AccountView,
+    #[account(check = SystemProgramAccount)]
+    pub system_program: &'a AccountView,
+}
+
+pub struct CancelOrderNonceInstructionData {
+    pub nonce: u64,
+}
+impl<'a> TryFrom<&'a [u8]> for CancelOrderNonceInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let nonce = u64::from_le_bytes(instruction_bytes(data, 0)?);
+        Ok(Self { nonce })
+    }
+}
+
+pub struct CancelOrderNonce<'a> {
+    pub accounts: CancelOrderNonceAccounts<'a>,
+    pub instruction_data: CancelOrderNonceInstructionData,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for CancelOrderNonce<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = CancelOrderNonceAccounts::try_from(accounts)?;
+        let instruction_data = CancelOrderNonceInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> CancelOrderNonce<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &41;
+
+    pub fn process(&mut self) -> ProgramResult {
+        OrderNonceAccount::init_if_needed(
+            self.accounts.order_nonce,
+            self.accounts.maker.address(),
+            self.accounts.maker,
+        )?;
+        let mut data = self.accounts.order_nonce.try_borrow_mut()?;
+        if !crate::state::OrderNonce::load_mut(data.as_mut())?.consume(self.instruction_data.nonce)
+        {
+            return Err(EscrowError::StaleOrder.into());
+        }
+        Ok(())
+    }
+}
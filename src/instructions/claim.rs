@@ -0,0 +1,141 @@
+use pinocchio::{
+    AccountView, ProgramResult,
+    cpi::Signer,
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::helpers::*;
+
+pub struct ClaimAccounts<'a> {
+    pub taker: &'a AccountView,
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub mint_a: &'a AccountView,
+    pub vault: &'a AccountView,
+    pub taker_ata_a: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for ClaimAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let taker = next_account(&mut iter)?;
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let mint_a = next_account(&mut iter)?;
+        let vault = next_account(&mut iter)?;
+        let taker_ata_a = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(taker)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_a)?;
+        EscrowVault::check(vault, escrow, mint_a, token_program)?;
+        AssociatedTokenAccount::check(taker_ata_a, taker, mint_a, token_program)?;
+        ConfigAccount::check(config)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            taker,
+            maker,
+            escrow,
+            mint_a,
+            vault,
+            taker_ata_a,
+            token_program,
+            config,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct Claim<'a> {
+    pub accounts: ClaimAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for Claim<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = ClaimAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> Claim<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &10;
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.escrow.try_borrow()?;
+        let escrow = crate::state::Escrow::load(&data)?;
+
+        if !escrow.is_vesting() || escrow.vesting_start == 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.taker.ne(self.accounts.taker.address())
+            || escrow.maker.ne(self.accounts.maker.address())
+            || escrow.mint_a.ne(self.accounts.mint_a.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let vested = escrow.vested_amount(Clock::get()?.unix_timestamp);
+        let claimable = vested.saturating_sub(escrow.claimed);
+        if claimable == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let claimed = escrow.claimed + claimable;
+        let fully_claimed = claimed == escrow.amount;
+
+        let escrow_seeds = EscrowSeeds::from_escrow(escrow);
+        let seeds = escrow_seeds.seeds();
+        let signer = Signer::from(&seeds);
+
+        TokenInterface::transfer_checked(
+            self.accounts.vault,
+            self.accounts.mint_a,
+            self.accounts.taker_ata_a,
+            self.accounts.escrow,
+            claimable,
+            core::slice::from_ref(&signer),
+        )?;
+        if fully_claimed {
+            TokenInterface::close_account(
+                self.accounts.vault,
+                self.accounts.maker,
+                self.accounts.escrow,
+                core::slice::from_ref(&signer),
+            )?;
+            let mut optional_accounts = self.accounts.remaining_accounts.iter();
+            let maker_index = if escrow.is_indexed() {
+                Some(next_account(&mut optional_accounts)?)
+            } else {
+                None
+            };
+            MakerIndexAccount::release(escrow, maker_index)?;
+            close_escrow_extensions(
+                escrow,
+                self.accounts.escrow,
+                &mut optional_accounts,
+                self.accounts.maker,
+            )?;
+        }
+
+        drop(data);
+        let seq = next_seq(self.accounts.config)?;
+        if fully_claimed {
+            ProgramAccount::close(self.accounts.escrow, self.accounts.taker)?;
+        } else {
+            let mut data = self.accounts.escrow.try_borrow_mut()?;
+            let escrow = crate::state::Escrow::load_mut(data.as_mut())?;
+            escrow.set_claimed(claimed);
+            escrow.set_last_update_seq(seq);
+        }
+        Ok(())
+    }
+}
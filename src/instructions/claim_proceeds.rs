@@ -0,0 +1,135 @@
+use pinocchio::{
+    AccountView, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::helpers::*;
+
+pub struct ClaimProceedsAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub lock: &'a AccountView,
+    pub lock_vault: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub maker_ata_b: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for ClaimProceedsAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let lock = next_account(&mut iter)?;
+        let lock_vault = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let maker_ata_b = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(maker)?;
+        MintInterface::check(mint_b)?;
+        AssociatedTokenAccount::check(lock_vault, lock, mint_b, token_program)?;
+        if !lock.owned_by(&crate::ID) || lock.data_len().ne(&crate::state::ProceedsLock::LEN) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            maker,
+            escrow,
+            lock,
+            lock_vault,
+            mint_b,
+            maker_ata_b,
+            system_program,
+            token_program,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct ClaimProceeds<'a> {
+    pub accounts: ClaimProceedsAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for ClaimProceeds<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = ClaimProceedsAccounts::try_from(accounts)?;
+        init_destination_if_needed(
+            accounts.maker_ata_b,
+            accounts.mint_b,
+            accounts.maker,
+            accounts.maker,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> ClaimProceeds<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &31;
+    pub fn process(&mut self) -> ProgramResult {
+        let lock = *crate::state::ProceedsLock::load(&self.accounts.lock.try_borrow()?)?;
+        if lock.maker.ne(self.accounts.maker.address())
+            || lock.escrow.ne(self.accounts.escrow.address())
+            || lock.vault.ne(self.accounts.lock_vault.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let balance = TokenInterface::get_amount(self.accounts.lock_vault)?;
+        let claimable = lock.claimable(balance, Clock::get()?.unix_timestamp);
+        let escrow_open = self.accounts.escrow.owned_by(&crate::ID)
+            && self
+                .accounts
+                .escrow
+                .data_len()
+                .eq(&crate::state::Escrow::LEN);
+        let drained = !escrow_open && claimable == balance;
+        if claimable == 0 && !drained {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let lock_seeds = [
+            Seed::from(crate::state::ProceedsLock::PREFIX),
+            Seed::from(lock.escrow.as_ref()),
+            Seed::from(&lock.bump),
+        ];
+        let signer = Signer::from(&lock_seeds);
+        if claimable != 0 {
+            TokenInterface::transfer_checked(
+                self.accounts.lock_vault,
+                self.accounts.mint_b,
+                self.accounts.maker_ata_b,
+                self.accounts.lock,
+                claimable,
+                core::slice::from_ref(&signer),
+            )?;
+        }
+        if drained {
+            TokenInterface::close_account(
+                self.accounts.lock_vault,
+                self.accounts.maker,
+                self.accounts.lock,
+                core::slice::from_ref(&signer),
+            )?;
+            return ProgramAccount::close(self.accounts.lock, self.accounts.maker);
+        }
+
+        let mut data = self.accounts.lock.try_borrow_mut()?;
+        let lock = crate::state::ProceedsLock::load_mut(data.as_mut())?;
+        lock.claimed += claimable;
+        Ok(())
+    }
+}
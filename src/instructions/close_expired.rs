@@ -0,0 +1,169 @@
+use pinocchio::{
+    AccountView, ProgramResult,
+    cpi::Signer,
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::{errors::EscrowError, helpers::*};
+
+pub struct CloseExpiredAccounts<'a> {
+    pub payer: &'a AccountView,
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub mint_a: &'a AccountView,
+    pub vault: &'a AccountView,
+    pub maker_ata_a: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for CloseExpiredAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let payer = next_account(&mut iter)?;
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let mint_a = next_account(&mut iter)?;
+        let vault = next_account(&mut iter)?;
+        let maker_ata_a = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(payer)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_a)?;
+        EscrowVault::check(vault, escrow, mint_a, token_program)?;
+        ConfigAccount::check(config)?;
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            payer,
+            maker,
+            escrow,
+            mint_a,
+            vault,
+            maker_ata_a,
+            system_program,
+            token_program,
+            config,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct CloseExpired<'a> {
+    pub accounts: CloseExpiredAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for CloseExpired<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = CloseExpiredAccounts::try_from(accounts)?;
+        init_destination_if_needed(
+            accounts.maker_ata_a,
+            accounts.mint_a,
+            accounts.payer,
+            accounts.maker,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> CloseExpired<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &14;
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.escrow.try_borrow()?;
+        let escrow = crate::state::Escrow::load(&data)?;
+        if escrow.is_auction() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.is_vesting() && escrow.vesting_start != 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !escrow.has_deadline() || !escrow.is_expired(&Clock::get()?) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        if escrow.maker.ne(self.accounts.maker.address())
+            || escrow.mint_a.ne(self.accounts.mint_a.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let escrow_seeds = EscrowSeeds::from_escrow(escrow);
+        let seeds = escrow_seeds.seeds();
+        let signer = Signer::from(&seeds);
+        let amount = TokenInterface::get_amount(self.accounts.vault)?;
+
+        TokenInterface::transfer_checked(
+            self.accounts.vault,
+            self.accounts.mint_a,
+            self.accounts.maker_ata_a,
+            self.accounts.escrow,
+            amount,
+            core::slice::from_ref(&signer),
+        )?;
+        TokenInterface::close_account(
+            self.accounts.vault,
+            self.accounts.maker,
+            self.accounts.escrow,
+            core::slice::from_ref(&signer),
+        )?;
+
+        let mut optional_accounts = self.accounts.remaining_accounts.iter();
+        let maker_index = if escrow.is_indexed() {
+            Some(next_account(&mut optional_accounts)?)
+        } else {
+            None
+        };
+        MakerIndexAccount::release(escrow, maker_index)?;
+        close_escrow_extensions(
+            escrow,
+            self.accounts.escrow,
+            &mut optional_accounts,
+            self.accounts.maker,
+        )?;
+        drop(data);
+        next_seq(self.accounts.config)?;
+        self.pay_closer()?;
+        ProgramAccount::close(self.accounts.escrow, self.accounts.maker)?;
+        Ok(())
+    }
+
+    fn pay_closer(&self) -> ProgramResult {
+        if self
+            .accounts
+            .payer
+            .address()
+            .eq(self.accounts.maker.address())
+        {
+            return Ok(());
+        }
+        let lamports = self.accounts.escrow.lamports();
+        let reward = {
+            let data = self.accounts.config.try_borrow()?;
+            crate::state::Config::load(&data)?.closer_reward(lamports)
+        };
+        if reward == 0 {
+            return Ok(());
+        }
+        let payer_lamports = self
+            .accounts
+            .payer
+            .lamports()
+            .checked_add(reward)
+            .ok_or(EscrowError::LamportOverflow)?;
+        self.accounts.escrow.set_lamports(lamports - reward);
+        self.accounts.payer.set_lamports(payer_lamports);
+        Ok(())
+    }
+}
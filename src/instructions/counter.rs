@@ -0,0 +1,184 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+};
+use pinocchio_system::create_account_with_minimum_balance_signed;
+
+use crate::helpers::*;
+
+pub struct CounterAccounts<'a> {
+    pub taker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub taker_ata_b: &'a AccountView,
+    pub counter: &'a AccountView,
+    pub counter_vault: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for CounterAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let taker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let taker_ata_b = next_account(&mut iter)?;
+        let counter = next_account(&mut iter)?;
+        let counter_vault = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(taker)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_b)?;
+        AssociatedTokenAccount::check(taker_ata_b, taker, mint_b, token_program)?;
+
+        if !counter.is_data_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        let (counter_vault_key, _) = Address::find_program_address(
+            &[
+                counter.address().as_ref(),
+                token_program.address().as_ref(),
+                mint_b.address().as_ref(),
+            ],
+            &pinocchio_associated_token_account::ID,
+        );
+        if counter_vault.address().ne(&counter_vault_key) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if !counter_vault.is_data_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            taker,
+            escrow,
+            mint_b,
+            taker_ata_b,
+            counter,
+            counter_vault,
+            system_program,
+            token_program,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct CounterInstructionData {
+    pub receive: u64,
+}
+impl<'a> TryFrom<&'a [u8]> for CounterInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let receive = u64::from_le_bytes(instruction_bytes(data, 0)?);
+        if receive == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self { receive })
+    }
+}
+
+pub struct Counter<'a> {
+    pub accounts: CounterAccounts<'a>,
+    pub instruction_data: CounterInstructionData,
+    pub bump: u8,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Counter<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = CounterAccounts::try_from(accounts)?;
+        let instruction_data = CounterInstructionData::try_from(data)?;
+        let (counter_key, bump) = Address::find_program_address(
+            &[
+                b"counter",
+                accounts.escrow.address().as_ref(),
+                accounts.taker.address().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if counter_key.ne(accounts.counter.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let bump_binding = [bump];
+        let counter_seeds = [
+            Seed::from(b"counter"),
+            Seed::from(accounts.escrow.address().as_ref()),
+            Seed::from(accounts.taker.address().as_ref()),
+            Seed::from(&bump_binding),
+        ];
+        let signers = [Signer::from(&counter_seeds)];
+        create_account_with_minimum_balance_signed(
+            accounts.counter,
+            crate::state::CounterOffer::LEN,
+            &crate::ID,
+            accounts.taker,
+            None,
+            &signers,
+        )?;
+        AssociatedTokenAccount::init(
+            accounts.counter_vault,
+            accounts.mint_b,
+            accounts.taker,
+            accounts.counter,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> Counter<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &6;
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.escrow.try_borrow()?;
+        let escrow = crate::state::Escrow::load(&data)?;
+
+        if escrow.is_auction() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.mint_b.ne(self.accounts.mint_b.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let receive = self.instruction_data.receive;
+        if receive == escrow.receive {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        TokenInterface::transfer_checked(
+            self.accounts.taker_ata_b,
+            self.accounts.mint_b,
+            self.accounts.counter_vault,
+            self.accounts.taker,
+            receive,
+            &[],
+        )?;
+
+        let mut counter_data = self.accounts.counter.try_borrow_mut()?;
+        let counter = crate::state::CounterOffer::load_mut(counter_data.as_mut())?;
+        counter.set_inner(
+            *self.accounts.escrow.address(),
+            *self.accounts.taker.address(),
+            receive,
+            [self.bump],
+        );
+        Ok(())
+    }
+}
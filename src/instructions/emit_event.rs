@@ -0,0 +1,32 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::helpers::*;
+
+pub struct EmitEvent<'a> {
+    pub event_authority: &'a AccountView,
+}
+impl<'a> TryFrom<&'a [AccountView]> for EmitEvent<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let event_authority = next_account(&mut iter)?;
+        if !event_authority.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        let (authority_key, _) =
+            Address::find_program_address(&[EmitEvent::AUTHORITY_SEED], &crate::ID);
+        if authority_key.ne(event_authority.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        Ok(Self { event_authority })
+    }
+}
+
+impl<'a> EmitEvent<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &42;
+    pub const AUTHORITY_SEED: &'static [u8] = b"__event_authority";
+
+    pub fn process(&mut self) -> ProgramResult {
+        Ok(())
+    }
+}
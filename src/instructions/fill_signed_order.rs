@@ -0,0 +1,264 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::{TakeSigned, errors::EscrowError, helpers::*};
+
+pub struct FillSignedOrderAccounts<'a> {
+    pub taker: &'a AccountView,
+    pub maker: &'a AccountView,
+    pub mint_a: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub maker_ata_a: &'a AccountView,
+    pub taker_ata_a: &'a AccountView,
+    pub taker_ata_b: &'a AccountView,
+    pub maker_ata_b: &'a AccountView,
+    pub order_nonce: &'a AccountView,
+    pub delegate: &'a AccountView,
+    pub instructions: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+    pub event_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for FillSignedOrderAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let taker = next_account(&mut iter)?;
+        let maker = next_account(&mut iter)?;
+        let mint_a = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let maker_ata_a = next_account(&mut iter)?;
+        let taker_ata_a = next_account(&mut iter)?;
+        let taker_ata_b = next_account(&mut iter)?;
+        let maker_ata_b = next_account(&mut iter)?;
+        let order_nonce = next_account(&mut iter)?;
+        let delegate = next_account(&mut iter)?;
+        let instructions = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let (remaining_accounts, event_accounts) =
+            crate::events::split_event_accounts(iter.as_slice())?;
+
+        SignerAccount::check(taker)?;
+        MintInterface::check(mint_a)?;
+        MintInterface::check(mint_b)?;
+        AssociatedTokenAccount::check(maker_ata_a, maker, mint_a, token_program)?;
+        OwnedTokenAccount::check(taker_ata_b, taker, mint_b)?;
+        ConfigAccount::check(config)?;
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            taker,
+            maker,
+            mint_a,
+            mint_b,
+            maker_ata_a,
+            taker_ata_a,
+            taker_ata_b,
+            maker_ata_b,
+            order_nonce,
+            delegate,
+            instructions,
+            system_program,
+            token_program,
+            config,
+            remaining_accounts,
+            event_accounts,
+        })
+    }
+}
+
+pub struct FillSignedOrderInstructionData {
+    pub maker: Address,
+    pub taker: Address,
+    pub mint_a: Address,
+    pub mint_b: Address,
+    pub amount: u64,
+    pub receive: u64,
+    pub expiry: i64,
+    pub nonce: u64,
+}
+impl FillSignedOrderInstructionData {
+    pub const LEN: usize = size_of::<Address>() * 4 + size_of::<u64>() * 4;
+
+    pub fn message(&self) -> [u8; Self::LEN] {
+        let mut message = [0u8; Self::LEN];
+        message[0..32].copy_from_slice(self.maker.as_ref());
+        message[32..64].copy_from_slice(self.taker.as_ref());
+        message[64..96].copy_from_slice(self.mint_a.as_ref());
+        message[96..128].copy_from_slice(self.mint_b.as_ref());
+        message[128..136].copy_from_slice(&self.amount.to_le_bytes());
+        message[136..144].copy_from_slice(&self.receive.to_le_bytes());
+        message[144..152].copy_from_slice(&self.expiry.to_le_bytes());
+        message[152..160].copy_from_slice(&self.nonce.to_le_bytes());
+        message
+    }
+}
+impl<'a> TryFrom<&'a [u8]> for FillSignedOrderInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let order = Self {
+            maker: Address::new_from_array(instruction_bytes(data, 0)?),
+            taker: Address::new_from_array(instruction_bytes(data, 32)?),
+            mint_a: Address::new_from_array(instruction_bytes(data, 64)?),
+            mint_b: Address::new_from_array(instruction_bytes(data, 96)?),
+            amount: u64::from_le_bytes(instruction_bytes(data, 128)?),
+            receive: u64::from_le_bytes(instruction_bytes(data, 136)?),
+            expiry: i64::from_le_bytes(instruction_bytes(data, 144)?),
+            nonce: u64::from_le_bytes(instruction_bytes(data, 152)?),
+        };
+        if order.amount == 0 || order.receive == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(order)
+    }
+}
+
+pub struct FillSignedOrder<'a> {
+    pub accounts: FillSignedOrderAccounts<'a>,
+    pub instruction_data: FillSignedOrderInstructionData,
+    pub delegate_bump: u8,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for FillSignedOrder<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = FillSignedOrderAccounts::try_from(accounts)?;
+        let instruction_data = FillSignedOrderInstructionData::try_from(data)?;
+        let (delegate_key, delegate_bump) =
+            Address::find_program_address(&[TakeSigned::DELEGATE_SEED], &crate::ID);
+        if delegate_key.ne(accounts.delegate.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let mut optional_accounts = accounts.remaining_accounts.iter();
+        if DenylistAccount::is_enabled(accounts.config)? {
+            DenylistAccount::check_parties(
+                next_account(&mut optional_accounts)?,
+                &[accounts.taker.address(), accounts.maker.address()],
+            )?;
+        }
+        CallerPolicy::check(accounts.config, &mut optional_accounts)?;
+        init_destination_if_needed(
+            accounts.taker_ata_a,
+            accounts.mint_a,
+            accounts.taker,
+            accounts.taker,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        init_destination_if_needed(
+            accounts.maker_ata_b,
+            accounts.mint_b,
+            accounts.taker,
+            accounts.maker,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        OwnedTokenAccount::check(accounts.maker_ata_b, accounts.maker, accounts.mint_b)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+            delegate_bump,
+        })
+    }
+}
+
+impl<'a> FillSignedOrder<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &40;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let order = &self.instruction_data;
+        #[cfg(feature = "strict")]
+        MintPolicy::check_pair(self.accounts.mint_a, self.accounts.mint_b, 0)?;
+        MintPolicy::check_unpaused(self.accounts.mint_a, self.accounts.mint_b)?;
+        if order.maker.ne(self.accounts.maker.address())
+            || order.mint_a.ne(self.accounts.mint_a.address())
+            || order.mint_b.ne(self.accounts.mint_b.address())
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if order.taker.ne(&Address::default()) && order.taker.ne(self.accounts.taker.address()) {
+            return Err(EscrowError::TakerNotAllowed.into());
+        }
+        let clock = Clock::get()?;
+        if order.expiry != 0 && clock.unix_timestamp > order.expiry {
+            return Err(ProgramError::InvalidArgument);
+        }
+        verify_ed25519_instruction(
+            self.accounts.instructions,
+            self.accounts.maker.address(),
+            &order.message(),
+        )?;
+        self.consume_nonce()?;
+
+        let delegate_bump_binding = [self.delegate_bump];
+        let delegate_seeds = [
+            Seed::from(TakeSigned::DELEGATE_SEED),
+            Seed::from(&delegate_bump_binding),
+        ];
+        TokenInterface::transfer_checked(
+            self.accounts.maker_ata_a,
+            self.accounts.mint_a,
+            self.accounts.taker_ata_a,
+            self.accounts.delegate,
+            order.amount,
+            &[Signer::from(&delegate_seeds)],
+        )?;
+        TokenInterface::transfer_checked(
+            self.accounts.taker_ata_b,
+            self.accounts.mint_b,
+            self.accounts.maker_ata_b,
+            self.accounts.taker,
+            order.receive,
+            &[],
+        )?;
+
+        let scale_a = UiAmount::scale(self.accounts.mint_a, clock.unix_timestamp)?;
+        let scale_b = UiAmount::scale(self.accounts.mint_b, clock.unix_timestamp)?;
+        crate::events::FillEvent {
+            escrow: *self.accounts.order_nonce.address(),
+            maker: order.maker,
+            taker: *self.accounts.taker.address(),
+            mint_a: order.mint_a,
+            mint_b: order.mint_b,
+            amount_a: order.amount,
+            amount_b: order.receive,
+            ui_amount_a: UiAmount::to_ui(order.amount, scale_a),
+            ui_amount_b: UiAmount::to_ui(order.receive, scale_b),
+            fills: order.nonce,
+            label: [0; 32],
+        }
+        .emit_cpi(self.accounts.event_accounts)?;
+        next_seq(self.accounts.config)?;
+        Ok(())
+    }
+
+    fn consume_nonce(&self) -> ProgramResult {
+        let order_nonce = self.accounts.order_nonce;
+        OrderNonceAccount::init_if_needed(
+            order_nonce,
+            self.accounts.maker.address(),
+            self.accounts.taker,
+        )?;
+        let mut data = order_nonce.try_borrow_mut()?;
+        if !crate::state::OrderNonce::load_mut(data.as_mut())?.consume(self.instruction_data.nonce)
+        {
+            return Err(EscrowError::StaleOrder.into());
+        }
+        Ok(())
+    }
+}
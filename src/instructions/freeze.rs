@@ -0,0 +1,83 @@
+use pinocchio::{AccountView, ProgramResult, error::ProgramError};
+
+use crate::helpers::*;
+
+pub struct FreezeAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for FreezeAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+        ConfigAccount::check(config)?;
+
+        Ok(Self {
+            maker,
+            escrow,
+            config,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct FreezeInstructionData {
+    pub frozen: bool,
+}
+impl<'a> TryFrom<&'a [u8]> for FreezeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        match data {
+            [0] => Ok(Self { frozen: false }),
+            [1] => Ok(Self { frozen: true }),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+pub struct Freeze<'a> {
+    pub accounts: FreezeAccounts<'a>,
+    pub instruction_data: FreezeInstructionData,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Freeze<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = FreezeAccounts::try_from(accounts)?;
+        let instruction_data = FreezeInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> Freeze<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &21;
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.escrow.try_borrow_mut()?;
+        let escrow = crate::state::Escrow::load_mut(data.as_mut())?;
+
+        if escrow.maker.ne(self.accounts.maker.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.is_frozen() == self.instruction_data.frozen {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        escrow.set_frozen(self.instruction_data.frozen);
+        escrow.set_last_update_seq(next_seq(self.accounts.config)?);
+        Ok(())
+    }
+}
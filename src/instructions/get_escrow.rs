@@ -0,0 +1,95 @@
+use blueshift_escrow_derive::Accounts;
+use pinocchio::{
+    AccountView, ProgramResult,
+    cpi::set_return_data,
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::helpers::*;
+
+pub const ESCROW_VIEW_VERSION: u8 = 1;
+pub const ESCROW_VIEW_LEN: usize = 179;
+pub const ESCROW_UI_VIEW_LEN: usize = ESCROW_VIEW_LEN + 25;
+
+pub fn serialize_escrow_view(escrow: &crate::state::Escrow) -> [u8; ESCROW_VIEW_LEN] {
+    let mut view = [0u8; ESCROW_VIEW_LEN];
+    let mut offset = 0;
+    let mut write = |bytes: &[u8]| {
+        view[offset..offset + bytes.len()].copy_from_slice(bytes);
+        offset += bytes.len();
+    };
+    write(&[ESCROW_VIEW_VERSION]);
+    write(&escrow.seed.to_le_bytes());
+    write(escrow.maker.as_ref());
+    write(escrow.mint_a.as_ref());
+    write(escrow.mint_b.as_ref());
+    write(&escrow.receive.to_le_bytes());
+    write(&escrow.amount.to_le_bytes());
+    write(&escrow.deadline.to_le_bytes());
+    write(&[escrow.deadline_kind, escrow.flags]);
+    write(&escrow.fills.to_le_bytes());
+    write(&escrow.last_update_seq.to_le_bytes());
+    write(&escrow.label);
+    view
+}
+
+pub fn serialize_escrow_ui_view(
+    escrow: &crate::state::Escrow,
+    scale_a: u128,
+    scale_b: u128,
+) -> Result<[u8; ESCROW_UI_VIEW_LEN], ProgramError> {
+    let (raw_receive, ui_receive) = if escrow.is_receive_in_ui() {
+        (UiAmount::from_ui(escrow.receive, scale_b)?, escrow.receive)
+    } else {
+        (escrow.receive, UiAmount::to_ui(escrow.receive, scale_b))
+    };
+    let mut view = [0u8; ESCROW_UI_VIEW_LEN];
+    view[..ESCROW_VIEW_LEN].copy_from_slice(&serialize_escrow_view(escrow));
+    view[ESCROW_VIEW_LEN] = escrow.receive_in_ui;
+    view[ESCROW_VIEW_LEN + 1..ESCROW_VIEW_LEN + 9]
+        .copy_from_slice(&UiAmount::to_ui(escrow.amount, scale_a).to_le_bytes());
+    view[ESCROW_VIEW_LEN + 9..ESCROW_VIEW_LEN + 17].copy_from_slice(&raw_receive.to_le_bytes());
+    view[ESCROW_VIEW_LEN + 17..].copy_from_slice(&ui_receive.to_le_bytes());
+    Ok(view)
+}
+
+#[derive(Accounts)]
+pub struct GetEscrowAccounts<'a> {
+    #[account(check = ProgramAccount)]
+    pub escrow: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+pub struct GetEscrow<'a> {
+    pub accounts: GetEscrowAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for GetEscrow<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = GetEscrowAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> GetEscrow<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &15;
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.escrow.try_borrow()?;
+        let escrow = crate::state::Escrow::load(&data)?;
+        let [mint_a, mint_b, ..] = self.accounts.remaining_accounts else {
+            set_return_data(&serialize_escrow_view(escrow));
+            return Ok(());
+        };
+        if mint_a.address().ne(&escrow.mint_a) || mint_b.address().ne(&escrow.mint_b) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        set_return_data(&serialize_escrow_ui_view(
+            escrow,
+            UiAmount::scale(mint_a, unix_timestamp)?,
+            UiAmount::scale(mint_b, unix_timestamp)?,
+        )?);
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+};
+use pinocchio_system::create_account_with_minimum_balance_signed;
+
+use crate::helpers::*;
+
+pub struct InitializeConfigAccounts<'a> {
+    pub admin: &'a AccountView,
+    pub config: &'a AccountView,
+    pub treasury: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for InitializeConfigAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let admin = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let treasury = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(admin)?;
+        if !config.is_data_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        SystemProgramAccount::check(system_program)?;
+
+        Ok(Self {
+            admin,
+            config,
+            treasury,
+            system_program,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct InitializeConfigInstructionData {
+    pub referral_bps: u16,
+    pub max_open_escrows: u16,
+    pub closer_pct: u8,
+}
+impl<'a> TryFrom<&'a [u8]> for InitializeConfigInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (referral_bps, max_open_escrows, closer_pct) = match data.len() {
+            0 => (0, 0, 0),
+            2 => (u16::from_le_bytes(instruction_bytes(data, 0)?), 0, 0),
+            4 | 5 => (
+                u16::from_le_bytes(instruction_bytes(data, 0)?),
+                u16::from_le_bytes(instruction_bytes(data, 2)?),
+                data.get(4).copied().unwrap_or(0),
+            ),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+        if referral_bps > crate::state::Config::MAX_REFERRAL_BPS
+            || closer_pct > crate::state::Config::MAX_CLOSER_PCT
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self {
+            referral_bps,
+            max_open_escrows,
+            closer_pct,
+        })
+    }
+}
+
+pub struct InitializeConfig<'a> {
+    pub accounts: InitializeConfigAccounts<'a>,
+    pub instruction_data: InitializeConfigInstructionData,
+    pub bump: u8,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for InitializeConfig<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = InitializeConfigAccounts::try_from(accounts)?;
+        let instruction_data = InitializeConfigInstructionData::try_from(data)?;
+        let (config_key, bump) = Address::find_program_address(&[b"config"], &crate::ID);
+        if config_key.ne(accounts.config.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let bump_binding = [bump];
+        let config_seeds = [Seed::from(b"config"), Seed::from(&bump_binding)];
+        create_account_with_minimum_balance_signed(
+            accounts.config,
+            crate::state::Config::LEN,
+            &crate::ID,
+            accounts.admin,
+            None,
+            &[Signer::from(&config_seeds)],
+        )?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> InitializeConfig<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &11;
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.config.try_borrow_mut()?;
+        let config = crate::state::Config::load_mut(data.as_mut())?;
+        config.set_inner(
+            *self.accounts.admin.address(),
+            *self.accounts.treasury.address(),
+            [self.bump],
+        );
+        config.set_referral_bps(self.instruction_data.referral_bps);
+        config.set_max_open_escrows(self.instruction_data.max_open_escrows);
+        config.set_closer_pct(self.instruction_data.closer_pct);
+        Ok(())
+    }
+}
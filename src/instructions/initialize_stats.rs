@@ -0,0 +1,89 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+};
+use pinocchio_system::create_account_with_minimum_balance_signed;
+
+use crate::helpers::*;
+
+pub struct InitializeStatsAccounts<'a> {
+    pub admin: &'a AccountView,
+    pub config: &'a AccountView,
+    pub stats: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for InitializeStatsAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let admin = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let stats = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(admin)?;
+        ConfigAccount::check(config)?;
+        if crate::state::Config::load(&config.try_borrow()?)?
+            .admin
+            .ne(admin.address())
+        {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+        if !stats.is_data_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        SystemProgramAccount::check(system_program)?;
+
+        Ok(Self {
+            admin,
+            config,
+            stats,
+            system_program,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct InitializeStats<'a> {
+    pub accounts: InitializeStatsAccounts<'a>,
+    pub bump: u8,
+}
+impl<'a> TryFrom<&'a [AccountView]> for InitializeStats<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = InitializeStatsAccounts::try_from(accounts)?;
+        let (stats_key, bump) =
+            Address::find_program_address(&[crate::state::EscrowStats::PREFIX], &crate::ID);
+        if stats_key.ne(accounts.stats.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let bump_binding = [bump];
+        let stats_seeds = [
+            Seed::from(crate::state::EscrowStats::PREFIX),
+            Seed::from(&bump_binding),
+        ];
+        create_account_with_minimum_balance_signed(
+            accounts.stats,
+            crate::state::EscrowStats::LEN,
+            &crate::ID,
+            accounts.admin,
+            None,
+            &[Signer::from(&stats_seeds)],
+        )?;
+        Ok(Self { accounts, bump })
+    }
+}
+
+impl<'a> InitializeStats<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &32;
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.stats.try_borrow_mut()?;
+        crate::state::EscrowStats::load_mut(data.as_mut())?.set_inner([self.bump]);
+        Ok(())
+    }
+}
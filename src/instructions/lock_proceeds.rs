@@ -0,0 +1,169 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_system::create_account_with_minimum_balance_signed;
+
+use crate::helpers::*;
+
+pub struct LockProceedsAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub lock: &'a AccountView,
+    pub lock_vault: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for LockProceedsAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let lock = next_account(&mut iter)?;
+        let lock_vault = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_b)?;
+        ConfigAccount::check(config)?;
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            maker,
+            escrow,
+            lock,
+            lock_vault,
+            mint_b,
+            system_program,
+            token_program,
+            config,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct LockProceedsInstructionData {
+    pub duration: i64,
+}
+impl<'a> TryFrom<&'a [u8]> for LockProceedsInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let duration = i64::from_le_bytes(
+            data.try_into()
+                .map_err(|_| ProgramError::InvalidInstructionData)?,
+        );
+        if duration <= 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self { duration })
+    }
+}
+
+pub struct LockProceeds<'a> {
+    pub accounts: LockProceedsAccounts<'a>,
+    pub instruction_data: LockProceedsInstructionData,
+    pub bump: u8,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for LockProceeds<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = LockProceedsAccounts::try_from(accounts)?;
+        let instruction_data = LockProceedsInstructionData::try_from(data)?;
+        let (lock_key, bump) = Address::find_program_address(
+            &[
+                crate::state::ProceedsLock::PREFIX,
+                accounts.escrow.address().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if lock_key.ne(accounts.lock.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let bump_binding = [bump];
+        let lock_seeds = [
+            Seed::from(crate::state::ProceedsLock::PREFIX),
+            Seed::from(accounts.escrow.address().as_ref()),
+            Seed::from(&bump_binding),
+        ];
+        create_account_with_minimum_balance_signed(
+            accounts.lock,
+            crate::state::ProceedsLock::LEN,
+            &crate::ID,
+            accounts.maker,
+            None,
+            &[Signer::from(&lock_seeds)],
+        )?;
+        AssociatedTokenAccount::init(
+            accounts.lock_vault,
+            accounts.mint_b,
+            accounts.maker,
+            accounts.lock,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> LockProceeds<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &30;
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.escrow.try_borrow_mut()?;
+        let escrow = crate::state::Escrow::load_mut(data.as_mut())?;
+
+        if escrow.maker.ne(self.accounts.maker.address())
+            || escrow.mint_b.ne(self.accounts.mint_b.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.is_proceeds_locked() || escrow.fills != 0 || escrow.is_auction() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let start = Clock::get()?.unix_timestamp;
+        let end = start
+            .checked_add(self.instruction_data.duration)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if !escrow.has_deadline() {
+            escrow.set_deadline(end);
+            escrow.set_deadline_kind(crate::state::Escrow::DEADLINE_UNIX_TIMESTAMP);
+        } else if escrow.deadline_kind != crate::state::Escrow::DEADLINE_UNIX_TIMESTAMP
+            || escrow.deadline > end
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut lock_data = self.accounts.lock.try_borrow_mut()?;
+        crate::state::ProceedsLock::load_mut(lock_data.as_mut())?.set_inner(
+            *self.accounts.escrow.address(),
+            *self.accounts.maker.address(),
+            *self.accounts.lock_vault.address(),
+            start,
+            self.instruction_data.duration,
+            [self.bump],
+        );
+        escrow.set_proceeds(*self.accounts.lock_vault.address());
+        escrow.set_proceeds_locked(true);
+        escrow.set_last_update_seq(next_seq(self.accounts.config)?);
+        Ok(())
+    }
+}
@@ -1,61 +1,123 @@
-use crate::helpers::*;
+use crate::{errors::EscrowError, helpers::*};
 use pinocchio::{
     AccountView, Address, ProgramResult,
-    cpi::{Seed, Signer},
+    cpi::{Seed, Signer, set_return_data},
     error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
 };
 use pinocchio_system::create_account_with_minimum_balance_signed;
-use pinocchio_token::instructions::Transfer;
+
+pub const MAKE_RETURN_LEN: usize = size_of::<Address>() + 1;
+
+pub fn serialize_make_return(escrow: &Address, bump: u8) -> [u8; MAKE_RETURN_LEN] {
+    let mut view = [0u8; MAKE_RETURN_LEN];
+    view[..size_of::<Address>()].copy_from_slice(escrow.as_ref());
+    view[size_of::<Address>()] = bump;
+    view
+}
 
 pub struct MakeAccounts<'a> {
-    pub maker: &'a AccountView,
-    pub escrow: &'a AccountView,
+    pub maker: MakerAccount<'a>,
+    pub escrow: EscrowPda<'a>,
     pub mint_a: &'a AccountView,
     pub mint_b: &'a AccountView,
     pub maker_ata_a: &'a AccountView,
-    pub vault: &'a AccountView,
+    pub vault: VaultAccount<'a>,
     pub system_program: &'a AccountView,
     pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub maker_index: Option<&'a AccountView>,
+    pub stats: Option<&'a AccountView>,
+    pub split: Option<&'a AccountView>,
+    pub split_recipients: &'a [AccountView],
+    pub proceeds: Option<&'a AccountView>,
+    pub remaining_accounts: &'a [AccountView],
 }
 impl<'a> TryFrom<&'a [AccountView]> for MakeAccounts<'a> {
     type Error = ProgramError;
     fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
-        let [
-            maker,
-            escrow,
-            mint_a,
-            mint_b,
-            maker_ata_a,
-            vault,
-            system_program,
-            token_program,
-            _,
-        ] = accounts
-        else {
-            return Err(ProgramError::NotEnoughAccountKeys);
+        Self::parse(accounts, false, 0, 0)
+    }
+}
+
+impl<'a> MakeAccounts<'a> {
+    pub fn parse(
+        accounts: &'a [AccountView],
+        with_nonce: bool,
+        splits: usize,
+        account_flags: u8,
+    ) -> Result<Self, ProgramError> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let mint_a = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let maker_ata_a = next_account(&mut iter)?;
+        let vault = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        ConfigAccount::check(config)?;
+        let maker_index = if with_nonce
+            || account_flags & MakeInstructionData::WITH_MAKER_INDEX != 0
+            || MakerIndexAccount::max_open_escrows(config)? != 0
+        {
+            Some(next_account(&mut iter)?)
+        } else {
+            None
         };
-        if !maker.is_signer() {
-            return Err(ProgramError::IllegalOwner);
+        if DenylistAccount::is_enabled(config)? {
+            DenylistAccount::check_parties(next_account(&mut iter)?, &[maker.address()])?;
         }
+        let mint_list = MintListAccount::mode(config)?;
+        if mint_list != crate::state::Config::MINT_LIST_NONE {
+            MintListAccount::check_pair(next_account(&mut iter)?, mint_list, mint_a, mint_b)?;
+        }
+        let stats = if account_flags & MakeInstructionData::WITH_STATS != 0 {
+            let stats = next_account(&mut iter)?;
+            StatsAccount::check(stats)?;
+            Some(stats)
+        } else {
+            None
+        };
+        let split = if splits != 0 {
+            Some(next_account(&mut iter)?)
+        } else {
+            None
+        };
+        let (split_recipients, rest) = iter
+            .as_slice()
+            .split_at_checked(splits)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let mut rest = rest.iter();
+        let proceeds = if account_flags & MakeInstructionData::WITH_PROCEEDS != 0 {
+            Some(next_account(&mut rest)?)
+        } else {
+            None
+        };
+        let remaining_accounts = rest.as_slice();
+        let maker = MakerAccount::signer(maker)?;
+        let escrow = EscrowPda::uninitialized(escrow)?;
 
-        MintAccount::check(mint_a)?;
-        MintAccount::check(mint_b)?;
-        AssociatedTokenAccount::check(maker_ata_a, maker, mint_a, token_program)?;
-
-        let (vault_key, _) = Address::find_program_address(
-            &[
-                escrow.address().as_ref(),
-                pinocchio_token::ID.as_ref(),
-                mint_a.address().as_ref(),
-            ],
-            &pinocchio_associated_token_account::ID,
-        );
-        if vault.address().ne(&vault_key) {
-            return Err(ProgramError::InvalidAccountOwner);
+        MintInterface::check(mint_a)?;
+        MintInterface::check(mint_b)?;
+        if TokenInterface::get_owner(maker_ata_a)?.eq(maker.address()) {
+            AssociatedTokenAccount::check(maker_ata_a, &maker, mint_a, token_program)?;
+        } else {
+            DelegatedTokenAccount::check(maker_ata_a, &maker, mint_a)?;
         }
-        if !vault.is_data_empty() {
-            return Err(ProgramError::AccountAlreadyInitialized);
+
+        for account in split_recipients.iter().chain(proceeds) {
+            TokenAccountInterface::check(account)?;
+            if TokenInterface::get_mint(account)?.ne(mint_b.address()) {
+                return Err(ProgramError::InvalidAccountData);
+            }
         }
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+        let vault = VaultAccount::uninitialized(vault, escrow, mint_a, token_program)?;
 
         Ok(Self {
             maker,
@@ -66,6 +128,13 @@ impl<'a> TryFrom<&'a [AccountView]> for MakeAccounts<'a> {
             vault,
             system_program,
             token_program,
+            config,
+            maker_index,
+            stats,
+            split,
+            split_recipients,
+            proceeds,
+            remaining_accounts,
         })
     }
 }
@@ -74,24 +143,182 @@ pub struct MakeInstructionData {
     pub seed: u64,
     pub receive: u64,
     pub amount: u64,
+    pub deadline: i64,
+    pub vesting_cliff: i64,
+    pub vesting_duration: i64,
+    pub flags: u8,
+    pub label: [u8; 32],
+    pub deadline_kind: u8,
+    pub min_fill: u64,
+    pub max_fill_per_tx: u64,
+    pub cooldown_slots: u64,
+    pub overpay_mode: u8,
+    pub receive_in_ui: bool,
+    pub burn_bps: u16,
+    pub split_bps: [u16; 4],
+    pub category: u16,
+    pub tag_flags: u16,
+    pub price_mode: u8,
+    pub min_price: u64,
+    pub max_price: u64,
+    pub account_flags: u8,
+}
+impl MakeInstructionData {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = size_of::<u8>()
+        + size_of::<u64>() * 3
+        + size_of::<i64>() * 3
+        + size_of::<u8>()
+        + size_of::<[u8; 32]>()
+        + size_of::<u8>()
+        + size_of::<u64>() * 3
+        + size_of::<u8>() * 2
+        + size_of::<u16>()
+        + size_of::<[u16; 4]>()
+        + size_of::<u16>() * 2
+        + size_of::<u8>()
+        + size_of::<u64>() * 2
+        + size_of::<u8>();
+
+    pub const WITH_PROCEEDS: u8 = 1 << 0;
+    pub const WITH_STATS: u8 = 1 << 1;
+    pub const WITH_MAKER_INDEX: u8 = 1 << 2;
+
+    #[inline(always)]
+    pub fn splits(&self) -> usize {
+        self.split_bps.iter().take_while(|bps| **bps != 0).count()
+    }
 }
 impl<'a> TryFrom<&'a [u8]> for MakeInstructionData {
     type Error = ProgramError;
 
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() != size_of::<u64>() * 3 {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (&version, data) = data
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        if version != Self::VERSION {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let seed = u64::from_le_bytes(instruction_bytes(data, 0)?);
+        let receive = u64::from_le_bytes(instruction_bytes(data, 8)?);
+        let amount = u64::from_le_bytes(instruction_bytes(data, 16)?);
+        let deadline = i64::from_le_bytes(instruction_bytes(data, 24)?);
+        let vesting_cliff = i64::from_le_bytes(instruction_bytes(data, 32)?);
+        let vesting_duration = i64::from_le_bytes(instruction_bytes(data, 40)?);
+        let [flags] = instruction_bytes(data, 48)?;
+        let label: [u8; 32] = instruction_bytes(data, 49)?;
+        let [deadline_kind] = instruction_bytes(data, 81)?;
+        let min_fill = u64::from_le_bytes(instruction_bytes(data, 82)?);
+        let max_fill_per_tx = u64::from_le_bytes(instruction_bytes(data, 90)?);
+        let cooldown_slots = u64::from_le_bytes(instruction_bytes(data, 98)?);
+        let [overpay_mode] = instruction_bytes(data, 106)?;
+        let receive_in_ui = match instruction_bytes(data, 107)? {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+        if receive_in_ui && flags & crate::state::Escrow::AUCTION != 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let burn_bps = u16::from_le_bytes(instruction_bytes(data, 108)?);
+        if burn_bps > crate::state::Escrow::MAX_BURN_BPS
+            || (burn_bps != 0 && flags & crate::state::Escrow::AUCTION != 0)
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let mut split_bps = [0u16; 4];
+        for (i, bps) in split_bps.iter_mut().enumerate() {
+            *bps = u16::from_le_bytes(instruction_bytes(data, 110 + i * 2)?);
+        }
+        let splits = split_bps.iter().take_while(|bps| **bps != 0).count();
+        if split_bps[splits..].iter().any(|bps| *bps != 0)
+            || split_bps.iter().map(|bps| *bps as u32).sum::<u32>() > 10_000
+            || (splits != 0 && flags & crate::state::Escrow::AUCTION != 0)
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let category = u16::from_le_bytes(instruction_bytes(data, 118)?);
+        let tag_flags = u16::from_le_bytes(instruction_bytes(data, 120)?);
+        let [price_mode] = instruction_bytes(data, 122)?;
+        if price_mode > crate::state::Escrow::PRICE_RATIO_ROUND_DOWN
+            || (price_mode != crate::state::Escrow::PRICE_ABSOLUTE
+                && flags & crate::state::Escrow::AUCTION != 0)
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let min_price = u64::from_le_bytes(instruction_bytes(data, 123)?);
+        let max_price = u64::from_le_bytes(instruction_bytes(data, 131)?);
+        if max_price != 0 && min_price > max_price {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let [account_flags] = instruction_bytes(data, 139)?;
+        if account_flags & !(Self::WITH_PROCEEDS | Self::WITH_STATS | Self::WITH_MAKER_INDEX) != 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if overpay_mode > crate::state::Escrow::OVERPAY_TO_MAKER {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if max_fill_per_tx != 0 && min_fill > max_fill_per_tx {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if deadline_kind > crate::state::Escrow::DEADLINE_SLOT {
             return Err(ProgramError::InvalidInstructionData);
         }
-        let seed = u64::from_le_bytes(data[0..8].try_into().unwrap());
-        let receive = u64::from_le_bytes(data[8..16].try_into().unwrap());
-        let amount = u64::from_le_bytes(data[16..24].try_into().unwrap());
         if amount == 0 {
             return Err(ProgramError::InvalidInstructionData);
         }
+        let modes = flags
+            & (crate::state::Escrow::AUCTION
+                | crate::state::Escrow::STANDING
+                | crate::state::Escrow::VESTING);
+        if modes.count_ones() > 1 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if flags & crate::state::Escrow::RENT_TO_TAKER != 0
+            && flags & crate::state::Escrow::RENT_TO_TREASURY != 0
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if flags & crate::state::Escrow::ROYALTY != 0 && flags & crate::state::Escrow::AUCTION != 0
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if (!cfg!(feature = "auction") && flags & crate::state::Escrow::AUCTION != 0)
+            || (!cfg!(feature = "nft") && flags & crate::state::Escrow::ROYALTY != 0)
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if flags & crate::state::Escrow::VESTING != 0
+            && (vesting_cliff < 0 || vesting_duration < vesting_cliff)
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
         Ok(Self {
             seed,
             receive,
             amount,
+            deadline,
+            vesting_cliff,
+            vesting_duration,
+            flags,
+            label,
+            deadline_kind,
+            min_fill,
+            max_fill_per_tx,
+            cooldown_slots,
+            overpay_mode,
+            receive_in_ui,
+            burn_bps,
+            split_bps,
+            category,
+            tag_flags,
+            price_mode,
+            min_price,
+            max_price,
+            account_flags,
         })
     }
 }
@@ -99,75 +326,262 @@ impl<'a> TryFrom<&'a [u8]> for MakeInstructionData {
 pub struct Make<'a> {
     pub accounts: MakeAccounts<'a>,
     pub instruction_data: MakeInstructionData,
+    pub namespace: u8,
     pub bump: u8,
 }
 impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Make<'a> {
     type Error = ProgramError;
     fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
-        let accounts = MakeAccounts::try_from(accounts)?;
+        Self::new(data, accounts, crate::state::Escrow::NAMESPACE_SEED)
+    }
+}
+
+impl<'a> Make<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &0;
+    pub const PAIR_DISCRIMINATOR: &'a u8 = &18;
+    pub const NONCE_DISCRIMINATOR: &'a u8 = &19;
+
+    pub fn new(
+        data: &'a [u8],
+        accounts: &'a [AccountView],
+        namespace: u8,
+    ) -> Result<Self, ProgramError> {
         let instruction_data = MakeInstructionData::try_from(data)?;
-        let (_, bump) = Address::find_program_address(
-            &[
-                b"escrow",
-                accounts.maker.address().as_ref(),
-                &instruction_data.seed.to_le_bytes(),
-            ],
-            &crate::ID,
-        );
-        let seed_binding = instruction_data.seed.to_le_bytes();
-        let bump_binding = [bump];
-        let escrow_seeds = [
-            Seed::from(b"escrow"),
-            Seed::from(accounts.maker.address().as_ref()),
-            Seed::from(&seed_binding),
-            Seed::from(&bump_binding),
-        ];
-        let signers = [Signer::from(&escrow_seeds)];
+        let accounts = MakeAccounts::parse(
+            accounts,
+            namespace == crate::state::Escrow::NAMESPACE_NONCE,
+            instruction_data.splits(),
+            instruction_data.account_flags,
+        )?;
+        Self::open(accounts, instruction_data, namespace)
+    }
+
+    pub fn open(
+        accounts: MakeAccounts<'a>,
+        mut instruction_data: MakeInstructionData,
+        namespace: u8,
+    ) -> Result<Self, ProgramError> {
+        if let Some(maker_index) = accounts.maker_index
+            && let Some(nonce) = Self::open_maker_index(
+                &accounts,
+                maker_index,
+                namespace == crate::state::Escrow::NAMESPACE_NONCE,
+            )?
+        {
+            instruction_data.seed = nonce;
+        }
+        MintPolicy::check(accounts.mint_a, instruction_data.flags)?;
+        MintPolicy::check(accounts.mint_b, instruction_data.flags)?;
+        if (instruction_data.flags & crate::state::Escrow::AUCTION != 0
+            || instruction_data.deadline != 0)
+            && instruction_data.deadline
+                <= crate::state::Escrow::clock_for(instruction_data.deadline_kind, &Clock::get()?)
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let escrow_seeds = match namespace {
+            crate::state::Escrow::NAMESPACE_PAIR => EscrowSeeds::find_pair(
+                accounts.maker.address(),
+                accounts.mint_a.address(),
+                accounts.mint_b.address(),
+            ),
+            crate::state::Escrow::NAMESPACE_NONCE => {
+                EscrowSeeds::find_nonce(accounts.maker.address(), instruction_data.seed)
+            }
+            _ => EscrowSeeds::find(accounts.maker.address(), instruction_data.seed),
+        };
+        let bump = escrow_seeds.bump();
+        let seeds = escrow_seeds.seeds();
+        let signers = [Signer::from(&seeds)];
         create_account_with_minimum_balance_signed(
-            accounts.escrow,
+            &accounts.escrow,
             crate::state::Escrow::LEN,
             &crate::ID,
-            accounts.maker,
+            &accounts.maker,
             None,
             &signers,
         )?;
-        AssociatedTokenAccount::init(
-            accounts.vault,
-            accounts.mint_a,
-            accounts.maker,
-            accounts.escrow,
-            accounts.system_program,
-            accounts.token_program,
-        )?;
+        if accounts.vault.is_data_empty() {
+            AssociatedTokenAccount::init(
+                &accounts.vault,
+                accounts.mint_a,
+                &accounts.maker,
+                &accounts.escrow,
+                accounts.system_program,
+                accounts.token_program,
+            )?;
+        }
+        if let Some(split) = accounts.split {
+            Self::open_split(&accounts, split, instruction_data.split_bps)?;
+        }
         Ok(Self {
             accounts,
             instruction_data,
+            namespace,
             bump,
         })
     }
-}
 
-impl<'a> Make<'a> {
-    pub const DISCRIMINATOR: &'a u8 = &0;
+    fn open_maker_index(
+        accounts: &MakeAccounts,
+        nonce: &AccountView,
+        with_nonce: bool,
+    ) -> Result<Option<u64>, ProgramError> {
+        let (nonce_key, bump) = Address::find_program_address(
+            &[
+                crate::state::MakerNonce::PREFIX,
+                accounts.maker.address().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if nonce.address().ne(&nonce_key) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if nonce.is_data_empty() {
+            let bump_binding = [bump];
+            let seeds = [
+                Seed::from(crate::state::MakerNonce::PREFIX),
+                Seed::from(accounts.maker.address().as_ref()),
+                Seed::from(&bump_binding),
+            ];
+            create_account_with_minimum_balance_signed(
+                nonce,
+                crate::state::MakerNonce::LEN,
+                &crate::ID,
+                &accounts.maker,
+                None,
+                &[Signer::from(&seeds)],
+            )?;
+            let mut data = nonce.try_borrow_mut()?;
+            crate::state::MakerNonce::load_mut(data.as_mut())?
+                .set_inner(*accounts.maker.address(), bump_binding);
+        } else if !nonce.owned_by(&crate::ID) || nonce.data_len().ne(&crate::state::MakerNonce::LEN)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        RentExemptAccount::check(nonce)?;
+        let max_open_escrows = MakerIndexAccount::max_open_escrows(accounts.config)?;
+        let mut data = nonce.try_borrow_mut()?;
+        let maker_index = crate::state::MakerNonce::load_mut(data.as_mut())?;
+        if !maker_index.open(max_open_escrows) {
+            return Err(EscrowError::OpenEscrowLimit.into());
+        }
+        Ok(with_nonce.then(|| maker_index.next_nonce()))
+    }
+
+    fn open_split(
+        accounts: &MakeAccounts,
+        split: &AccountView,
+        split_bps: [u16; 4],
+    ) -> ProgramResult {
+        let (split_key, bump) = Address::find_program_address(
+            &[
+                crate::state::ProceedsSplit::PREFIX,
+                accounts.escrow.address().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if split.address().ne(&split_key) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let bump_binding = [bump];
+        if split.is_data_empty() {
+            let seeds = [
+                Seed::from(crate::state::ProceedsSplit::PREFIX),
+                Seed::from(accounts.escrow.address().as_ref()),
+                Seed::from(&bump_binding),
+            ];
+            create_account_with_minimum_balance_signed(
+                split,
+                crate::state::ProceedsSplit::LEN,
+                &crate::ID,
+                &accounts.maker,
+                None,
+                &[Signer::from(&seeds)],
+            )?;
+        } else if !split.owned_by(&crate::ID)
+            || split.data_len().ne(&crate::state::ProceedsSplit::LEN)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        RentExemptAccount::check(split)?;
+        let mut data = split.try_borrow_mut()?;
+        let split = crate::state::ProceedsSplit::load_mut(data.as_mut())?;
+        split.set_inner(*accounts.escrow.address(), split_bps, bump_binding);
+        for recipient in accounts.split_recipients {
+            split.push(*recipient.address());
+        }
+        Ok(())
+    }
+
     pub fn process(&mut self) -> ProgramResult {
+        PriceBounds::check(
+            self.accounts.mint_a,
+            self.accounts.mint_b,
+            self.instruction_data.receive,
+            self.instruction_data.amount,
+            self.instruction_data.min_price,
+            self.instruction_data.max_price,
+        )?;
         let mut data = self.accounts.escrow.try_borrow_mut()?;
         let escrow = crate::state::Escrow::load_mut(data.as_mut())?;
 
         escrow.set_inner(
             self.instruction_data.seed,
-            self.accounts.maker.address().clone(),
-            self.accounts.mint_a.address().clone(),
-            self.accounts.mint_b.address().clone(),
+            *self.accounts.maker.address(),
+            *self.accounts.mint_a.address(),
+            *self.accounts.mint_b.address(),
             self.instruction_data.receive,
             [self.bump],
         );
-        Transfer {
-            from: self.accounts.maker_ata_a,
-            to: self.accounts.vault,
-            authority: self.accounts.maker,
-            amount: self.instruction_data.amount,
+        escrow.set_namespace(self.namespace);
+        escrow.set_vault(*self.accounts.vault.address());
+        escrow.set_amount(self.instruction_data.amount);
+        escrow.set_deadline(self.instruction_data.deadline);
+        escrow.set_deadline_kind(self.instruction_data.deadline_kind);
+        escrow.set_vesting_schedule(
+            self.instruction_data.vesting_cliff,
+            self.instruction_data.vesting_duration,
+        );
+        escrow.set_flags(self.instruction_data.flags);
+        escrow.set_label(self.instruction_data.label);
+        escrow.set_fill_limits(
+            self.instruction_data.min_fill,
+            self.instruction_data.max_fill_per_tx,
+        );
+        escrow.set_cooldown_slots(self.instruction_data.cooldown_slots);
+        escrow.set_overpay_mode(self.instruction_data.overpay_mode);
+        escrow.set_receive_in_ui(self.instruction_data.receive_in_ui);
+        escrow.set_burn_bps(self.instruction_data.burn_bps);
+        escrow.set_tags(
+            self.instruction_data.category,
+            self.instruction_data.tag_flags,
+        );
+        escrow.set_price_mode(self.instruction_data.price_mode);
+        escrow.set_indexed(self.accounts.maker_index.is_some());
+        escrow.set_splits(self.accounts.split_recipients.len() as u8);
+        escrow.set_tracked(self.accounts.stats.is_some());
+        if let Some(proceeds) = self.accounts.proceeds {
+            escrow.set_proceeds(*proceeds.address());
+        }
+        escrow.set_last_update_seq(next_seq(self.accounts.config)?);
+        if let Some(stats) = self.accounts.stats {
+            StatsAccount::record_created(stats)?;
         }
-        .invoke()?;
+        GroupMemberMint::emit(self.accounts.escrow.address(), self.accounts.mint_a)?;
+        GroupMemberMint::emit(self.accounts.escrow.address(), self.accounts.mint_b)?;
+        TokenInterface::transfer_checked(
+            self.accounts.maker_ata_a,
+            self.accounts.mint_a,
+            &self.accounts.vault,
+            &self.accounts.maker,
+            self.instruction_data.amount,
+            &[],
+        )?;
+        set_return_data(&serialize_make_return(
+            self.accounts.escrow.address(),
+            self.bump,
+        ));
         Ok(())
     }
 }
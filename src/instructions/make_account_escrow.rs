@@ -0,0 +1,140 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+};
+use pinocchio_system::create_account_with_minimum_balance_signed;
+
+use crate::helpers::*;
+
+pub struct MakeAccountEscrowAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub account_escrow: &'a AccountView,
+    pub token_account: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for MakeAccountEscrowAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let account_escrow = next_account(&mut iter)?;
+        let token_account = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(maker)?;
+        TokenAccountInterface::check(token_account)?;
+        MintInterface::check(mint_b)?;
+        SystemProgramAccount::check(system_program)?;
+        if !account_escrow.is_data_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        Ok(Self {
+            maker,
+            account_escrow,
+            token_account,
+            mint_b,
+            system_program,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct MakeAccountEscrowInstructionData {
+    pub receive: u64,
+}
+impl<'a> TryFrom<&'a [u8]> for MakeAccountEscrowInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let receive = u64::from_le_bytes(instruction_bytes(data, 0)?);
+        if receive == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self { receive })
+    }
+}
+
+pub struct MakeAccountEscrow<'a> {
+    pub accounts: MakeAccountEscrowAccounts<'a>,
+    pub instruction_data: MakeAccountEscrowInstructionData,
+    pub bump: u8,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for MakeAccountEscrow<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = MakeAccountEscrowAccounts::try_from(accounts)?;
+        let instruction_data = MakeAccountEscrowInstructionData::try_from(data)?;
+        let (account_escrow_key, bump) = Address::find_program_address(
+            &[
+                crate::state::AccountEscrow::PREFIX,
+                accounts.token_account.address().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if account_escrow_key.ne(accounts.account_escrow.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> MakeAccountEscrow<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &46;
+
+    pub fn process(&mut self) -> ProgramResult {
+        if TokenInterface::get_owner(self.accounts.token_account)?.ne(self.accounts.maker.address())
+        {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+        if TokenInterface::is_frozen(self.accounts.token_account)?
+            || TokenInterface::has_close_authority(self.accounts.token_account)?
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let bump_binding = [self.bump];
+        let account_escrow_seeds = [
+            Seed::from(crate::state::AccountEscrow::PREFIX),
+            Seed::from(self.accounts.token_account.address().as_ref()),
+            Seed::from(&bump_binding),
+        ];
+        create_account_with_minimum_balance_signed(
+            self.accounts.account_escrow,
+            crate::state::AccountEscrow::LEN,
+            &crate::ID,
+            self.accounts.maker,
+            None,
+            &[Signer::from(&account_escrow_seeds)],
+        )?;
+        TokenInterface::set_owner(
+            self.accounts.token_account,
+            self.accounts.maker,
+            self.accounts.account_escrow.address(),
+            &[],
+        )?;
+
+        let mut data = self.accounts.account_escrow.try_borrow_mut()?;
+        crate::state::AccountEscrow::load_mut(data.as_mut())?.set_inner(
+            *self.accounts.maker.address(),
+            *self.accounts.token_account.address(),
+            *self.accounts.mint_b.address(),
+            self.instruction_data.receive,
+            bump_binding,
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+};
+use pinocchio_system::create_account_with_minimum_balance_signed;
+
+use crate::helpers::*;
+
+pub struct MakeCollectionOfferAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub offer: &'a AccountView,
+    pub collection: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub maker_ata_b: &'a AccountView,
+    pub vault: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for MakeCollectionOfferAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let offer = next_account(&mut iter)?;
+        let collection = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let maker_ata_b = next_account(&mut iter)?;
+        let vault = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(maker)?;
+        MintInterface::check(collection)?;
+        MintInterface::check(mint_b)?;
+        OwnedTokenAccount::check(maker_ata_b, maker, mint_b)?;
+        SystemAccount::check(vault)?;
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+        if !offer.is_data_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        Ok(Self {
+            maker,
+            offer,
+            collection,
+            mint_b,
+            maker_ata_b,
+            vault,
+            system_program,
+            token_program,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct MakeCollectionOfferInstructionData {
+    pub seed: u64,
+    pub amount: u64,
+    pub attributes: Option<(Address, [u8; 32])>,
+}
+impl MakeCollectionOfferInstructionData {
+    pub const LEN: usize = size_of::<u64>() * 2;
+    pub const ATTRIBUTES_LEN: usize = size_of::<Address>() + 32;
+}
+impl<'a> TryFrom<&'a [u8]> for MakeCollectionOfferInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let attributes = if data.len() == Self::LEN {
+            None
+        } else if data.len() == Self::LEN + Self::ATTRIBUTES_LEN {
+            let attester = Address::new_from_array(instruction_bytes(data, Self::LEN)?);
+            let attributes_hash = instruction_bytes(data, Self::LEN + 32)?;
+            if attester.eq(&Address::default()) || attributes_hash == [0u8; 32] {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            Some((attester, attributes_hash))
+        } else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+        let seed = u64::from_le_bytes(instruction_bytes(data, 0)?);
+        let amount = u64::from_le_bytes(instruction_bytes(data, 8)?);
+        if amount == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self {
+            seed,
+            amount,
+            attributes,
+        })
+    }
+}
+
+pub struct MakeCollectionOffer<'a> {
+    pub accounts: MakeCollectionOfferAccounts<'a>,
+    pub instruction_data: MakeCollectionOfferInstructionData,
+    pub bump: u8,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for MakeCollectionOffer<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = MakeCollectionOfferAccounts::try_from(accounts)?;
+        let instruction_data = MakeCollectionOfferInstructionData::try_from(data)?;
+        let (offer_key, bump) = Address::find_program_address(
+            &[
+                crate::state::CollectionOffer::PREFIX,
+                accounts.maker.address().as_ref(),
+                &instruction_data.seed.to_le_bytes(),
+            ],
+            &crate::ID,
+        );
+        if offer_key.ne(accounts.offer.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> MakeCollectionOffer<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &49;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let seed_binding = self.instruction_data.seed.to_le_bytes();
+        let bump_binding = [self.bump];
+        let offer_seeds = [
+            Seed::from(crate::state::CollectionOffer::PREFIX),
+            Seed::from(self.accounts.maker.address().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        create_account_with_minimum_balance_signed(
+            self.accounts.offer,
+            crate::state::CollectionOffer::LEN,
+            &crate::ID,
+            self.accounts.maker,
+            None,
+            &[Signer::from(&offer_seeds)],
+        )?;
+        AssociatedTokenAccount::init(
+            self.accounts.vault,
+            self.accounts.mint_b,
+            self.accounts.maker,
+            self.accounts.offer,
+            self.accounts.system_program,
+            self.accounts.token_program,
+        )?;
+        TokenInterface::transfer_checked(
+            self.accounts.maker_ata_b,
+            self.accounts.mint_b,
+            self.accounts.vault,
+            self.accounts.maker,
+            self.instruction_data.amount,
+            &[],
+        )?;
+
+        let mut data = self.accounts.offer.try_borrow_mut()?;
+        let offer = crate::state::CollectionOffer::load_mut(data.as_mut())?;
+        offer.set_inner(
+            *self.accounts.maker.address(),
+            *self.accounts.collection.address(),
+            *self.accounts.mint_b.address(),
+            self.instruction_data.seed,
+            self.instruction_data.amount,
+            bump_binding,
+        );
+        if let Some((attester, attributes_hash)) = self.instruction_data.attributes {
+            offer.attester = attester;
+            offer.attributes_hash = attributes_hash;
+        }
+        Ok(())
+    }
+}
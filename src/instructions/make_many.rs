@@ -0,0 +1,112 @@
+use pinocchio::{AccountView, ProgramResult, error::ProgramError};
+
+use crate::{MakeAccounts, MakeInstructionData, helpers::*};
+
+pub struct MakeManyInstructionData<'a> {
+    pub offers: &'a [u8],
+}
+impl MakeManyInstructionData<'_> {
+    pub const OFFER_LEN: usize = size_of::<u64>() * 3;
+    pub const MAX_OFFERS: usize = 8;
+    pub const GROUP_LEN: usize = 2;
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.offers.len() / Self::OFFER_LEN
+    }
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.offers.is_empty()
+    }
+    pub fn offer(&self, index: usize) -> Result<MakeInstructionData, ProgramError> {
+        let start = index * Self::OFFER_LEN;
+        let offer = self
+            .offers
+            .get(start..start + Self::OFFER_LEN)
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        let mut data = [0u8; MakeInstructionData::LEN];
+        data[0] = MakeInstructionData::VERSION;
+        for (byte, offer) in data[1..].iter_mut().zip(offer) {
+            *byte = *offer;
+        }
+        MakeInstructionData::try_from(data.as_slice())
+    }
+}
+impl<'a> TryFrom<&'a [u8]> for MakeManyInstructionData<'a> {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.is_empty()
+            || !data.len().is_multiple_of(Self::OFFER_LEN)
+            || data.len() / Self::OFFER_LEN > Self::MAX_OFFERS
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self { offers: data })
+    }
+}
+
+pub struct MakeMany<'a> {
+    pub accounts: MakeAccounts<'a>,
+    pub groups: &'a [AccountView],
+    pub instruction_data: MakeManyInstructionData<'a>,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for MakeMany<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let instruction_data = MakeManyInstructionData::try_from(data)?;
+        let (accounts, groups) = accounts
+            .len()
+            .checked_sub((instruction_data.len() - 1) * MakeManyInstructionData::GROUP_LEN)
+            .map(|at| accounts.split_at(at))
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let accounts = MakeAccounts::parse(accounts, false, 0, 0)?;
+
+        Ok(Self {
+            accounts,
+            groups,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> MakeMany<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &33;
+
+    pub fn process(&mut self) -> ProgramResult {
+        crate::Make::open(
+            MakeAccounts { ..self.accounts },
+            self.instruction_data.offer(0)?,
+            crate::state::Escrow::NAMESPACE_SEED,
+        )?
+        .process()?;
+        for (index, group) in self
+            .groups
+            .chunks_exact(MakeManyInstructionData::GROUP_LEN)
+            .enumerate()
+        {
+            let [escrow, vault] = group else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            let escrow = EscrowPda::uninitialized(escrow)?;
+            let vault = VaultAccount::uninitialized(
+                vault,
+                escrow,
+                self.accounts.mint_a,
+                self.accounts.token_program,
+            )?;
+            let accounts = MakeAccounts {
+                escrow,
+                vault,
+                ..self.accounts
+            };
+            crate::Make::open(
+                accounts,
+                self.instruction_data.offer(index + 1)?,
+                crate::state::Escrow::NAMESPACE_SEED,
+            )?
+            .process()?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_system::create_account_with_minimum_balance_signed;
+
+use crate::helpers::*;
+
+pub struct MakeStakeAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub stake_escrow: &'a AccountView,
+    pub stake: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub clock: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for MakeStakeAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let stake_escrow = next_account(&mut iter)?;
+        let stake = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let clock = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let stake_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(maker)?;
+        MintInterface::check(mint_b)?;
+        ClockSysvarAccount::check(clock)?;
+        SystemProgramAccount::check(system_program)?;
+        StakeProgramAccount::check(stake_program)?;
+        if !stake_escrow.is_data_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        Ok(Self {
+            maker,
+            stake_escrow,
+            stake,
+            mint_b,
+            clock,
+            system_program,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct MakeStakeInstructionData {
+    pub receive: u64,
+}
+impl<'a> TryFrom<&'a [u8]> for MakeStakeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let receive = u64::from_le_bytes(instruction_bytes(data, 0)?);
+        if receive == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self { receive })
+    }
+}
+
+pub struct MakeStake<'a> {
+    pub accounts: MakeStakeAccounts<'a>,
+    pub instruction_data: MakeStakeInstructionData,
+    pub bump: u8,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for MakeStake<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = MakeStakeAccounts::try_from(accounts)?;
+        let instruction_data = MakeStakeInstructionData::try_from(data)?;
+        let (stake_escrow_key, bump) = Address::find_program_address(
+            &[
+                crate::state::StakeEscrow::PREFIX,
+                accounts.stake.address().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if stake_escrow_key.ne(accounts.stake_escrow.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> MakeStake<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &43;
+
+    pub fn process(&mut self) -> ProgramResult {
+        StakeAccount::check_transferable(
+            self.accounts.stake,
+            self.accounts.maker.address(),
+            &Clock::get()?,
+        )?;
+        let bump_binding = [self.bump];
+        let stake_escrow_seeds = [
+            Seed::from(crate::state::StakeEscrow::PREFIX),
+            Seed::from(self.accounts.stake.address().as_ref()),
+            Seed::from(&bump_binding),
+        ];
+        create_account_with_minimum_balance_signed(
+            self.accounts.stake_escrow,
+            crate::state::StakeEscrow::LEN,
+            &crate::ID,
+            self.accounts.maker,
+            None,
+            &[Signer::from(&stake_escrow_seeds)],
+        )?;
+        StakeAccount::authorize(
+            self.accounts.stake,
+            self.accounts.clock,
+            self.accounts.maker,
+            self.accounts.stake_escrow.address(),
+            &[],
+        )?;
+
+        let mut data = self.accounts.stake_escrow.try_borrow_mut()?;
+        crate::state::StakeEscrow::load_mut(data.as_mut())?.set_inner(
+            *self.accounts.maker.address(),
+            *self.accounts.stake.address(),
+            *self.accounts.mint_b.address(),
+            self.instruction_data.receive,
+            bump_binding,
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use pinocchio::{
+    AccountView, ProgramResult,
+    error::ProgramError,
+    sysvars::{Sysvar, rent::Rent},
+};
+use pinocchio_system::instructions::Transfer;
+
+use crate::helpers::*;
+
+pub struct MigrateAccounts<'a> {
+    pub payer: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for MigrateAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let payer = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(payer)?;
+        if !escrow.owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if escrow.data_len().ne(&crate::state::EscrowV1::LEN) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        ConfigAccount::check(config)?;
+        SystemProgramAccount::check(system_program)?;
+
+        Ok(Self {
+            payer,
+            escrow,
+            system_program,
+            config,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct Migrate<'a> {
+    pub accounts: MigrateAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for Migrate<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = MigrateAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> Migrate<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &12;
+    pub fn process(&mut self) -> ProgramResult {
+        let (seed, maker, mint_a, mint_b, receive, bump) = {
+            let data = self.accounts.escrow.try_borrow()?;
+            let legacy = crate::state::EscrowV1::load(&data)?;
+            (
+                u64::from_le_bytes(legacy.seed),
+                legacy.maker,
+                legacy.mint_a,
+                legacy.mint_b,
+                u64::from_le_bytes(legacy.receive),
+                legacy.bump,
+            )
+        };
+
+        let lamports = Rent::get()?
+            .try_minimum_balance(crate::state::Escrow::LEN)?
+            .saturating_sub(self.accounts.escrow.lamports());
+        if lamports > 0 {
+            Transfer {
+                from: self.accounts.payer,
+                to: self.accounts.escrow,
+                lamports,
+            }
+            .invoke()?;
+        }
+        self.accounts.escrow.resize(crate::state::Escrow::LEN)?;
+
+        let mut data = self.accounts.escrow.try_borrow_mut()?;
+        data.fill(0);
+        let escrow = crate::state::Escrow::load_mut(data.as_mut())?;
+        escrow.set_inner(seed, maker, mint_a, mint_b, receive, bump);
+        escrow.set_last_update_seq(next_seq(self.accounts.config)?);
+        Ok(())
+    }
+}
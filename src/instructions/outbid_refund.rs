@@ -0,0 +1,118 @@
+use pinocchio::{
+    AccountView, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+};
+
+use crate::helpers::*;
+
+pub struct OutbidRefundAccounts<'a> {
+    pub bidder: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub bid: &'a AccountView,
+    pub bid_vault: &'a AccountView,
+    pub bidder_ata_b: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for OutbidRefundAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let bidder = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let bid = next_account(&mut iter)?;
+        let bid_vault = next_account(&mut iter)?;
+        let bidder_ata_b = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        MintInterface::check(mint_b)?;
+        BidAccount::check(bid)?;
+        AssociatedTokenAccount::check(bid_vault, bid, mint_b, token_program)?;
+        AssociatedTokenAccount::check(bidder_ata_b, bidder, mint_b, token_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            bidder,
+            escrow,
+            mint_b,
+            bid,
+            bid_vault,
+            bidder_ata_b,
+            token_program,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct OutbidRefund<'a> {
+    pub accounts: OutbidRefundAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for OutbidRefund<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = OutbidRefundAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> OutbidRefund<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &4;
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.bid.try_borrow()?;
+        let bid = crate::state::AuctionBid::load(&data)?;
+        check_program_pda(
+            self.accounts.bid,
+            &[b"bid", bid.escrow.as_ref(), bid.bidder.as_ref(), &bid.bump],
+        )?;
+
+        if bid.escrow.ne(self.accounts.escrow.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if bid.bidder.ne(self.accounts.bidder.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if ProgramAccount::check(self.accounts.escrow).is_ok() {
+            let escrow_data = self.accounts.escrow.try_borrow()?;
+            let escrow = crate::state::Escrow::load(&escrow_data)?;
+            if escrow.highest_bidder.eq(&bid.bidder) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        let bump_binding = bid.bump;
+        let bid_seeds = [
+            Seed::from(b"bid"),
+            Seed::from(bid.escrow.as_ref()),
+            Seed::from(bid.bidder.as_ref()),
+            Seed::from(bump_binding.as_ref()),
+        ];
+        let signer = Signer::from(&bid_seeds);
+        let amount = TokenInterface::get_amount(self.accounts.bid_vault)?;
+
+        TokenInterface::transfer_checked(
+            self.accounts.bid_vault,
+            self.accounts.mint_b,
+            self.accounts.bidder_ata_b,
+            self.accounts.bid,
+            amount,
+            core::slice::from_ref(&signer),
+        )?;
+        TokenInterface::close_account(
+            self.accounts.bid_vault,
+            self.accounts.bidder,
+            self.accounts.bid,
+            core::slice::from_ref(&signer),
+        )?;
+
+        drop(data);
+        ProgramAccount::close(self.accounts.bid, self.accounts.bidder)?;
+        Ok(())
+    }
+}
@@ -1,9 +1,4 @@
-use pinocchio::{
-    AccountView, ProgramResult,
-    cpi::{Seed, Signer},
-    error::ProgramError,
-};
-use pinocchio_token::instructions::Transfer;
+use pinocchio::{AccountView, ProgramResult, cpi::Signer, error::ProgramError};
 
 use crate::helpers::*;
 
@@ -15,29 +10,33 @@ pub struct RefundAccounts<'a> {
     pub maker_ata_a: &'a AccountView,
     pub system_program: &'a AccountView,
     pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
 }
 
 impl<'a> TryFrom<&'a [AccountView]> for RefundAccounts<'a> {
     type Error = ProgramError;
     fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
-        let [
-            maker,
-            escrow,
-            mint_a,
-            vault,
-            maker_ata_a,
-            system_program,
-            token_program,
-            _,
-        ] = accounts
-        else {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        };
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let mint_a = next_account(&mut iter)?;
+        let vault = next_account(&mut iter)?;
+        let maker_ata_a = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
 
         SignerAccount::check(maker)?;
         ProgramAccount::check(escrow)?;
         MintInterface::check(mint_a)?;
-        AssociatedTokenAccount::check(vault, escrow, mint_a, token_program)?;
+        EscrowVault::check(vault, escrow, mint_a, token_program)?;
+        ConfigAccount::check(config)?;
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
 
         Ok(Self {
             maker,
@@ -47,19 +46,71 @@ impl<'a> TryFrom<&'a [AccountView]> for RefundAccounts<'a> {
             maker_ata_a,
             system_program,
             token_program,
+            config,
+            remaining_accounts,
         })
     }
 }
 
+pub struct RefundInstructionData<'a> {
+    pub memo: Option<&'a [u8]>,
+}
+impl<'a> TryFrom<&'a [u8]> for RefundInstructionData<'a> {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.is_empty() {
+            return Ok(Self { memo: None });
+        }
+        let (memo, rest) = parse_memo(data)?;
+        if !rest.is_empty() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self { memo: Some(memo) })
+    }
+}
+
 pub struct Refund<'a> {
     pub accounts: RefundAccounts<'a>,
+    pub instruction_data: RefundInstructionData<'a>,
+    pub memo_program: Option<&'a AccountView>,
+    pub maker_index: Option<&'a AccountView>,
+    pub extension_accounts: &'a [AccountView],
 }
-impl<'a> TryFrom<&'a [AccountView]> for Refund<'a> {
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Refund<'a> {
     type Error = ProgramError;
-    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
         let accounts = RefundAccounts::try_from(accounts)?;
+        let instruction_data = RefundInstructionData::try_from(data)?;
+        Self::new(accounts, instruction_data)
+    }
+}
+
+impl<'a> Refund<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &2;
+
+    pub fn new(
+        accounts: RefundAccounts<'a>,
+        instruction_data: RefundInstructionData<'a>,
+    ) -> Result<Self, ProgramError> {
+        let mut optional_accounts = accounts.remaining_accounts.iter();
+        let memo_program = if instruction_data.memo.is_some() {
+            Some(next_account(&mut optional_accounts)?)
+        } else {
+            None
+        };
+        let is_indexed = {
+            let data = accounts.escrow.try_borrow()?;
+            crate::state::Escrow::load(&data)?.is_indexed()
+        };
+        let maker_index = if is_indexed {
+            Some(next_account(&mut optional_accounts)?)
+        } else {
+            None
+        };
+        let extension_accounts = optional_accounts.as_slice();
 
-        AssociatedTokenAccount::init_if_needed(
+        init_destination_if_needed(
             accounts.maker_ata_a,
             accounts.mint_a,
             accounts.maker,
@@ -68,44 +119,63 @@ impl<'a> TryFrom<&'a [AccountView]> for Refund<'a> {
             accounts.token_program,
         )?;
 
-        Ok(Self { accounts })
+        Ok(Self {
+            accounts,
+            instruction_data,
+            memo_program,
+            maker_index,
+            extension_accounts,
+        })
     }
-}
 
-impl<'a> Refund<'a> {
-    pub const DISCRIMINATOR: &'a u8 = &2;
     pub fn process(&mut self) -> ProgramResult {
         let data = self.accounts.escrow.try_borrow()?;
         let escrow = crate::state::Escrow::load(&data)?;
+        if escrow.is_auction() && escrow.highest_bid != 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.is_vesting() && escrow.vesting_start != 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.maker.ne(self.accounts.maker.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let escrow_seeds = EscrowSeeds::from_escrow(escrow);
+        let seeds = escrow_seeds.seeds();
+        let signer = Signer::from(&seeds);
+        let amount = TokenInterface::get_amount(self.accounts.vault)?;
 
-        let seed_binding = escrow.seed.to_le_bytes();
-        let bump_binding = escrow.bump;
-        let escrow_seeds = [
-            Seed::from(b"escrow"),
-            Seed::from(self.accounts.maker.address().as_ref()),
-            Seed::from(seed_binding.as_ref()),
-            Seed::from(bump_binding.as_ref()),
-        ];
-        let signer = Signer::from(&escrow_seeds);
-        let amount =
-            pinocchio_token::state::TokenAccount::from_account_view(self.accounts.vault)?.amount();
-
-        Transfer {
-            from: self.accounts.vault,
-            to: self.accounts.maker_ata_a,
-            authority: self.accounts.escrow,
+        MemoProgram::invoke_if_required(
+            self.memo_program,
+            self.instruction_data.memo,
+            self.accounts.maker_ata_a,
+        )?;
+        TokenInterface::transfer_checked(
+            self.accounts.vault,
+            self.accounts.mint_a,
+            self.accounts.maker_ata_a,
+            self.accounts.escrow,
             amount,
-        }
-        .invoke_signed(core::slice::from_ref(&signer))?;
+            core::slice::from_ref(&signer),
+        )?;
 
-        pinocchio_token::instructions::CloseAccount {
-            account: self.accounts.vault,
-            destination: self.accounts.maker,
-            authority: self.accounts.escrow,
-        }
-        .invoke_signed(core::slice::from_ref(&signer))?;
+        TokenInterface::close_account(
+            self.accounts.vault,
+            self.accounts.maker,
+            self.accounts.escrow,
+            core::slice::from_ref(&signer),
+        )?;
 
+        MakerIndexAccount::release(escrow, self.maker_index)?;
+        close_escrow_extensions(
+            escrow,
+            self.accounts.escrow,
+            &mut self.extension_accounts.iter(),
+            self.accounts.maker,
+        )?;
         drop(data);
+        next_seq(self.accounts.config)?;
 
         ProgramAccount::close(self.accounts.escrow, self.accounts.maker)?;
         Ok(())
@@ -0,0 +1,78 @@
+use pinocchio::{
+    AccountView, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+};
+
+use crate::helpers::*;
+
+pub struct RefundAccountEscrowAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub account_escrow: &'a AccountView,
+    pub token_account: &'a AccountView,
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for RefundAccountEscrowAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let account_escrow = next_account(&mut iter)?;
+        let token_account = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+
+        SignerAccount::check(maker)?;
+        AccountEscrowAccount::check(account_escrow)?;
+        TokenAccountInterface::check(token_account)?;
+        TokenProgramAccount::check(token_program)?;
+
+        Ok(Self {
+            maker,
+            account_escrow,
+            token_account,
+        })
+    }
+}
+
+pub struct RefundAccountEscrow<'a> {
+    pub accounts: RefundAccountEscrowAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for RefundAccountEscrow<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = RefundAccountEscrowAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> RefundAccountEscrow<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &48;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.account_escrow.try_borrow()?;
+        let account_escrow = crate::state::AccountEscrow::load(&data)?;
+        if account_escrow.maker.ne(self.accounts.maker.address())
+            || account_escrow
+                .token_account
+                .ne(self.accounts.token_account.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let bump_binding = account_escrow.bump;
+        let account_escrow_seeds = [
+            Seed::from(crate::state::AccountEscrow::PREFIX),
+            Seed::from(self.accounts.token_account.address().as_ref()),
+            Seed::from(&bump_binding),
+        ];
+        TokenInterface::set_owner(
+            self.accounts.token_account,
+            self.accounts.account_escrow,
+            self.accounts.maker.address(),
+            &[Signer::from(&account_escrow_seeds)],
+        )?;
+        drop(data);
+
+        ProgramAccount::close(self.accounts.account_escrow, self.accounts.maker)
+    }
+}
@@ -0,0 +1,108 @@
+use pinocchio::{
+    AccountView, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+};
+
+use crate::helpers::*;
+
+pub struct RefundCollectionOfferAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub offer: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub vault: &'a AccountView,
+    pub maker_ata_b: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for RefundCollectionOfferAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let offer = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let vault = next_account(&mut iter)?;
+        let maker_ata_b = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+
+        SignerAccount::check(maker)?;
+        CollectionOfferAccount::check(offer)?;
+        MintInterface::check(mint_b)?;
+        AssociatedTokenAccount::check(vault, offer, mint_b, token_program)?;
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            maker,
+            offer,
+            mint_b,
+            vault,
+            maker_ata_b,
+            system_program,
+            token_program,
+        })
+    }
+}
+
+pub struct RefundCollectionOffer<'a> {
+    pub accounts: RefundCollectionOfferAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for RefundCollectionOffer<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = RefundCollectionOfferAccounts::try_from(accounts)?;
+        init_destination_if_needed(
+            accounts.maker_ata_b,
+            accounts.mint_b,
+            accounts.maker,
+            accounts.maker,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> RefundCollectionOffer<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &51;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.offer.try_borrow()?;
+        let offer = crate::state::CollectionOffer::load(&data)?;
+        if offer.maker.ne(self.accounts.maker.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let seed_binding = offer.seed.to_le_bytes();
+        let bump_binding = offer.bump;
+        let offer_seeds = [
+            Seed::from(crate::state::CollectionOffer::PREFIX),
+            Seed::from(offer.maker.as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&offer_seeds);
+        TokenInterface::transfer_checked(
+            self.accounts.vault,
+            self.accounts.mint_b,
+            self.accounts.maker_ata_b,
+            self.accounts.offer,
+            TokenInterface::get_amount(self.accounts.vault)?,
+            core::slice::from_ref(&signer),
+        )?;
+        TokenInterface::close_account(
+            self.accounts.vault,
+            self.accounts.maker,
+            self.accounts.offer,
+            core::slice::from_ref(&signer),
+        )?;
+        drop(data);
+
+        ProgramAccount::close(self.accounts.offer, self.accounts.maker)
+    }
+}
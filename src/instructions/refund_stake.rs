@@ -0,0 +1,80 @@
+use pinocchio::{
+    AccountView, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+};
+
+use crate::helpers::*;
+
+pub struct RefundStakeAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub stake_escrow: &'a AccountView,
+    pub stake: &'a AccountView,
+    pub clock: &'a AccountView,
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for RefundStakeAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let stake_escrow = next_account(&mut iter)?;
+        let stake = next_account(&mut iter)?;
+        let clock = next_account(&mut iter)?;
+        let stake_program = next_account(&mut iter)?;
+
+        SignerAccount::check(maker)?;
+        StakeEscrowAccount::check(stake_escrow)?;
+        ClockSysvarAccount::check(clock)?;
+        StakeProgramAccount::check(stake_program)?;
+
+        Ok(Self {
+            maker,
+            stake_escrow,
+            stake,
+            clock,
+        })
+    }
+}
+
+pub struct RefundStake<'a> {
+    pub accounts: RefundStakeAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for RefundStake<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = RefundStakeAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> RefundStake<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &45;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.stake_escrow.try_borrow()?;
+        let stake_escrow = crate::state::StakeEscrow::load(&data)?;
+        if stake_escrow.maker.ne(self.accounts.maker.address())
+            || stake_escrow.stake.ne(self.accounts.stake.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let bump_binding = stake_escrow.bump;
+        let stake_escrow_seeds = [
+            Seed::from(crate::state::StakeEscrow::PREFIX),
+            Seed::from(self.accounts.stake.address().as_ref()),
+            Seed::from(&bump_binding),
+        ];
+        StakeAccount::authorize(
+            self.accounts.stake,
+            self.accounts.clock,
+            self.accounts.stake_escrow,
+            self.accounts.maker.address(),
+            &[Signer::from(&stake_escrow_seeds)],
+        )?;
+        drop(data);
+
+        ProgramAccount::close(self.accounts.stake_escrow, self.accounts.maker)
+    }
+}
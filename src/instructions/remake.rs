@@ -0,0 +1,149 @@
+use pinocchio::{
+    AccountView, ProgramResult,
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::{errors::EscrowError, helpers::*};
+
+pub struct RemakeAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub mint_a: &'a AccountView,
+    pub maker_ata_a: &'a AccountView,
+    pub vault: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for RemakeAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let mint_a = next_account(&mut iter)?;
+        let maker_ata_a = next_account(&mut iter)?;
+        let vault = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_a)?;
+        AssociatedTokenAccount::check(maker_ata_a, maker, mint_a, token_program)?;
+        EscrowVault::check(vault, escrow, mint_a, token_program)?;
+        ConfigAccount::check(config)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            maker,
+            escrow,
+            mint_a,
+            maker_ata_a,
+            vault,
+            token_program,
+            config,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct RemakeInstructionData {
+    pub receive: u64,
+    pub deadline: i64,
+}
+impl<'a> TryFrom<&'a [u8]> for RemakeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u64>() + size_of::<i64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let receive = u64::from_le_bytes(instruction_bytes(data, 0)?);
+        let deadline = i64::from_le_bytes(instruction_bytes(data, 8)?);
+        Ok(Self { receive, deadline })
+    }
+}
+
+pub struct Remake<'a> {
+    pub accounts: RemakeAccounts<'a>,
+    pub instruction_data: RemakeInstructionData,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Remake<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = RemakeAccounts::try_from(accounts)?;
+        let instruction_data = RemakeInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> Remake<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &35;
+    pub fn process(&mut self) -> ProgramResult {
+        let escrow = *crate::state::Escrow::load(&self.accounts.escrow.try_borrow()?)?;
+        if escrow.is_auction() || escrow.is_vesting() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.is_frozen() {
+            return Err(EscrowError::EscrowFrozen.into());
+        }
+        if escrow.maker.ne(self.accounts.maker.address())
+            || escrow.mint_a.ne(self.accounts.mint_a.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let clock = Clock::get()?;
+        let expired = escrow.has_deadline() && escrow.is_expired(&clock);
+        if (escrow.fills == 0 && !expired) || escrow.is_proceeds_locked() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        if self.instruction_data.deadline != 0
+            && self.instruction_data.deadline
+                <= crate::state::Escrow::clock_for(escrow.deadline_kind, &clock)
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let vault_amount = TokenInterface::get_amount(self.accounts.vault)?;
+        let receive = match self.instruction_data.receive {
+            0 if escrow.is_standing()
+                || escrow.is_ratio_priced()
+                || vault_amount == escrow.amount =>
+            {
+                escrow.receive
+            }
+            0 if vault_amount == 0 => return Err(ProgramError::InvalidArgument),
+            0 => (escrow.receive as u128 * escrow.amount as u128).div_ceil(vault_amount as u128)
+                as u64,
+            receive => receive,
+        };
+        let shortfall = escrow.amount.saturating_sub(vault_amount);
+        if shortfall != 0 {
+            TokenInterface::transfer_checked(
+                self.accounts.maker_ata_a,
+                self.accounts.mint_a,
+                self.accounts.vault,
+                self.accounts.maker,
+                shortfall,
+                &[],
+            )?;
+        }
+
+        let mut data = self.accounts.escrow.try_borrow_mut()?;
+        let escrow = crate::state::Escrow::load_mut(data.as_mut())?;
+        escrow.set_receive(receive);
+        escrow.set_deadline(self.instruction_data.deadline);
+        escrow.set_fills(0);
+        escrow.set_last_update_seq(next_seq(self.accounts.config)?);
+        Ok(())
+    }
+}
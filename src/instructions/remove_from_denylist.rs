@@ -0,0 +1,83 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::helpers::*;
+
+pub struct RemoveFromDenylistAccounts<'a> {
+    pub admin: &'a AccountView,
+    pub config: &'a AccountView,
+    pub denylist: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for RemoveFromDenylistAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let admin = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let denylist = next_account(&mut iter)?;
+        next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(admin)?;
+        ConfigAccount::check(config)?;
+        if crate::state::Config::load(&config.try_borrow()?)?
+            .admin
+            .ne(admin.address())
+        {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+        DenylistAccount::check(denylist)?;
+
+        Ok(Self {
+            admin,
+            config,
+            denylist,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct RemoveFromDenylistInstructionData {
+    pub address: Address,
+}
+impl<'a> TryFrom<&'a [u8]> for RemoveFromDenylistInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let address: [u8; 32] = data
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        Ok(Self {
+            address: Address::new_from_array(address),
+        })
+    }
+}
+
+pub struct RemoveFromDenylist<'a> {
+    pub accounts: RemoveFromDenylistAccounts<'a>,
+    pub instruction_data: RemoveFromDenylistInstructionData,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for RemoveFromDenylist<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = RemoveFromDenylistAccounts::try_from(accounts)?;
+        let instruction_data = RemoveFromDenylistInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> RemoveFromDenylist<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &25;
+    pub fn process(&mut self) -> ProgramResult {
+        AddressListAccount::remove(
+            self.accounts.denylist,
+            self.accounts.admin,
+            &self.instruction_data.address,
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::helpers::*;
+
+pub struct RemoveFromMintListAccounts<'a> {
+    pub admin: &'a AccountView,
+    pub config: &'a AccountView,
+    pub mint_list: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for RemoveFromMintListAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let admin = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let mint_list = next_account(&mut iter)?;
+        next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(admin)?;
+        ConfigAccount::check(config)?;
+        if crate::state::Config::load(&config.try_borrow()?)?
+            .admin
+            .ne(admin.address())
+        {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+        MintListAccount::check(mint_list)?;
+
+        Ok(Self {
+            admin,
+            config,
+            mint_list,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct RemoveFromMintListInstructionData {
+    pub address: Address,
+}
+impl<'a> TryFrom<&'a [u8]> for RemoveFromMintListInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let address: [u8; 32] = data
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        Ok(Self {
+            address: Address::new_from_array(address),
+        })
+    }
+}
+
+pub struct RemoveFromMintList<'a> {
+    pub accounts: RemoveFromMintListAccounts<'a>,
+    pub instruction_data: RemoveFromMintListInstructionData,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for RemoveFromMintList<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = RemoveFromMintListAccounts::try_from(accounts)?;
+        let instruction_data = RemoveFromMintListInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> RemoveFromMintList<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &27;
+    pub fn process(&mut self) -> ProgramResult {
+        let remaining = AddressListAccount::remove(
+            self.accounts.mint_list,
+            self.accounts.admin,
+            &self.instruction_data.address,
+        )?;
+        if remaining == 0 {
+            let mut data = self.accounts.config.try_borrow_mut()?;
+            crate::state::Config::load_mut(data.as_mut())?
+                .set_mint_list(crate::state::Config::MINT_LIST_NONE);
+        }
+        Ok(())
+    }
+}
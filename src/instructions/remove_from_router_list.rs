@@ -0,0 +1,83 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::helpers::*;
+
+pub struct RemoveFromRouterListAccounts<'a> {
+    pub admin: &'a AccountView,
+    pub config: &'a AccountView,
+    pub router_list: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for RemoveFromRouterListAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let admin = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let router_list = next_account(&mut iter)?;
+        next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(admin)?;
+        ConfigAccount::check(config)?;
+        if crate::state::Config::load(&config.try_borrow()?)?
+            .admin
+            .ne(admin.address())
+        {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+        RouterListAccount::check(router_list)?;
+
+        Ok(Self {
+            admin,
+            config,
+            router_list,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct RemoveFromRouterListInstructionData {
+    pub address: Address,
+}
+impl<'a> TryFrom<&'a [u8]> for RemoveFromRouterListInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let address: [u8; 32] = data
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        Ok(Self {
+            address: Address::new_from_array(address),
+        })
+    }
+}
+
+pub struct RemoveFromRouterList<'a> {
+    pub accounts: RemoveFromRouterListAccounts<'a>,
+    pub instruction_data: RemoveFromRouterListInstructionData,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for RemoveFromRouterList<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = RemoveFromRouterListAccounts::try_from(accounts)?;
+        let instruction_data = RemoveFromRouterListInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> RemoveFromRouterList<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &39;
+    pub fn process(&mut self) -> ProgramResult {
+        AddressListAccount::remove(
+            self.accounts.router_list,
+            self.accounts.admin,
+            &self.instruction_data.address,
+        )?;
+        Ok(())
+    }
+}
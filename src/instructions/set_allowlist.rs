@@ -0,0 +1,73 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::helpers::*;
+
+pub struct SetAllowlistAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub config: &'a AccountView,
+    pub allowlist: Option<&'a AccountView>,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SetAllowlistAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+        ConfigAccount::check(config)?;
+
+        let allowlist = remaining_accounts.first();
+        if let Some(allowlist) = allowlist
+            && (!allowlist.executable() || allowlist.address().eq(&crate::ID))
+        {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        Ok(Self {
+            maker,
+            escrow,
+            config,
+            allowlist,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct SetAllowlist<'a> {
+    pub accounts: SetAllowlistAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for SetAllowlist<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = SetAllowlistAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> SetAllowlist<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &23;
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.escrow.try_borrow_mut()?;
+        let escrow = crate::state::Escrow::load_mut(data.as_mut())?;
+
+        if escrow.maker.ne(self.accounts.maker.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        escrow.set_allowlist(
+            self.accounts
+                .allowlist
+                .map_or(Address::default(), |allowlist| *allowlist.address()),
+        );
+        escrow.set_last_update_seq(next_seq(self.accounts.config)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use pinocchio::{AccountView, ProgramResult, error::ProgramError};
+
+use crate::helpers::*;
+
+pub struct SetCallerPolicyAccounts<'a> {
+    pub admin: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SetCallerPolicyAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let admin = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(admin)?;
+        ConfigAccount::check(config)?;
+        if crate::state::Config::load(&config.try_borrow()?)?
+            .admin
+            .ne(admin.address())
+        {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+
+        Ok(Self {
+            admin,
+            config,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct SetCallerPolicyInstructionData {
+    pub policy: u8,
+}
+impl<'a> TryFrom<&'a [u8]> for SetCallerPolicyInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        match data {
+            [policy] if *policy <= crate::state::Config::CALLER_ROUTERS => {
+                Ok(Self { policy: *policy })
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+pub struct SetCallerPolicy<'a> {
+    pub accounts: SetCallerPolicyAccounts<'a>,
+    pub instruction_data: SetCallerPolicyInstructionData,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for SetCallerPolicy<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = SetCallerPolicyAccounts::try_from(accounts)?;
+        let instruction_data = SetCallerPolicyInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> SetCallerPolicy<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &37;
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.config.try_borrow_mut()?;
+        crate::state::Config::load_mut(data.as_mut())?
+            .set_caller_policy(self.instruction_data.policy);
+        Ok(())
+    }
+}
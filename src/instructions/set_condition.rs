@@ -0,0 +1,145 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+};
+use pinocchio_system::create_account_with_minimum_balance_signed;
+
+use crate::helpers::*;
+
+pub struct SetConditionAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub condition: &'a AccountView,
+    pub condition_account: &'a AccountView,
+    pub condition_program: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SetConditionAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let condition = next_account(&mut iter)?;
+        let condition_account = next_account(&mut iter)?;
+        let condition_program = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+        ConfigAccount::check(config)?;
+        if !condition_account.owned_by(condition_program.address()) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        SystemProgramAccount::check(system_program)?;
+
+        Ok(Self {
+            maker,
+            escrow,
+            condition,
+            condition_account,
+            condition_program,
+            system_program,
+            config,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct SetConditionInstructionData<'a> {
+    pub offset: u32,
+    pub expected: &'a [u8],
+}
+impl<'a> TryFrom<&'a [u8]> for SetConditionInstructionData<'a> {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (offset, expected) = data
+            .split_first_chunk::<4>()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        if expected.is_empty() || expected.len() > 32 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self {
+            offset: u32::from_le_bytes(*offset),
+            expected,
+        })
+    }
+}
+
+pub struct SetCondition<'a> {
+    pub accounts: SetConditionAccounts<'a>,
+    pub instruction_data: SetConditionInstructionData<'a>,
+    pub bump: u8,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for SetCondition<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = SetConditionAccounts::try_from(accounts)?;
+        let instruction_data = SetConditionInstructionData::try_from(data)?;
+        let (condition_key, bump) = Address::find_program_address(
+            &[
+                crate::state::EscrowCondition::PREFIX,
+                accounts.escrow.address().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if condition_key.ne(accounts.condition.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let bump_binding = [bump];
+        let condition_seeds = [
+            Seed::from(crate::state::EscrowCondition::PREFIX),
+            Seed::from(accounts.escrow.address().as_ref()),
+            Seed::from(&bump_binding),
+        ];
+        create_account_with_minimum_balance_signed(
+            accounts.condition,
+            crate::state::EscrowCondition::LEN,
+            &crate::ID,
+            accounts.maker,
+            None,
+            &[Signer::from(&condition_seeds)],
+        )?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> SetCondition<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &22;
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.escrow.try_borrow_mut()?;
+        let escrow = crate::state::Escrow::load_mut(data.as_mut())?;
+
+        if escrow.maker.ne(self.accounts.maker.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.is_conditional() || escrow.fills != 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut condition_data = self.accounts.condition.try_borrow_mut()?;
+        crate::state::EscrowCondition::load_mut(condition_data.as_mut())?.set_inner(
+            *self.accounts.escrow.address(),
+            *self.accounts.condition_account.address(),
+            *self.accounts.condition_program.address(),
+            self.instruction_data.offset,
+            self.instruction_data.expected,
+            [self.bump],
+        );
+        escrow.set_conditional(true);
+        escrow.set_last_update_seq(next_seq(self.accounts.config)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+};
+use pinocchio_system::create_account_with_minimum_balance_signed;
+
+use crate::helpers::*;
+
+pub struct SetReleaseSignersAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub approvals: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SetReleaseSignersAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let approvals = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+        ConfigAccount::check(config)?;
+        SystemProgramAccount::check(system_program)?;
+
+        Ok(Self {
+            maker,
+            escrow,
+            approvals,
+            system_program,
+            config,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct SetReleaseSignersInstructionData<'a> {
+    pub threshold: u8,
+    pub signers: &'a [Address],
+}
+impl<'a> TryFrom<&'a [u8]> for SetReleaseSignersInstructionData<'a> {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (threshold, signers) = data
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        let signers = bytemuck::try_cast_slice::<u8, Address>(signers)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        if signers.is_empty()
+            || signers.len() > crate::state::ReleaseApprovals::MAX_SIGNERS
+            || *threshold == 0
+            || *threshold as usize > signers.len()
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if signers
+            .iter()
+            .enumerate()
+            .any(|(i, signer)| signers[..i].contains(signer))
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self {
+            threshold: *threshold,
+            signers,
+        })
+    }
+}
+
+pub struct SetReleaseSigners<'a> {
+    pub accounts: SetReleaseSignersAccounts<'a>,
+    pub instruction_data: SetReleaseSignersInstructionData<'a>,
+    pub bump: u8,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for SetReleaseSigners<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = SetReleaseSignersAccounts::try_from(accounts)?;
+        let instruction_data = SetReleaseSignersInstructionData::try_from(data)?;
+        let (approvals_key, bump) = Address::find_program_address(
+            &[
+                crate::state::ReleaseApprovals::PREFIX,
+                accounts.escrow.address().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if approvals_key.ne(accounts.approvals.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let bump_binding = [bump];
+        let approvals_seeds = [
+            Seed::from(crate::state::ReleaseApprovals::PREFIX),
+            Seed::from(accounts.escrow.address().as_ref()),
+            Seed::from(&bump_binding),
+        ];
+        create_account_with_minimum_balance_signed(
+            accounts.approvals,
+            crate::state::ReleaseApprovals::LEN,
+            &crate::ID,
+            accounts.maker,
+            None,
+            &[Signer::from(&approvals_seeds)],
+        )?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> SetReleaseSigners<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &53;
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.escrow.try_borrow_mut()?;
+        let escrow = crate::state::Escrow::load_mut(data.as_mut())?;
+
+        if escrow.maker.ne(self.accounts.maker.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.is_release_gated() || escrow.fills != 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut approvals_data = self.accounts.approvals.try_borrow_mut()?;
+        let approvals = crate::state::ReleaseApprovals::load_mut(approvals_data.as_mut())?;
+        approvals.set_inner(
+            *self.accounts.escrow.address(),
+            self.instruction_data.threshold,
+            [self.bump],
+        );
+        for signer in self.instruction_data.signers {
+            approvals.push(*signer);
+        }
+        escrow.set_release_gated(true);
+        escrow.set_last_update_seq(next_seq(self.accounts.config)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+};
+use pinocchio_system::create_account_with_minimum_balance_signed;
+
+use crate::helpers::*;
+
+pub struct SetRollAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub roll: &'a AccountView,
+    pub mint_a: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SetRollAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let roll = next_account(&mut iter)?;
+        let mint_a = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(maker)?;
+        MintInterface::check(mint_a)?;
+        MintInterface::check(mint_b)?;
+        SystemProgramAccount::check(system_program)?;
+
+        Ok(Self {
+            maker,
+            roll,
+            mint_a,
+            mint_b,
+            system_program,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct SetRollInstructionData {
+    pub receive_num: u64,
+    pub receive_den: u64,
+}
+impl<'a> TryFrom<&'a [u8]> for SetRollInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u64>() * 2 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let receive_num = u64::from_le_bytes(instruction_bytes(data, 0)?);
+        let receive_den = u64::from_le_bytes(instruction_bytes(data, 8)?);
+        if receive_num != 0 && receive_den == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self {
+            receive_num,
+            receive_den,
+        })
+    }
+}
+
+pub struct SetRoll<'a> {
+    pub accounts: SetRollAccounts<'a>,
+    pub instruction_data: SetRollInstructionData,
+    pub bump: u8,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for SetRoll<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = SetRollAccounts::try_from(accounts)?;
+        let instruction_data = SetRollInstructionData::try_from(data)?;
+        let (roll_key, bump) = Address::find_program_address(
+            &[
+                crate::state::RollConfig::PREFIX,
+                accounts.maker.address().as_ref(),
+                accounts.mint_a.address().as_ref(),
+                accounts.mint_b.address().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if roll_key.ne(accounts.roll.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> SetRoll<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &28;
+    pub fn process(&mut self) -> ProgramResult {
+        if self.accounts.roll.is_data_empty() {
+            if self.instruction_data.receive_num == 0 {
+                return Err(ProgramError::UninitializedAccount);
+            }
+            let bump_binding = [self.bump];
+            let roll_seeds = [
+                Seed::from(crate::state::RollConfig::PREFIX),
+                Seed::from(self.accounts.maker.address().as_ref()),
+                Seed::from(self.accounts.mint_a.address().as_ref()),
+                Seed::from(self.accounts.mint_b.address().as_ref()),
+                Seed::from(&bump_binding),
+            ];
+            create_account_with_minimum_balance_signed(
+                self.accounts.roll,
+                crate::state::RollConfig::LEN,
+                &crate::ID,
+                self.accounts.maker,
+                None,
+                &[Signer::from(&roll_seeds)],
+            )?;
+            let mut data = self.accounts.roll.try_borrow_mut()?;
+            crate::state::RollConfig::load_mut(data.as_mut())?.set_inner(
+                *self.accounts.maker.address(),
+                *self.accounts.mint_a.address(),
+                *self.accounts.mint_b.address(),
+                bump_binding,
+            );
+        } else if !self.accounts.roll.owned_by(&crate::ID)
+            || self
+                .accounts
+                .roll
+                .data_len()
+                .ne(&crate::state::RollConfig::LEN)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        RentExemptAccount::check(self.accounts.roll)?;
+
+        if self.instruction_data.receive_num == 0 {
+            return ProgramAccount::close(self.accounts.roll, self.accounts.maker);
+        }
+        let mut data = self.accounts.roll.try_borrow_mut()?;
+        crate::state::RollConfig::load_mut(data.as_mut())?.set_price(
+            self.instruction_data.receive_num,
+            self.instruction_data.receive_den,
+        );
+        Ok(())
+    }
+}
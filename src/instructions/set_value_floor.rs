@@ -0,0 +1,156 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+};
+use pinocchio_system::create_account_with_minimum_balance_signed;
+
+use crate::helpers::*;
+
+pub struct SetValueFloorAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub value_floor: &'a AccountView,
+    pub oracle: &'a AccountView,
+    pub oracle_program: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SetValueFloorAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let value_floor = next_account(&mut iter)?;
+        let oracle = next_account(&mut iter)?;
+        let oracle_program = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+        ConfigAccount::check(config)?;
+        if !oracle.owned_by(oracle_program.address()) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        SystemProgramAccount::check(system_program)?;
+
+        Ok(Self {
+            maker,
+            escrow,
+            value_floor,
+            oracle,
+            oracle_program,
+            system_program,
+            config,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct SetValueFloorInstructionData {
+    pub price_offset: u32,
+    pub timestamp_offset: u32,
+    pub max_age: u64,
+    pub min_value_bps: u16,
+    pub expo: i8,
+}
+impl SetValueFloorInstructionData {
+    pub const LEN: usize = size_of::<u32>() * 2 + size_of::<u64>() + size_of::<u16>() + 1;
+}
+impl<'a> TryFrom<&'a [u8]> for SetValueFloorInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let min_value_bps = u16::from_le_bytes(instruction_bytes(data, 16)?);
+        if min_value_bps == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self {
+            price_offset: u32::from_le_bytes(instruction_bytes(data, 0)?),
+            timestamp_offset: u32::from_le_bytes(instruction_bytes(data, 4)?),
+            max_age: u64::from_le_bytes(instruction_bytes(data, 8)?),
+            min_value_bps,
+            expo: i8::from_le_bytes(instruction_bytes(data, 18)?),
+        })
+    }
+}
+
+pub struct SetValueFloor<'a> {
+    pub accounts: SetValueFloorAccounts<'a>,
+    pub instruction_data: SetValueFloorInstructionData,
+    pub bump: u8,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for SetValueFloor<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = SetValueFloorAccounts::try_from(accounts)?;
+        let instruction_data = SetValueFloorInstructionData::try_from(data)?;
+        let (value_floor_key, bump) = Address::find_program_address(
+            &[
+                crate::state::ValueFloor::PREFIX,
+                accounts.escrow.address().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if value_floor_key.ne(accounts.value_floor.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let bump_binding = [bump];
+        let value_floor_seeds = [
+            Seed::from(crate::state::ValueFloor::PREFIX),
+            Seed::from(accounts.escrow.address().as_ref()),
+            Seed::from(&bump_binding),
+        ];
+        create_account_with_minimum_balance_signed(
+            accounts.value_floor,
+            crate::state::ValueFloor::LEN,
+            &crate::ID,
+            accounts.maker,
+            None,
+            &[Signer::from(&value_floor_seeds)],
+        )?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> SetValueFloor<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &52;
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.escrow.try_borrow_mut()?;
+        let escrow = crate::state::Escrow::load_mut(data.as_mut())?;
+
+        if escrow.maker.ne(self.accounts.maker.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.has_value_floor() || escrow.is_receive_in_ui() || escrow.fills != 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut value_floor_data = self.accounts.value_floor.try_borrow_mut()?;
+        let value_floor = crate::state::ValueFloor::load_mut(value_floor_data.as_mut())?;
+        value_floor.escrow = *self.accounts.escrow.address();
+        value_floor.oracle = *self.accounts.oracle.address();
+        value_floor.program = *self.accounts.oracle_program.address();
+        value_floor.max_age = self.instruction_data.max_age;
+        value_floor.price_offset = self.instruction_data.price_offset;
+        value_floor.timestamp_offset = self.instruction_data.timestamp_offset;
+        value_floor.min_value_bps = self.instruction_data.min_value_bps;
+        value_floor.expo = self.instruction_data.expo;
+        value_floor.bump = [self.bump];
+        escrow.set_value_floor(true);
+        escrow.set_last_update_seq(next_seq(self.accounts.config)?);
+        Ok(())
+    }
+}
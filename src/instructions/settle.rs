@@ -0,0 +1,206 @@
+use pinocchio::{
+    AccountView, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::helpers::*;
+
+pub struct SettleAccounts<'a> {
+    pub payer: &'a AccountView,
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub mint_a: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub vault: &'a AccountView,
+    pub bidder: &'a AccountView,
+    pub bid: &'a AccountView,
+    pub bid_vault: &'a AccountView,
+    pub bidder_ata_a: &'a AccountView,
+    pub maker_ata_b: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SettleAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let payer = next_account(&mut iter)?;
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let mint_a = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let vault = next_account(&mut iter)?;
+        let bidder = next_account(&mut iter)?;
+        let bid = next_account(&mut iter)?;
+        let bid_vault = next_account(&mut iter)?;
+        let bidder_ata_a = next_account(&mut iter)?;
+        let maker_ata_b = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(payer)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_a)?;
+        MintInterface::check(mint_b)?;
+        EscrowVault::check(vault, escrow, mint_a, token_program)?;
+        BidAccount::check(bid)?;
+        AssociatedTokenAccount::check(bid_vault, bid, mint_b, token_program)?;
+        ConfigAccount::check(config)?;
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            payer,
+            maker,
+            escrow,
+            mint_a,
+            mint_b,
+            vault,
+            bidder,
+            bid,
+            bid_vault,
+            bidder_ata_a,
+            maker_ata_b,
+            system_program,
+            token_program,
+            config,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct Settle<'a> {
+    pub accounts: SettleAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for Settle<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = SettleAccounts::try_from(accounts)?;
+        init_destination_if_needed(
+            accounts.bidder_ata_a,
+            accounts.mint_a,
+            accounts.payer,
+            accounts.bidder,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> Settle<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &5;
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.escrow.try_borrow()?;
+        let escrow = crate::state::Escrow::load(&data)?;
+        #[cfg(feature = "strict")]
+        MintPolicy::check_pair(self.accounts.mint_a, self.accounts.mint_b, escrow.flags)?;
+        MintPolicy::check_unpaused(self.accounts.mint_a, self.accounts.mint_b)?;
+        let bid_data = self.accounts.bid.try_borrow()?;
+        let bid = crate::state::AuctionBid::load(&bid_data)?;
+        check_program_pda(
+            self.accounts.bid,
+            &[b"bid", bid.escrow.as_ref(), bid.bidder.as_ref(), &bid.bump],
+        )?;
+
+        if !escrow.is_auction() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !escrow.is_expired(&Clock::get()?) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        if escrow.maker.ne(self.accounts.maker.address())
+            || escrow.mint_a.ne(self.accounts.mint_a.address())
+            || escrow.mint_b.ne(self.accounts.mint_b.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if bid.escrow.ne(self.accounts.escrow.address())
+            || bid.bidder.ne(&escrow.highest_bidder)
+            || bid.bidder.ne(self.accounts.bidder.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        init_proceeds_if_needed(
+            escrow,
+            self.accounts.maker_ata_b,
+            self.accounts.mint_b,
+            self.accounts.payer,
+            self.accounts.maker,
+            self.accounts.system_program,
+            self.accounts.token_program,
+        )?;
+
+        let escrow_seeds = EscrowSeeds::from_escrow(escrow);
+        let seeds = escrow_seeds.seeds();
+        let escrow_signer = Signer::from(&seeds);
+        let bid_bump_binding = bid.bump;
+        let bid_seeds = [
+            Seed::from(b"bid"),
+            Seed::from(bid.escrow.as_ref()),
+            Seed::from(bid.bidder.as_ref()),
+            Seed::from(bid_bump_binding.as_ref()),
+        ];
+        let bid_signer = Signer::from(&bid_seeds);
+        let amount = TokenInterface::get_amount(self.accounts.vault)?;
+
+        TokenInterface::transfer_checked(
+            self.accounts.vault,
+            self.accounts.mint_a,
+            self.accounts.bidder_ata_a,
+            self.accounts.escrow,
+            amount,
+            core::slice::from_ref(&escrow_signer),
+        )?;
+        TokenInterface::close_account(
+            self.accounts.vault,
+            self.accounts.maker,
+            self.accounts.escrow,
+            core::slice::from_ref(&escrow_signer),
+        )?;
+        TokenInterface::transfer_checked(
+            self.accounts.bid_vault,
+            self.accounts.mint_b,
+            self.accounts.maker_ata_b,
+            self.accounts.bid,
+            bid.amount,
+            core::slice::from_ref(&bid_signer),
+        )?;
+        TokenInterface::close_account(
+            self.accounts.bid_vault,
+            self.accounts.bidder,
+            self.accounts.bid,
+            core::slice::from_ref(&bid_signer),
+        )?;
+
+        drop(bid_data);
+        let mut optional_accounts = self.accounts.remaining_accounts.iter();
+        let maker_index = if escrow.is_indexed() {
+            Some(next_account(&mut optional_accounts)?)
+        } else {
+            None
+        };
+        MakerIndexAccount::release(escrow, maker_index)?;
+        close_escrow_extensions(
+            escrow,
+            self.accounts.escrow,
+            &mut optional_accounts,
+            self.accounts.maker,
+        )?;
+        drop(data);
+        next_seq(self.accounts.config)?;
+        ProgramAccount::close(self.accounts.bid, self.accounts.bidder)?;
+        ProgramAccount::close(self.accounts.escrow, self.accounts.maker)?;
+        Ok(())
+    }
+}
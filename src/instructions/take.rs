@@ -1,52 +1,62 @@
 use pinocchio::{
     AccountView, Address, ProgramResult,
-    cpi::{Seed, Signer},
+    cpi::{Seed, Signer, invoke_with_bounds},
     error::ProgramError,
+    instruction::{InstructionAccount, InstructionView},
+    sysvars::{Sysvar, clock::Clock},
 };
-use pinocchio_token::instructions::Transfer;
+use pinocchio_system::create_account_with_minimum_balance_signed;
 
-use crate::helpers::*;
+use crate::{errors::EscrowError, helpers::*};
 
 pub struct TakeAccounts<'a> {
     pub taker: &'a AccountView,
-    pub maker: &'a AccountView,
-    pub escrow: &'a AccountView,
+    pub maker: MakerAccount<'a>,
+    pub escrow: EscrowPda<'a>,
     pub mint_a: &'a AccountView,
     pub mint_b: &'a AccountView,
-    pub vault: &'a AccountView,
+    pub vault: VaultAccount<'a>,
     pub taker_ata_a: &'a AccountView,
     pub taker_ata_b: &'a AccountView,
     pub maker_ata_b: &'a AccountView,
     pub system_program: &'a AccountView,
     pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+    pub event_accounts: &'a [AccountView],
 }
 
 impl<'a> TryFrom<&'a [AccountView]> for TakeAccounts<'a> {
     type Error = ProgramError;
     fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
-        let [
-            taker,
-            maker,
-            escrow,
-            mint_a,
-            mint_b,
-            vault,
-            taker_ata_a,
-            taker_ata_b,
-            maker_ata_b,
-            system_program,
-            token_program,
-            _,
-        ] = accounts
-        else {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        };
+        let mut iter = accounts.iter();
+        let taker = next_account(&mut iter)?;
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let mint_a = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let vault = next_account(&mut iter)?;
+        let taker_ata_a = next_account(&mut iter)?;
+        let taker_ata_b = next_account(&mut iter)?;
+        let maker_ata_b = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let (remaining_accounts, event_accounts) =
+            crate::events::split_event_accounts(iter.as_slice())?;
         SignerAccount::check(taker)?;
-        ProgramAccount::check(escrow)?;
         MintInterface::check(mint_a)?;
         MintInterface::check(mint_b)?;
-        AssociatedTokenAccount::check(taker_ata_b, taker, mint_b, token_program)?;
-        AssociatedTokenAccount::check(vault, escrow, mint_a, token_program)?;
+        SourceTokenAccount::check(taker_ata_b, taker, mint_b)?;
+        ConfigAccount::check(config)?;
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+        let escrow = EscrowPda::check(escrow)?;
+        let maker = MakerAccount::of_escrow(maker, escrow)?;
+        let vault = VaultAccount::check(vault, escrow, mint_a, token_program)?;
+
         Ok(Self {
             taker,
             maker,
@@ -59,90 +69,793 @@ impl<'a> TryFrom<&'a [AccountView]> for TakeAccounts<'a> {
             vault,
             system_program,
             token_program,
+            config,
+            remaining_accounts,
+            event_accounts,
+        })
+    }
+}
+
+pub struct TakeInstructionData<'a> {
+    pub receipt: bool,
+    pub recipient: bool,
+    pub treasury: bool,
+    pub flash: bool,
+    pub referrer: bool,
+    pub fill: Option<u64>,
+    pub pay: Option<u64>,
+    pub memo: Option<&'a [u8]>,
+    pub fill_mode: u8,
+    pub terms: Option<[u8; 32]>,
+    pub callback_data: &'a [u8],
+}
+impl TakeInstructionData<'_> {
+    pub const RECEIPT: u8 = 1 << 0;
+    pub const RECIPIENT: u8 = 1 << 1;
+    pub const TREASURY: u8 = 1 << 2;
+    pub const FLASH: u8 = 1 << 3;
+    pub const REFERRER: u8 = 1 << 4;
+    pub const PARTIAL: u8 = 1 << 5;
+    pub const PAY: u8 = 1 << 6;
+    pub const MEMO: u8 = 1 << 7;
+    pub const TERMS_LEN: usize = 32;
+    pub const FILL_OR_KILL: u8 = 0;
+    pub const IMMEDIATE_OR_CANCEL: u8 = 1;
+}
+impl<'a> TryFrom<&'a [u8]> for TakeInstructionData<'a> {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (flags, callback_data) = match data.split_first() {
+            Some((flags, rest)) => (*flags, rest),
+            None => (0, data),
+        };
+        let (fill, callback_data) = if flags & Self::PARTIAL != 0 {
+            let (fill, rest) = callback_data
+                .split_first_chunk::<8>()
+                .ok_or(ProgramError::InvalidInstructionData)?;
+            (Some(u64::from_le_bytes(*fill)), rest)
+        } else {
+            (None, callback_data)
+        };
+        let (pay, callback_data) = if flags & Self::PAY != 0 {
+            let (pay, rest) = callback_data
+                .split_first_chunk::<8>()
+                .ok_or(ProgramError::InvalidInstructionData)?;
+            (Some(u64::from_le_bytes(*pay)), rest)
+        } else {
+            (None, callback_data)
+        };
+        let (memo, callback_data) = if flags & Self::MEMO != 0 {
+            let (memo, rest) = parse_memo(callback_data)?;
+            (Some(memo), rest)
+        } else {
+            (None, callback_data)
+        };
+        let flash = flags & Self::FLASH != 0;
+        if flash && pay.is_some() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (fill_mode, terms, callback_data) = if flash {
+            (Self::FILL_OR_KILL, None, callback_data)
+        } else {
+            let (fill_mode, rest) = match callback_data.split_first() {
+                Some((fill_mode, rest)) if matches!(rest.len(), 0 | Self::TERMS_LEN) => {
+                    (*fill_mode, rest)
+                }
+                _ => (Self::FILL_OR_KILL, callback_data),
+            };
+            match rest.split_first_chunk::<32>() {
+                Some((terms, rest)) if rest.is_empty() => (fill_mode, Some(*terms), rest),
+                None if rest.is_empty() => (fill_mode, None, rest),
+                _ => return Err(ProgramError::InvalidInstructionData),
+            }
+        };
+        if fill_mode > Self::IMMEDIATE_OR_CANCEL
+            || (fill_mode == Self::IMMEDIATE_OR_CANCEL && fill.is_none())
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self {
+            receipt: flags & Self::RECEIPT != 0,
+            recipient: flags & Self::RECIPIENT != 0,
+            treasury: flags & Self::TREASURY != 0,
+            flash,
+            referrer: flags & Self::REFERRER != 0,
+            fill,
+            pay,
+            memo,
+            fill_mode,
+            terms,
+            callback_data,
         })
     }
 }
 
+const MAX_CALLBACK_ACCOUNTS: usize = 16;
+
 pub struct Take<'a> {
     pub accounts: TakeAccounts<'a>,
+    pub instruction_data: TakeInstructionData<'a>,
+    pub receipt: Option<&'a AccountView>,
+    pub recipient: &'a AccountView,
+    pub treasury: Option<&'a AccountView>,
+    pub referrer: Option<&'a AccountView>,
+    pub memo_program: Option<&'a AccountView>,
+    pub cooldown: Option<&'a AccountView>,
+    pub value_floor: Option<(&'a AccountView, &'a AccountView)>,
+    pub maker_index: Option<&'a AccountView>,
+    pub stats: Option<&'a AccountView>,
+    pub royalty: Option<Royalty>,
+    pub creator_accounts: &'a [AccountView],
+    pub split: Option<&'a AccountView>,
+    pub split_accounts: &'a [AccountView],
+    pub approvals: Option<&'a AccountView>,
+    pub callback_program: Option<&'a AccountView>,
+    pub callback_accounts: &'a [AccountView],
+    #[cfg(feature = "wormhole")]
+    pub wormhole: WormholeAccounts<'a>,
 }
-impl<'a> TryFrom<&'a [AccountView]> for Take<'a> {
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Take<'a> {
     type Error = ProgramError;
-    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
         let accounts = TakeAccounts::try_from(accounts)?;
-        AssociatedTokenAccount::init_if_needed(
+        let instruction_data = TakeInstructionData::try_from(data)?;
+        Self::new(accounts, instruction_data)
+    }
+}
+
+impl<'a> Take<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &1;
+
+    pub fn new(
+        accounts: TakeAccounts<'a>,
+        instruction_data: TakeInstructionData<'a>,
+    ) -> Result<Self, ProgramError> {
+        let mut optional_accounts = accounts.remaining_accounts.iter();
+        let receipt = if instruction_data.receipt {
+            Some(next_account(&mut optional_accounts)?)
+        } else {
+            None
+        };
+        let recipient = if instruction_data.recipient {
+            next_account(&mut optional_accounts)?
+        } else {
+            accounts.taker
+        };
+        let treasury = if instruction_data.treasury {
+            Some(next_account(&mut optional_accounts)?)
+        } else {
+            None
+        };
+        let referrer = if instruction_data.referrer {
+            let referrer = next_account(&mut optional_accounts)?;
+            TokenAccountInterface::check(referrer)?;
+            if TokenInterface::get_mint(referrer)?.ne(accounts.mint_b.address()) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            Some(referrer)
+        } else {
+            None
+        };
+        let memo_program = if instruction_data.memo.is_some() {
+            Some(next_account(&mut optional_accounts)?)
+        } else {
+            None
+        };
+        let (
+            has_cooldown,
+            is_conditional,
+            has_value_floor,
+            is_release_gated,
+            allowlist,
+            is_indexed,
+            is_tracked,
+            has_royalty,
+            splits,
+        ) = {
+            let data = accounts.escrow.try_borrow()?;
+            let escrow = crate::state::Escrow::load(&data)?;
+            (
+                escrow.has_cooldown(),
+                escrow.is_conditional(),
+                escrow.has_value_floor(),
+                escrow.is_release_gated(),
+                escrow.has_allowlist().then_some(escrow.allowlist),
+                escrow.is_indexed(),
+                escrow.is_tracked(),
+                escrow.has_royalty(),
+                escrow.splits as usize,
+            )
+        };
+        let cooldown = if has_cooldown {
+            Some(next_account(&mut optional_accounts)?)
+        } else {
+            None
+        };
+        if is_conditional {
+            let condition = next_account(&mut optional_accounts)?;
+            let condition_account = next_account(&mut optional_accounts)?;
+            ConditionAccount::check_met(condition, condition_account, &accounts.escrow)?;
+        }
+        let value_floor = if has_value_floor {
+            Some((
+                next_account(&mut optional_accounts)?,
+                next_account(&mut optional_accounts)?,
+            ))
+        } else {
+            None
+        };
+        let approvals = if is_release_gated {
+            let approvals = next_account(&mut optional_accounts)?;
+            ReleaseApprovalsAccount::check_released(approvals, &accounts.escrow)?;
+            Some(approvals)
+        } else {
+            None
+        };
+        if let Some(allowlist) = allowlist {
+            let pass = next_account(&mut optional_accounts)?;
+            AllowlistAccount::check(pass, &allowlist, accounts.taker)?;
+        }
+        if DenylistAccount::is_enabled(accounts.config)? {
+            DenylistAccount::check_parties(
+                next_account(&mut optional_accounts)?,
+                &[accounts.taker.address(), accounts.maker.address()],
+            )?;
+        }
+        CallerPolicy::check(accounts.config, &mut optional_accounts)?;
+        let maker_index = if is_indexed {
+            Some(next_account(&mut optional_accounts)?)
+        } else {
+            None
+        };
+        let stats = if is_tracked {
+            let stats = next_account(&mut optional_accounts)?;
+            StatsAccount::check(stats)?;
+            Some(stats)
+        } else {
+            None
+        };
+        let royalty = if has_royalty {
+            if !cfg!(feature = "nft") || instruction_data.flash {
+                return Err(ProgramError::InvalidArgument);
+            }
+            let metadata = next_account(&mut optional_accounts)?;
+            Some(MetadataAccount::royalty(metadata, accounts.mint_a)?)
+        } else {
+            None
+        };
+        let creators = royalty
+            .as_ref()
+            .map_or(0, |royalty| royalty.creators().len());
+        let (creator_accounts, rest) = optional_accounts
+            .as_slice()
+            .split_at_checked(creators)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if let Some(royalty) = &royalty {
+            for (creator, account) in royalty.creators().iter().zip(creator_accounts) {
+                TokenAccountInterface::check(account)?;
+                if TokenInterface::get_mint(account)?.ne(accounts.mint_b.address())
+                    || TokenInterface::get_owner(account)?.ne(&creator.address)
+                {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+            }
+        }
+        let mut optional_accounts = rest.iter();
+        let split = if splits != 0 {
+            if instruction_data.flash {
+                return Err(ProgramError::InvalidArgument);
+            }
+            Some(next_account(&mut optional_accounts)?)
+        } else {
+            None
+        };
+        let (split_accounts, rest) = optional_accounts
+            .as_slice()
+            .split_at_checked(splits)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if let Some(split) = split {
+            ProceedsSplitAccount::check(split, &accounts.escrow, split_accounts)?;
+        }
+        let mut optional_accounts = rest.iter();
+        #[cfg(feature = "wormhole")]
+        let wormhole = WormholeAccounts::parse(&mut optional_accounts)?;
+        let callback_program = if instruction_data.flash {
+            Some(next_account(&mut optional_accounts)?)
+        } else {
+            None
+        };
+        let callback_accounts = optional_accounts.as_slice();
+        if callback_accounts.len() > MAX_CALLBACK_ACCOUNTS {
+            return Err(ProgramError::InvalidArgument);
+        }
+        init_destination_if_needed(
             accounts.taker_ata_a,
             accounts.mint_a,
             accounts.taker,
-            accounts.taker,
-            accounts.system_program,
-            accounts.token_program,
-        )?;
-        AssociatedTokenAccount::init_if_needed(
-            accounts.maker_ata_b,
-            accounts.mint_b,
-            accounts.taker,
-            accounts.maker,
+            recipient,
             accounts.system_program,
             accounts.token_program,
         )?;
-        Ok(Self { accounts })
+        Ok(Self {
+            accounts,
+            instruction_data,
+            receipt,
+            recipient,
+            treasury,
+            referrer,
+            memo_program,
+            cooldown,
+            value_floor,
+            maker_index,
+            stats,
+            royalty,
+            creator_accounts,
+            split,
+            split_accounts,
+            approvals,
+            callback_program,
+            callback_accounts,
+            #[cfg(feature = "wormhole")]
+            wormhole,
+        })
     }
-}
 
-impl<'a> Take<'a> {
-    pub const DISCRIMINATOR: &'a u8 = &1;
     pub fn process(&mut self) -> ProgramResult {
-        let data = self.accounts.escrow.try_borrow()?;
-        let escrow = crate::state::Escrow::load(&data)?;
-        let escrow_key = Address::create_program_address(
+        let escrow = *crate::state::Escrow::load(&self.accounts.escrow.try_borrow()?)?;
+        if escrow.mint_a.ne(self.accounts.mint_a.address())
+            || escrow.mint_b.ne(self.accounts.mint_b.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        #[cfg(feature = "strict")]
+        MintPolicy::check_pair(self.accounts.mint_a, self.accounts.mint_b, escrow.flags)?;
+        MintPolicy::check_unpaused(self.accounts.mint_a, self.accounts.mint_b)?;
+        if escrow.is_auction() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.is_frozen() {
+            return Err(EscrowError::EscrowFrozen.into());
+        }
+        if self
+            .instruction_data
+            .terms
+            .is_some_and(|terms| terms != escrow.terms_hash())
+        {
+            return Err(EscrowError::TermsMismatch.into());
+        }
+        let clock = Clock::get()?;
+        if escrow.has_deadline() && escrow.is_expired(&clock) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let vesting = escrow.is_vesting();
+        if vesting && escrow.vesting_start != 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if vesting
+            && (self.instruction_data.recipient
+                || self.instruction_data.flash
+                || self.instruction_data.fill.is_some())
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+        if escrow.has_burn() && self.instruction_data.flash {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let escrow_seeds = EscrowSeeds::from_escrow(&escrow);
+        if escrow_seeds.address()?.ne(self.accounts.escrow.address()) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if let Some(cooldown) = self.cooldown {
+            self.record_cooldown(&escrow, cooldown)?;
+        }
+        let seeds = escrow_seeds.seeds();
+        let signer = Signer::from(&seeds);
+        let vault_amount = TokenInterface::get_amount(&self.accounts.vault)?;
+        let standing = escrow.is_standing();
+        let available = if standing {
+            if vault_amount < escrow.amount {
+                return Err(ProgramError::InsufficientFunds);
+            }
+            escrow.amount
+        } else {
+            vault_amount
+        };
+        let amount = match self.instruction_data.fill {
+            Some(fill)
+                if self.instruction_data.fill_mode == TakeInstructionData::IMMEDIATE_OR_CANCEL =>
+            {
+                escrow.clamp_fill(fill, available)
+            }
+            Some(fill) => fill,
+            None => available,
+        };
+        if amount == 0 || amount > available || !escrow.is_valid_fill(amount, vault_amount) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let partial = amount < available;
+        let quoted = if escrow.is_ratio_priced() {
+            escrow
+                .ratio_quote(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?
+        } else if partial {
+            (escrow.receive as u128 * amount as u128).div_ceil(available as u128) as u64
+        } else {
+            escrow.receive
+        };
+        let scale_a = UiAmount::scale(self.accounts.mint_a, clock.unix_timestamp)?;
+        let scale_b = UiAmount::scale(self.accounts.mint_b, clock.unix_timestamp)?;
+        let receive = if escrow.is_receive_in_ui() {
+            UiAmount::from_ui(quoted, scale_b)?
+        } else {
+            quoted
+        };
+        if let Some((value_floor, oracle)) = self.value_floor {
+            ValueFloorAccount::check_met(
+                value_floor,
+                oracle,
+                &self.accounts.escrow,
+                self.accounts.mint_a,
+                self.accounts.mint_b,
+                amount,
+                receive,
+            )?;
+        }
+        let paid = escrow.payment(receive, self.instruction_data.pay.unwrap_or(receive))?;
+
+        if !vesting {
+            self.memo(self.accounts.taker_ata_a)?;
+            TokenInterface::transfer_checked(
+                &self.accounts.vault,
+                self.accounts.mint_a,
+                self.accounts.taker_ata_a,
+                &self.accounts.escrow,
+                amount,
+                core::slice::from_ref(&signer),
+            )?;
+        }
+        if !standing && !vesting && !partial {
+            TokenInterface::close_account(
+                &self.accounts.vault,
+                self.rent_destination(&escrow)?,
+                &self.accounts.escrow,
+                core::slice::from_ref(&signer),
+            )?;
+        }
+        init_proceeds_if_needed(
+            &escrow,
+            self.accounts.maker_ata_b,
+            self.accounts.mint_b,
+            self.accounts.taker,
+            &self.accounts.maker,
+            self.accounts.system_program,
+            self.accounts.token_program,
+        )?;
+        self.pay_referral(receive)?;
+        if self.instruction_data.flash {
+            let expected = TokenInterface::get_amount(self.accounts.maker_ata_b)?
+                .checked_add(receive)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            self.invoke_callback()?;
+            if TokenInterface::get_amount(self.accounts.maker_ata_b)? < expected {
+                return Err(EscrowError::FlashRepaymentShortfall.into());
+            }
+        } else {
+            let royalties = self.pay_royalties(receive)?;
+            let burned = escrow.burn_amount(receive);
+            if burned != 0 {
+                TokenInterface::burn_checked(
+                    self.accounts.taker_ata_b,
+                    self.accounts.mint_b,
+                    self.accounts.taker,
+                    burned,
+                    &[],
+                )?;
+            }
+            let net = royalties
+                .checked_add(burned)
+                .and_then(|fees| paid.checked_sub(fees))
+                .ok_or(ProgramError::InsufficientFunds)?;
+            let split = self.pay_splits(net)?;
+            self.memo(self.accounts.maker_ata_b)?;
+            TokenInterface::transfer_checked(
+                self.accounts.taker_ata_b,
+                self.accounts.mint_b,
+                self.accounts.maker_ata_b,
+                self.accounts.taker,
+                net - split,
+                &[],
+            )?;
+        }
+
+        let fills = escrow.fills;
+        crate::events::FillEvent {
+            escrow: *self.accounts.escrow.address(),
+            maker: escrow.maker,
+            taker: *self.accounts.taker.address(),
+            mint_a: escrow.mint_a,
+            mint_b: escrow.mint_b,
+            amount_a: amount,
+            amount_b: paid,
+            ui_amount_a: UiAmount::to_ui(amount, scale_a),
+            ui_amount_b: UiAmount::to_ui(paid, scale_b),
+            fills,
+            label: escrow.label,
+        }
+        .emit_cpi(self.accounts.event_accounts)?;
+        #[cfg(feature = "wormhole")]
+        self.wormhole.post_fill(
+            &WormholeFill {
+                escrow: *self.accounts.escrow.address(),
+                maker: escrow.maker,
+                taker: *self.accounts.taker.address(),
+                mint_a: escrow.mint_a,
+                mint_b: escrow.mint_b,
+                amount_a: amount,
+                amount_b: paid,
+            },
+            crate::state::Config::load(&self.accounts.config.try_borrow()?)?.seq + 1,
+            self.accounts.taker,
+            self.accounts.system_program,
+        )?;
+        GroupMemberMint::emit(self.accounts.escrow.address(), self.accounts.mint_a)?;
+        GroupMemberMint::emit(self.accounts.escrow.address(), self.accounts.mint_b)?;
+        if let Some(stats) = self.stats {
+            StatsAccount::record_fill(stats, &escrow, amount, paid)?;
+        }
+        if self.instruction_data.receipt {
+            self.write_receipt(&escrow, amount, paid, fills)?;
+        }
+
+        if vesting || standing || partial {
+            let mut data = self.accounts.escrow.try_borrow_mut()?;
+            let escrow = crate::state::Escrow::load_mut(data.as_mut())?;
+            if vesting {
+                escrow.start_vesting(*self.accounts.taker.address(), amount, clock.unix_timestamp);
+            }
+            if partial && !standing && !escrow.is_ratio_priced() {
+                escrow.set_receive(escrow.receive - quoted);
+            }
+            escrow.set_fills(fills + 1);
+            escrow.set_last_update_seq(next_seq(self.accounts.config)?);
+        } else {
+            next_seq(self.accounts.config)?;
+            MakerIndexAccount::release(&escrow, self.maker_index)?;
+            close_escrow_extensions(
+                &escrow,
+                &self.accounts.escrow,
+                &mut self.split.into_iter().chain(self.approvals),
+                &self.accounts.maker,
+            )?;
+            ProgramAccount::close(&self.accounts.escrow, self.accounts.taker)?;
+        }
+        Ok(())
+    }
+
+    fn record_cooldown(
+        &self,
+        escrow: &crate::state::Escrow,
+        cooldown: &AccountView,
+    ) -> ProgramResult {
+        let (cooldown_key, bump) = Address::find_program_address(
             &[
-                b"escrow",
-                self.accounts.maker.address().as_ref(),
-                &escrow.seed.to_le_bytes(),
-                &escrow.bump,
+                crate::state::TakerCooldown::PREFIX,
+                self.accounts.escrow.address().as_ref(),
+                self.accounts.taker.address().as_ref(),
             ],
             &crate::ID,
-        )?;
-        if escrow_key.ne(self.accounts.escrow.address()) {
-            return Err(ProgramError::InvalidAccountOwner);
+        );
+        if cooldown.address().ne(&cooldown_key) {
+            return Err(ProgramError::InvalidSeeds);
         }
+        if cooldown.is_data_empty() {
+            let bump_binding = [bump];
+            let seeds = [
+                Seed::from(crate::state::TakerCooldown::PREFIX),
+                Seed::from(self.accounts.escrow.address().as_ref()),
+                Seed::from(self.accounts.taker.address().as_ref()),
+                Seed::from(&bump_binding),
+            ];
+            create_account_with_minimum_balance_signed(
+                cooldown,
+                crate::state::TakerCooldown::LEN,
+                &crate::ID,
+                self.accounts.taker,
+                None,
+                &[Signer::from(&seeds)],
+            )?;
+            let mut data = cooldown.try_borrow_mut()?;
+            crate::state::TakerCooldown::load_mut(data.as_mut())?.set_inner(
+                *self.accounts.escrow.address(),
+                *self.accounts.taker.address(),
+                bump_binding,
+            );
+        } else if !cooldown.owned_by(&crate::ID)
+            || cooldown.data_len().ne(&crate::state::TakerCooldown::LEN)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        RentExemptAccount::check(cooldown)?;
+        let slot = Clock::get()?.slot;
+        let mut data = cooldown.try_borrow_mut()?;
+        let cooldown = crate::state::TakerCooldown::load_mut(data.as_mut())?;
+        if cooldown.is_cooling_down(escrow.cooldown_slots, slot) {
+            return Err(EscrowError::TakerCooldown.into());
+        }
+        cooldown.last_fill_slot = slot;
+        Ok(())
+    }
+
+    fn pay_referral(&self, receive: u64) -> ProgramResult {
+        let Some(referrer) = self.referrer else {
+            return Ok(());
+        };
+        let data = self.accounts.config.try_borrow()?;
+        let fee = crate::state::Config::load(&data)?.referral_fee(receive);
+        if fee == 0 {
+            return Ok(());
+        }
+        self.memo(referrer)?;
+        TokenInterface::transfer_checked(
+            self.accounts.taker_ata_b,
+            self.accounts.mint_b,
+            referrer,
+            self.accounts.taker,
+            fee,
+            &[],
+        )
+    }
+
+    fn pay_royalties(&self, receive: u64) -> Result<u64, ProgramError> {
+        let Some(royalty) = &self.royalty else {
+            return Ok(0);
+        };
+        let mut paid = 0u64;
+        for (creator, account) in royalty.creators().iter().zip(self.creator_accounts) {
+            let share = royalty.share(creator, receive);
+            if share == 0 {
+                continue;
+            }
+            self.memo(account)?;
+            TokenInterface::transfer_checked(
+                self.accounts.taker_ata_b,
+                self.accounts.mint_b,
+                account,
+                self.accounts.taker,
+                share,
+                &[],
+            )?;
+            paid = paid
+                .checked_add(share)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+        if paid > royalty.amount(receive) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(paid)
+    }
+
+    fn pay_splits(&self, net: u64) -> Result<u64, ProgramError> {
+        let Some(split) = self.split else {
+            return Ok(0);
+        };
+        let data = split.try_borrow()?;
+        let split = crate::state::ProceedsSplit::load(&data)?;
+        let mut paid = 0u64;
+        for (index, account) in self.split_accounts.iter().enumerate() {
+            let share = split.share(index, net);
+            if share == 0 {
+                continue;
+            }
+            self.memo(account)?;
+            TokenInterface::transfer_checked(
+                self.accounts.taker_ata_b,
+                self.accounts.mint_b,
+                account,
+                self.accounts.taker,
+                share,
+                &[],
+            )?;
+            paid += share;
+        }
+        Ok(paid)
+    }
 
-        let seed_binding = escrow.seed.to_le_bytes();
-        let bump_binding = escrow.bump;
-        let escrow_seeds = [
-            Seed::from(b"escrow"),
-            Seed::from(self.accounts.maker.address().as_ref()),
-            Seed::from(seed_binding.as_ref()),
-            Seed::from(bump_binding.as_ref()),
+    fn memo(&self, destination: &AccountView) -> ProgramResult {
+        MemoProgram::invoke_if_required(self.memo_program, self.instruction_data.memo, destination)
+    }
+
+    fn invoke_callback(&self) -> ProgramResult {
+        let program = self
+            .callback_program
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if program.address().eq(&crate::ID) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let len = self.callback_accounts.len();
+        if len > MAX_CALLBACK_ACCOUNTS {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let instruction_accounts: [InstructionAccount; MAX_CALLBACK_ACCOUNTS] =
+            core::array::from_fn(|i| {
+                self.callback_accounts
+                    .get(i)
+                    .map_or(InstructionAccount::readonly(program.address()), |account| {
+                        InstructionAccount::from(account)
+                    })
+            });
+        let account_views: [&AccountView; MAX_CALLBACK_ACCOUNTS] =
+            core::array::from_fn(|i| self.callback_accounts.get(i).unwrap_or(program));
+        invoke_with_bounds::<MAX_CALLBACK_ACCOUNTS>(
+            &InstructionView {
+                program_id: program.address(),
+                accounts: &instruction_accounts[..len],
+                data: self.instruction_data.callback_data,
+            },
+            &account_views[..len],
+        )
+    }
+
+    fn rent_destination(
+        &self,
+        escrow: &crate::state::Escrow,
+    ) -> Result<&'a AccountView, ProgramError> {
+        rent_destination(
+            escrow,
+            self.accounts.config,
+            self.accounts.maker.account(),
+            self.accounts.taker,
+            self.treasury,
+        )
+    }
+
+    fn write_receipt(
+        &self,
+        escrow: &crate::state::Escrow,
+        amount: u64,
+        receive: u64,
+        fills: u64,
+    ) -> ProgramResult {
+        let receipt = self.receipt.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let fills_binding = fills.to_le_bytes();
+        let (receipt_key, bump) = Address::find_program_address(
+            &[
+                b"receipt",
+                self.accounts.escrow.address().as_ref(),
+                &fills_binding,
+            ],
+            &crate::ID,
+        );
+        if receipt_key.ne(receipt.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let bump_binding = [bump];
+        let receipt_seeds = [
+            Seed::from(b"receipt"),
+            Seed::from(self.accounts.escrow.address().as_ref()),
+            Seed::from(&fills_binding),
+            Seed::from(&bump_binding),
         ];
-        let signer = Signer::from(&escrow_seeds);
-        let amount =
-            pinocchio_token::state::TokenAccount::from_account_view(self.accounts.vault)?.amount();
-
-        Transfer {
-            from: self.accounts.vault,
-            to: self.accounts.taker_ata_a,
-            authority: self.accounts.escrow,
-            amount,
-        }
-        .invoke_signed(core::slice::from_ref(&signer))?;
-        pinocchio_token::instructions::CloseAccount {
-            account: self.accounts.vault,
-            destination: self.accounts.maker,
-            authority: self.accounts.escrow,
-        }
-        .invoke_signed(core::slice::from_ref(&signer))?;
-        Transfer {
-            from: self.accounts.taker_ata_b,
-            to: self.accounts.maker_ata_b,
-            authority: self.accounts.taker,
-            amount: escrow.receive,
-        }
-        .invoke()?;
-
-        drop(data);
-        ProgramAccount::close(self.accounts.escrow, self.accounts.taker)?;
+        create_account_with_minimum_balance_signed(
+            receipt,
+            crate::state::FillReceipt::LEN,
+            &crate::ID,
+            self.accounts.taker,
+            None,
+            &[Signer::from(&receipt_seeds)],
+        )?;
+
+        let mut data = receipt.try_borrow_mut()?;
+        let receipt = crate::state::FillReceipt::load_mut(data.as_mut())?;
+        receipt.escrow = *self.accounts.escrow.address();
+        receipt.taker = *self.accounts.taker.address();
+        receipt.mint_a = escrow.mint_a;
+        receipt.mint_b = escrow.mint_b;
+        receipt.amount_a = amount;
+        receipt.amount_b = receive;
+        receipt.slot = Clock::get()?.slot;
+        receipt.bump = [bump];
         Ok(())
     }
 }
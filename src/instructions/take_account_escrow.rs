@@ -0,0 +1,126 @@
+use pinocchio::{
+    AccountView, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+};
+
+use crate::helpers::*;
+
+pub struct TakeAccountEscrowAccounts<'a> {
+    pub taker: &'a AccountView,
+    pub maker: &'a AccountView,
+    pub account_escrow: &'a AccountView,
+    pub token_account: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub taker_ata_b: &'a AccountView,
+    pub maker_ata_b: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for TakeAccountEscrowAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let taker = next_account(&mut iter)?;
+        let maker = next_account(&mut iter)?;
+        let account_escrow = next_account(&mut iter)?;
+        let token_account = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let taker_ata_b = next_account(&mut iter)?;
+        let maker_ata_b = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(taker)?;
+        AccountEscrowAccount::check(account_escrow)?;
+        TokenAccountInterface::check(token_account)?;
+        MintInterface::check(mint_b)?;
+        TokenAccountInterface::check(taker_ata_b)?;
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        ConfigAccount::check(config)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            taker,
+            maker,
+            account_escrow,
+            token_account,
+            mint_b,
+            taker_ata_b,
+            maker_ata_b,
+            system_program,
+            token_program,
+            config,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct TakeAccountEscrow<'a> {
+    pub accounts: TakeAccountEscrowAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for TakeAccountEscrow<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = TakeAccountEscrowAccounts::try_from(accounts)?;
+        CallerPolicy::check(accounts.config, &mut accounts.remaining_accounts.iter())?;
+        init_destination_if_needed(
+            accounts.maker_ata_b,
+            accounts.mint_b,
+            accounts.taker,
+            accounts.maker,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> TakeAccountEscrow<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &47;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.account_escrow.try_borrow()?;
+        let account_escrow = crate::state::AccountEscrow::load(&data)?;
+        if account_escrow.maker.ne(self.accounts.maker.address())
+            || account_escrow
+                .token_account
+                .ne(self.accounts.token_account.address())
+            || account_escrow.mint_b.ne(self.accounts.mint_b.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        TokenInterface::transfer_checked(
+            self.accounts.taker_ata_b,
+            self.accounts.mint_b,
+            self.accounts.maker_ata_b,
+            self.accounts.taker,
+            account_escrow.receive,
+            &[],
+        )?;
+
+        let bump_binding = account_escrow.bump;
+        let account_escrow_seeds = [
+            Seed::from(crate::state::AccountEscrow::PREFIX),
+            Seed::from(self.accounts.token_account.address().as_ref()),
+            Seed::from(&bump_binding),
+        ];
+        TokenInterface::set_owner(
+            self.accounts.token_account,
+            self.accounts.account_escrow,
+            self.accounts.taker.address(),
+            &[Signer::from(&account_escrow_seeds)],
+        )?;
+        drop(data);
+
+        ProgramAccount::close(self.accounts.account_escrow, self.accounts.maker)
+    }
+}
@@ -0,0 +1,167 @@
+use pinocchio::{
+    AccountView, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+};
+
+use crate::helpers::*;
+
+pub struct TakeCollectionOfferAccounts<'a> {
+    pub taker: &'a AccountView,
+    pub maker: &'a AccountView,
+    pub offer: &'a AccountView,
+    pub nft_mint: &'a AccountView,
+    pub metadata: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub vault: &'a AccountView,
+    pub taker_nft_ata: &'a AccountView,
+    pub maker_nft_ata: &'a AccountView,
+    pub taker_ata_b: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for TakeCollectionOfferAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let taker = next_account(&mut iter)?;
+        let maker = next_account(&mut iter)?;
+        let offer = next_account(&mut iter)?;
+        let nft_mint = next_account(&mut iter)?;
+        let metadata = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let vault = next_account(&mut iter)?;
+        let taker_nft_ata = next_account(&mut iter)?;
+        let maker_nft_ata = next_account(&mut iter)?;
+        let taker_ata_b = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(taker)?;
+        CollectionOfferAccount::check(offer)?;
+        MintInterface::check(nft_mint)?;
+        MintInterface::check(mint_b)?;
+        AssociatedTokenAccount::check(vault, offer, mint_b, token_program)?;
+        OwnedTokenAccount::check(taker_nft_ata, taker, nft_mint)?;
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            taker,
+            maker,
+            offer,
+            nft_mint,
+            metadata,
+            mint_b,
+            vault,
+            taker_nft_ata,
+            maker_nft_ata,
+            taker_ata_b,
+            system_program,
+            token_program,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct TakeCollectionOffer<'a> {
+    pub accounts: TakeCollectionOfferAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for TakeCollectionOffer<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = TakeCollectionOfferAccounts::try_from(accounts)?;
+        init_destination_if_needed(
+            accounts.maker_nft_ata,
+            accounts.nft_mint,
+            accounts.taker,
+            accounts.maker,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        init_destination_if_needed(
+            accounts.taker_ata_b,
+            accounts.mint_b,
+            accounts.taker,
+            accounts.taker,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> TakeCollectionOffer<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &50;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.offer.try_borrow()?;
+        let offer = crate::state::CollectionOffer::load(&data)?;
+        if offer.maker.ne(self.accounts.maker.address())
+            || offer.mint_b.ne(self.accounts.mint_b.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if TokenInterface::get_decimals(self.accounts.nft_mint)? != 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if MetadataAccount::verified_collection(self.accounts.metadata, self.accounts.nft_mint)?
+            != Some(offer.collection)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if offer.has_attributes() {
+            let instructions = self
+                .accounts
+                .remaining_accounts
+                .first()
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            verify_ed25519_instruction(
+                instructions,
+                &offer.attester,
+                &offer.attestation(self.accounts.nft_mint.address()),
+            )?;
+        }
+
+        TokenInterface::transfer_checked(
+            self.accounts.taker_nft_ata,
+            self.accounts.nft_mint,
+            self.accounts.maker_nft_ata,
+            self.accounts.taker,
+            1,
+            &[],
+        )?;
+
+        let seed_binding = offer.seed.to_le_bytes();
+        let bump_binding = offer.bump;
+        let offer_seeds = [
+            Seed::from(crate::state::CollectionOffer::PREFIX),
+            Seed::from(offer.maker.as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&offer_seeds);
+        TokenInterface::transfer_checked(
+            self.accounts.vault,
+            self.accounts.mint_b,
+            self.accounts.taker_ata_b,
+            self.accounts.offer,
+            TokenInterface::get_amount(self.accounts.vault)?,
+            core::slice::from_ref(&signer),
+        )?;
+        TokenInterface::close_account(
+            self.accounts.vault,
+            self.accounts.maker,
+            self.accounts.offer,
+            core::slice::from_ref(&signer),
+        )?;
+        drop(data);
+
+        ProgramAccount::close(self.accounts.offer, self.accounts.maker)
+    }
+}
@@ -0,0 +1,141 @@
+use pinocchio::{AccountView, ProgramResult, error::ProgramError};
+
+use crate::{Take, TakeAccounts, TakeInstructionData, helpers::*};
+
+pub struct TakeManyInstructionData<'a> {
+    pub max_pay: u64,
+    pub group_lens: &'a [u8],
+}
+impl TakeManyInstructionData<'_> {
+    pub const MAX_GROUPS: usize = 8;
+    pub const MIN_GROUP_LEN: u8 = 4;
+}
+impl<'a> TryFrom<&'a [u8]> for TakeManyInstructionData<'a> {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (max_pay, group_lens) = data
+            .split_first_chunk::<8>()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        if group_lens.is_empty()
+            || group_lens.len() > Self::MAX_GROUPS
+            || group_lens.iter().any(|len| *len < Self::MIN_GROUP_LEN)
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self {
+            max_pay: u64::from_le_bytes(*max_pay),
+            group_lens,
+        })
+    }
+}
+
+pub struct TakeMany<'a> {
+    pub taker: &'a AccountView,
+    pub mint_a: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub taker_ata_a: &'a AccountView,
+    pub taker_ata_b: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub groups: &'a [AccountView],
+    pub instruction_data: TakeManyInstructionData<'a>,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TakeMany<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let instruction_data = TakeManyInstructionData::try_from(data)?;
+        let mut iter = accounts.iter();
+        let taker = next_account(&mut iter)?;
+        let mint_a = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let taker_ata_a = next_account(&mut iter)?;
+        let taker_ata_b = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let groups = iter.as_slice();
+        let expected = instruction_data
+            .group_lens
+            .iter()
+            .map(|len| *len as usize)
+            .sum::<usize>();
+        if groups.len() != expected {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        SignerAccount::check(taker)?;
+        MintInterface::check(mint_a)?;
+        MintInterface::check(mint_b)?;
+        SourceTokenAccount::check(taker_ata_b, taker, mint_b)?;
+        ConfigAccount::check(config)?;
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            taker,
+            mint_a,
+            mint_b,
+            taker_ata_a,
+            taker_ata_b,
+            system_program,
+            token_program,
+            config,
+            groups,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> TakeMany<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &34;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let balance = TokenInterface::get_amount(self.taker_ata_b)?;
+        let mut groups = self.groups;
+        for len in self.instruction_data.group_lens {
+            let (group, rest) = groups
+                .split_at_checked(*len as usize)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            groups = rest;
+            let [maker, escrow, vault, maker_ata_b, remaining_accounts @ ..] = group else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            let (remaining_accounts, event_accounts) =
+                crate::events::split_event_accounts(remaining_accounts)?;
+            let escrow = EscrowPda::check(escrow)?;
+            {
+                let data = escrow.try_borrow()?;
+                let state = crate::state::Escrow::load(&data)?;
+                if state.mint_a.ne(self.mint_a.address()) || state.mint_b.ne(self.mint_b.address())
+                {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+            }
+            let accounts = TakeAccounts {
+                taker: self.taker,
+                maker: MakerAccount::of_escrow(maker, escrow)?,
+                escrow,
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                vault: VaultAccount::check(vault, escrow, self.mint_a, self.token_program)?,
+                taker_ata_a: self.taker_ata_a,
+                taker_ata_b: self.taker_ata_b,
+                maker_ata_b,
+                system_program: self.system_program,
+                token_program: self.token_program,
+                config: self.config,
+                remaining_accounts,
+                event_accounts,
+            };
+            Take::new(accounts, TakeInstructionData::try_from(&[][..])?)?.process()?;
+        }
+        let paid = balance.saturating_sub(TokenInterface::get_amount(self.taker_ata_b)?);
+        if paid > self.instruction_data.max_pay {
+            return Err(ProgramError::InsufficientFunds);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,329 @@
+use pinocchio::{
+    AccountView, ProgramResult,
+    cpi::Signer,
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock, rent::Rent},
+};
+use pinocchio_system::create_account_with_minimum_balance_signed;
+
+use crate::{errors::EscrowError, helpers::*};
+
+pub struct TakeRollAccounts<'a> {
+    pub taker: &'a AccountView,
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub rolled_escrow: &'a AccountView,
+    pub mint_a: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub vault: &'a AccountView,
+    pub rolled_vault: &'a AccountView,
+    pub taker_ata_a: &'a AccountView,
+    pub taker_ata_b: &'a AccountView,
+    pub roll: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+    pub event_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for TakeRollAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let taker = next_account(&mut iter)?;
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let rolled_escrow = next_account(&mut iter)?;
+        let mint_a = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let vault = next_account(&mut iter)?;
+        let rolled_vault = next_account(&mut iter)?;
+        let taker_ata_a = next_account(&mut iter)?;
+        let taker_ata_b = next_account(&mut iter)?;
+        let roll = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let (remaining_accounts, event_accounts) =
+            crate::events::split_event_accounts(iter.as_slice())?;
+
+        SignerAccount::check(taker)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_a)?;
+        MintInterface::check(mint_b)?;
+        SourceTokenAccount::check(taker_ata_b, taker, mint_b)?;
+        EscrowVault::check(vault, escrow, mint_a, token_program)?;
+        ConfigAccount::check(config)?;
+        if !roll.owned_by(&crate::ID) || roll.data_len().ne(&crate::state::RollConfig::LEN) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            taker,
+            maker,
+            escrow,
+            rolled_escrow,
+            mint_a,
+            mint_b,
+            vault,
+            rolled_vault,
+            taker_ata_a,
+            taker_ata_b,
+            roll,
+            system_program,
+            token_program,
+            config,
+            remaining_accounts,
+            event_accounts,
+        })
+    }
+}
+
+pub struct TakeRoll<'a> {
+    pub accounts: TakeRollAccounts<'a>,
+    pub optional_accounts: &'a [AccountView],
+}
+impl<'a> TryFrom<&'a [AccountView]> for TakeRoll<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = TakeRollAccounts::try_from(accounts)?;
+        let mut optional_accounts = accounts.remaining_accounts.iter();
+        if DenylistAccount::is_enabled(accounts.config)? {
+            DenylistAccount::check_parties(
+                next_account(&mut optional_accounts)?,
+                &[accounts.taker.address(), accounts.maker.address()],
+            )?;
+        }
+        CallerPolicy::check(accounts.config, &mut optional_accounts)?;
+        let optional_accounts = optional_accounts.as_slice();
+        init_destination_if_needed(
+            accounts.taker_ata_a,
+            accounts.mint_a,
+            accounts.taker,
+            accounts.taker,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        Ok(Self {
+            accounts,
+            optional_accounts,
+        })
+    }
+}
+
+impl<'a> TakeRoll<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &29;
+    pub fn process(&mut self) -> ProgramResult {
+        let escrow = *crate::state::Escrow::load(&self.accounts.escrow.try_borrow()?)?;
+        if escrow.mint_a.ne(self.accounts.mint_a.address())
+            || escrow.mint_b.ne(self.accounts.mint_b.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        #[cfg(feature = "strict")]
+        MintPolicy::check_pair(self.accounts.mint_a, self.accounts.mint_b, escrow.flags)?;
+        MintPolicy::check_unpaused(self.accounts.mint_a, self.accounts.mint_b)?;
+        if escrow.is_auction()
+            || escrow.is_vesting()
+            || escrow.is_standing()
+            || escrow.has_royalty()
+            || escrow.is_conditional()
+            || escrow.has_value_floor()
+            || escrow.is_release_gated()
+            || escrow.has_allowlist()
+            || escrow.has_burn()
+            || escrow.has_split()
+            || escrow.has_cooldown()
+            || escrow.is_receive_in_ui()
+            || escrow.is_ratio_priced()
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.is_frozen() {
+            return Err(EscrowError::EscrowFrozen.into());
+        }
+        let clock = Clock::get()?;
+        if escrow.has_deadline() && escrow.is_expired(&clock) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let escrow_seeds = EscrowSeeds::from_escrow(&escrow);
+        if escrow_seeds.address()?.ne(self.accounts.escrow.address()) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if escrow.maker.ne(self.accounts.maker.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let rolled_receive = {
+            let data = self.accounts.roll.try_borrow()?;
+            let roll = crate::state::RollConfig::load(&data)?;
+            if roll.maker.ne(&escrow.maker)
+                || roll.mint_a.ne(&escrow.mint_a)
+                || roll.mint_b.ne(&escrow.mint_b)
+            {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            roll.rolled_receive(escrow.receive)
+                .ok_or(ProgramError::InvalidAccountData)?
+        };
+
+        let mut optional_accounts = self.optional_accounts.iter();
+        let treasury = if escrow.flags & crate::state::Escrow::RENT_TO_TREASURY != 0 {
+            Some(next_account(&mut optional_accounts)?)
+        } else {
+            None
+        };
+        let rent_destination = rent_destination(
+            &escrow,
+            self.accounts.config,
+            self.accounts.maker,
+            self.accounts.taker,
+            treasury,
+        )?;
+
+        let seeds = escrow_seeds.seeds();
+        let signer = Signer::from(&seeds);
+        let amount = TokenInterface::get_amount(self.accounts.vault)?;
+        TokenInterface::transfer_checked(
+            self.accounts.vault,
+            self.accounts.mint_a,
+            self.accounts.taker_ata_a,
+            self.accounts.escrow,
+            amount,
+            core::slice::from_ref(&signer),
+        )?;
+        TokenInterface::close_account(
+            self.accounts.vault,
+            rent_destination,
+            self.accounts.escrow,
+            core::slice::from_ref(&signer),
+        )?;
+
+        let rolled_seeds = match escrow.namespace {
+            crate::state::Escrow::NAMESPACE_PAIR => {
+                EscrowSeeds::find_pair(&escrow.creator, &escrow.mint_b, &escrow.mint_a)
+            }
+            crate::state::Escrow::NAMESPACE_NONCE => EscrowSeeds::find_nonce(
+                &escrow.creator,
+                escrow.seed ^ crate::state::RollConfig::SEED_BIT,
+            ),
+            _ => EscrowSeeds::find(
+                &escrow.creator,
+                escrow.seed ^ crate::state::RollConfig::SEED_BIT,
+            ),
+        };
+        if rolled_seeds
+            .address()?
+            .ne(self.accounts.rolled_escrow.address())
+        {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let rolled_bump = rolled_seeds.bump();
+        let rolled_signer_seeds = rolled_seeds.seeds();
+        let rent = Rent::get()?;
+        let escrow_lamports = self.accounts.escrow.lamports();
+        let escrow_rent = escrow_lamports.min(rent.try_minimum_balance(crate::state::Escrow::LEN)?);
+        let vault_rent = (escrow_lamports - escrow_rent)
+            .min(rent.try_minimum_balance(pinocchio_token::state::TokenAccount::LEN)?);
+        let rolled_escrow_lamports = self
+            .accounts
+            .rolled_escrow
+            .lamports()
+            .checked_add(escrow_rent)
+            .ok_or(EscrowError::LamportOverflow)?;
+        let rolled_vault_lamports = self
+            .accounts
+            .rolled_vault
+            .lamports()
+            .checked_add(vault_rent)
+            .ok_or(EscrowError::LamportOverflow)?;
+        self.accounts
+            .escrow
+            .set_lamports(escrow_lamports - escrow_rent - vault_rent);
+        self.accounts
+            .rolled_escrow
+            .set_lamports(rolled_escrow_lamports);
+        self.accounts
+            .rolled_vault
+            .set_lamports(rolled_vault_lamports);
+        create_account_with_minimum_balance_signed(
+            self.accounts.rolled_escrow,
+            crate::state::Escrow::LEN,
+            &crate::ID,
+            self.accounts.taker,
+            None,
+            &[Signer::from(&rolled_signer_seeds)],
+        )?;
+        AssociatedTokenAccount::init(
+            self.accounts.rolled_vault,
+            self.accounts.mint_b,
+            self.accounts.taker,
+            self.accounts.rolled_escrow,
+            self.accounts.system_program,
+            self.accounts.token_program,
+        )?;
+        TokenInterface::transfer_checked(
+            self.accounts.taker_ata_b,
+            self.accounts.mint_b,
+            self.accounts.rolled_vault,
+            self.accounts.taker,
+            escrow.receive,
+            &[],
+        )?;
+
+        crate::events::FillEvent {
+            escrow: *self.accounts.escrow.address(),
+            maker: escrow.maker,
+            taker: *self.accounts.taker.address(),
+            mint_a: escrow.mint_a,
+            mint_b: escrow.mint_b,
+            amount_a: amount,
+            amount_b: escrow.receive,
+            ui_amount_a: UiAmount::to_ui(
+                amount,
+                UiAmount::scale(self.accounts.mint_a, clock.unix_timestamp)?,
+            ),
+            ui_amount_b: UiAmount::to_ui(
+                escrow.receive,
+                UiAmount::scale(self.accounts.mint_b, clock.unix_timestamp)?,
+            ),
+            fills: escrow.fills,
+            label: escrow.label,
+        }
+        .emit_cpi(self.accounts.event_accounts)?;
+
+        {
+            let mut data = self.accounts.rolled_escrow.try_borrow_mut()?;
+            let rolled = crate::state::Escrow::load_mut(data.as_mut())?;
+            rolled.set_inner(
+                match escrow.namespace {
+                    crate::state::Escrow::NAMESPACE_PAIR => escrow.seed,
+                    _ => escrow.seed ^ crate::state::RollConfig::SEED_BIT,
+                },
+                escrow.maker,
+                escrow.mint_b,
+                escrow.mint_a,
+                rolled_receive,
+                [rolled_bump],
+            );
+            rolled.creator = escrow.creator;
+            rolled.set_namespace(escrow.namespace);
+            rolled.set_vault(*self.accounts.rolled_vault.address());
+            rolled.set_amount(escrow.receive);
+            rolled.set_flags(
+                escrow.flags
+                    & (crate::state::Escrow::ALLOW_PERMANENT_DELEGATE
+                        | crate::state::Escrow::ALLOW_MINT_CLOSE_AUTHORITY),
+            );
+            rolled.set_label(escrow.label);
+            rolled.set_indexed(escrow.is_indexed());
+            rolled.set_last_update_seq(next_seq(self.accounts.config)?);
+        }
+        ProgramAccount::close(self.accounts.escrow, self.accounts.taker)?;
+        Ok(())
+    }
+}
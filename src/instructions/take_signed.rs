@@ -0,0 +1,287 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::{errors::EscrowError, helpers::*};
+
+pub struct TakeSignedAccounts<'a> {
+    pub relayer: &'a AccountView,
+    pub taker: &'a AccountView,
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub mint_a: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub vault: &'a AccountView,
+    pub taker_ata_a: &'a AccountView,
+    pub taker_ata_b: &'a AccountView,
+    pub maker_ata_b: &'a AccountView,
+    pub delegate: &'a AccountView,
+    pub instructions: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+    pub event_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for TakeSignedAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let relayer = next_account(&mut iter)?;
+        let taker = next_account(&mut iter)?;
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let mint_a = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let vault = next_account(&mut iter)?;
+        let taker_ata_a = next_account(&mut iter)?;
+        let taker_ata_b = next_account(&mut iter)?;
+        let maker_ata_b = next_account(&mut iter)?;
+        let delegate = next_account(&mut iter)?;
+        let instructions = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let (remaining_accounts, event_accounts) =
+            crate::events::split_event_accounts(iter.as_slice())?;
+
+        SignerAccount::check(relayer)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_a)?;
+        MintInterface::check(mint_b)?;
+        OwnedTokenAccount::check(taker_ata_b, taker, mint_b)?;
+        EscrowVault::check(vault, escrow, mint_a, token_program)?;
+        ConfigAccount::check(config)?;
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            relayer,
+            taker,
+            maker,
+            escrow,
+            mint_a,
+            mint_b,
+            vault,
+            taker_ata_a,
+            taker_ata_b,
+            maker_ata_b,
+            delegate,
+            instructions,
+            system_program,
+            token_program,
+            config,
+            remaining_accounts,
+            event_accounts,
+        })
+    }
+}
+
+pub struct TakeSigned<'a> {
+    pub accounts: TakeSignedAccounts<'a>,
+    pub maker_index: Option<&'a AccountView>,
+    pub delegate_bump: u8,
+}
+impl<'a> TryFrom<&'a [AccountView]> for TakeSigned<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = TakeSignedAccounts::try_from(accounts)?;
+        let (delegate_key, delegate_bump) =
+            Address::find_program_address(&[Self::DELEGATE_SEED], &crate::ID);
+        if delegate_key.ne(accounts.delegate.address()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let mut optional_accounts = accounts.remaining_accounts.iter();
+        if DenylistAccount::is_enabled(accounts.config)? {
+            DenylistAccount::check_parties(
+                next_account(&mut optional_accounts)?,
+                &[accounts.taker.address(), accounts.maker.address()],
+            )?;
+        }
+        CallerPolicy::check(accounts.config, &mut optional_accounts)?;
+        let maker_index = optional_accounts.next();
+        init_destination_if_needed(
+            accounts.taker_ata_a,
+            accounts.mint_a,
+            accounts.relayer,
+            accounts.taker,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        Ok(Self {
+            accounts,
+            maker_index,
+            delegate_bump,
+        })
+    }
+}
+
+impl<'a> TakeSigned<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &16;
+    pub const DELEGATE_SEED: &'static [u8] = b"delegate";
+
+    pub fn message(escrow: &Address, last_update_seq: u64, amount: u64, receive: u64) -> [u8; 56] {
+        let mut message = [0u8; 56];
+        message[0..32].copy_from_slice(escrow.as_ref());
+        message[32..40].copy_from_slice(&last_update_seq.to_le_bytes());
+        message[40..48].copy_from_slice(&amount.to_le_bytes());
+        message[48..56].copy_from_slice(&receive.to_le_bytes());
+        message
+    }
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.escrow.try_borrow()?;
+        let escrow = crate::state::Escrow::load(&data)?;
+        if escrow.mint_a.ne(self.accounts.mint_a.address())
+            || escrow.mint_b.ne(self.accounts.mint_b.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        #[cfg(feature = "strict")]
+        MintPolicy::check_pair(self.accounts.mint_a, self.accounts.mint_b, escrow.flags)?;
+        MintPolicy::check_unpaused(self.accounts.mint_a, self.accounts.mint_b)?;
+        if escrow.is_auction()
+            || escrow.is_vesting()
+            || escrow.has_royalty()
+            || escrow.is_conditional()
+            || escrow.has_value_floor()
+            || escrow.is_release_gated()
+            || escrow.has_allowlist()
+            || escrow.has_burn()
+            || escrow.has_split()
+            || escrow.has_cooldown()
+            || escrow.is_tracked()
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.is_frozen() {
+            return Err(EscrowError::EscrowFrozen.into());
+        }
+        let clock = Clock::get()?;
+        if escrow.has_deadline() && escrow.is_expired(&clock) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let escrow_seeds = EscrowSeeds::from_escrow(escrow);
+        if escrow_seeds.address()?.ne(self.accounts.escrow.address()) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if escrow.maker.ne(self.accounts.maker.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let seeds = escrow_seeds.seeds();
+        let signer = Signer::from(&seeds);
+        let vault_amount = TokenInterface::get_amount(self.accounts.vault)?;
+        let standing = escrow.is_standing();
+        let amount = if standing {
+            if vault_amount < escrow.amount {
+                return Err(ProgramError::InsufficientFunds);
+            }
+            escrow.amount
+        } else {
+            vault_amount
+        };
+        let scale_a = UiAmount::scale(self.accounts.mint_a, clock.unix_timestamp)?;
+        let scale_b = UiAmount::scale(self.accounts.mint_b, clock.unix_timestamp)?;
+        let quoted = if escrow.is_ratio_priced() {
+            escrow
+                .ratio_quote(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?
+        } else {
+            escrow.receive
+        };
+        let receive = if escrow.is_receive_in_ui() {
+            UiAmount::from_ui(quoted, scale_b)?
+        } else {
+            quoted
+        };
+        verify_ed25519_instruction(
+            self.accounts.instructions,
+            self.accounts.taker.address(),
+            &Self::message(
+                self.accounts.escrow.address(),
+                escrow.last_update_seq,
+                amount,
+                receive,
+            ),
+        )?;
+
+        TokenInterface::transfer_checked(
+            self.accounts.vault,
+            self.accounts.mint_a,
+            self.accounts.taker_ata_a,
+            self.accounts.escrow,
+            amount,
+            core::slice::from_ref(&signer),
+        )?;
+        if !standing {
+            TokenInterface::close_account(
+                self.accounts.vault,
+                self.accounts.maker,
+                self.accounts.escrow,
+                core::slice::from_ref(&signer),
+            )?;
+        }
+        init_proceeds_if_needed(
+            escrow,
+            self.accounts.maker_ata_b,
+            self.accounts.mint_b,
+            self.accounts.relayer,
+            self.accounts.maker,
+            self.accounts.system_program,
+            self.accounts.token_program,
+        )?;
+        let delegate_bump_binding = [self.delegate_bump];
+        let delegate_seeds = [
+            Seed::from(Self::DELEGATE_SEED),
+            Seed::from(&delegate_bump_binding),
+        ];
+        TokenInterface::transfer_checked(
+            self.accounts.taker_ata_b,
+            self.accounts.mint_b,
+            self.accounts.maker_ata_b,
+            self.accounts.delegate,
+            receive,
+            &[Signer::from(&delegate_seeds)],
+        )?;
+
+        let fills = escrow.fills;
+        crate::events::FillEvent {
+            escrow: *self.accounts.escrow.address(),
+            maker: escrow.maker,
+            taker: *self.accounts.taker.address(),
+            mint_a: escrow.mint_a,
+            mint_b: escrow.mint_b,
+            amount_a: amount,
+            amount_b: receive,
+            ui_amount_a: UiAmount::to_ui(amount, scale_a),
+            ui_amount_b: UiAmount::to_ui(receive, scale_b),
+            fills,
+            label: escrow.label,
+        }
+        .emit_cpi(self.accounts.event_accounts)?;
+        GroupMemberMint::emit(self.accounts.escrow.address(), self.accounts.mint_a)?;
+        GroupMemberMint::emit(self.accounts.escrow.address(), self.accounts.mint_b)?;
+
+        if !standing {
+            MakerIndexAccount::release(escrow, self.maker_index)?;
+        }
+        drop(data);
+        if standing {
+            let mut data = self.accounts.escrow.try_borrow_mut()?;
+            let escrow = crate::state::Escrow::load_mut(data.as_mut())?;
+            escrow.set_fills(fills + 1);
+            escrow.set_last_update_seq(next_seq(self.accounts.config)?);
+        } else {
+            next_seq(self.accounts.config)?;
+            ProgramAccount::close(self.accounts.escrow, self.accounts.taker)?;
+        }
+        Ok(())
+    }
+}
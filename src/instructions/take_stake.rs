@@ -0,0 +1,125 @@
+use pinocchio::{
+    AccountView, ProgramResult,
+    cpi::{Seed, Signer},
+    error::ProgramError,
+};
+
+use crate::helpers::*;
+
+pub struct TakeStakeAccounts<'a> {
+    pub taker: &'a AccountView,
+    pub maker: &'a AccountView,
+    pub stake_escrow: &'a AccountView,
+    pub stake: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub taker_ata_b: &'a AccountView,
+    pub maker_ata_b: &'a AccountView,
+    pub clock: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for TakeStakeAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let taker = next_account(&mut iter)?;
+        let maker = next_account(&mut iter)?;
+        let stake_escrow = next_account(&mut iter)?;
+        let stake = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let taker_ata_b = next_account(&mut iter)?;
+        let maker_ata_b = next_account(&mut iter)?;
+        let clock = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let stake_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(taker)?;
+        StakeEscrowAccount::check(stake_escrow)?;
+        MintInterface::check(mint_b)?;
+        TokenAccountInterface::check(taker_ata_b)?;
+        ClockSysvarAccount::check(clock)?;
+        SystemProgramAccount::check(system_program)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+        StakeProgramAccount::check(stake_program)?;
+
+        Ok(Self {
+            taker,
+            maker,
+            stake_escrow,
+            stake,
+            mint_b,
+            taker_ata_b,
+            maker_ata_b,
+            clock,
+            system_program,
+            token_program,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct TakeStake<'a> {
+    pub accounts: TakeStakeAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for TakeStake<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = TakeStakeAccounts::try_from(accounts)?;
+        init_destination_if_needed(
+            accounts.maker_ata_b,
+            accounts.mint_b,
+            accounts.taker,
+            accounts.maker,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> TakeStake<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &44;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.stake_escrow.try_borrow()?;
+        let stake_escrow = crate::state::StakeEscrow::load(&data)?;
+        if stake_escrow.maker.ne(self.accounts.maker.address())
+            || stake_escrow.stake.ne(self.accounts.stake.address())
+            || stake_escrow.mint_b.ne(self.accounts.mint_b.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        TokenInterface::transfer_checked(
+            self.accounts.taker_ata_b,
+            self.accounts.mint_b,
+            self.accounts.maker_ata_b,
+            self.accounts.taker,
+            stake_escrow.receive,
+            &[],
+        )?;
+
+        let bump_binding = stake_escrow.bump;
+        let stake_escrow_seeds = [
+            Seed::from(crate::state::StakeEscrow::PREFIX),
+            Seed::from(self.accounts.stake.address().as_ref()),
+            Seed::from(&bump_binding),
+        ];
+        StakeAccount::authorize(
+            self.accounts.stake,
+            self.accounts.clock,
+            self.accounts.stake_escrow,
+            self.accounts.taker.address(),
+            &[Signer::from(&stake_escrow_seeds)],
+        )?;
+        drop(data);
+
+        ProgramAccount::close(self.accounts.stake_escrow, self.accounts.maker)
+    }
+}
@@ -0,0 +1,116 @@
+use pinocchio::{AccountView, ProgramResult, error::ProgramError};
+
+use crate::helpers::*;
+
+pub struct TopUpAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub mint_a: &'a AccountView,
+    pub maker_ata_a: &'a AccountView,
+    pub vault: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for TopUpAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let mint_a = next_account(&mut iter)?;
+        let maker_ata_a = next_account(&mut iter)?;
+        let vault = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_a)?;
+        AssociatedTokenAccount::check(maker_ata_a, maker, mint_a, token_program)?;
+        EscrowVault::check(vault, escrow, mint_a, token_program)?;
+        ConfigAccount::check(config)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            maker,
+            escrow,
+            mint_a,
+            maker_ata_a,
+            vault,
+            token_program,
+            config,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct TopUpInstructionData {
+    pub amount: u64,
+}
+impl<'a> TryFrom<&'a [u8]> for TopUpInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let amount = u64::from_le_bytes(instruction_bytes(data, 0)?);
+        if amount == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self { amount })
+    }
+}
+
+pub struct TopUp<'a> {
+    pub accounts: TopUpAccounts<'a>,
+    pub instruction_data: TopUpInstructionData,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TopUp<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = TopUpAccounts::try_from(accounts)?;
+        let instruction_data = TopUpInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> TopUp<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &9;
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.escrow.try_borrow()?;
+        let escrow = crate::state::Escrow::load(&data)?;
+
+        if !escrow.is_standing() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.maker.ne(self.accounts.maker.address())
+            || escrow.mint_a.ne(self.accounts.mint_a.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        TokenInterface::transfer_checked(
+            self.accounts.maker_ata_a,
+            self.accounts.mint_a,
+            self.accounts.vault,
+            self.accounts.maker,
+            self.instruction_data.amount,
+            &[],
+        )?;
+
+        drop(data);
+        let mut data = self.accounts.escrow.try_borrow_mut()?;
+        crate::state::Escrow::load_mut(data.as_mut())?
+            .set_last_update_seq(next_seq(self.accounts.config)?);
+        Ok(())
+    }
+}
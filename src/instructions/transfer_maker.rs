@@ -0,0 +1,92 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::helpers::*;
+
+pub struct TransferMakerAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub new_maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub mint_b: &'a AccountView,
+    pub config: &'a AccountView,
+    pub proceeds: Option<&'a AccountView>,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for TransferMakerAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let new_maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let mint_b = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_b)?;
+        ConfigAccount::check(config)?;
+
+        let proceeds = remaining_accounts.first();
+        if let Some(proceeds) = proceeds {
+            OwnedTokenAccount::check(proceeds, new_maker, mint_b)?;
+        }
+
+        Ok(Self {
+            maker,
+            new_maker,
+            escrow,
+            mint_b,
+            config,
+            proceeds,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct TransferMaker<'a> {
+    pub accounts: TransferMakerAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountView]> for TransferMaker<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let accounts = TransferMakerAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> TransferMaker<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &17;
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.escrow.try_borrow_mut()?;
+        let escrow = crate::state::Escrow::load_mut(data.as_mut())?;
+
+        if escrow.maker.ne(self.accounts.maker.address())
+            || escrow.mint_b.ne(self.accounts.mint_b.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.is_proceeds_locked() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if self
+            .accounts
+            .new_maker
+            .address()
+            .eq(self.accounts.maker.address())
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        escrow.set_maker(*self.accounts.new_maker.address());
+        escrow.set_proceeds(
+            self.accounts
+                .proceeds
+                .map_or(Address::default(), |proceeds| *proceeds.address()),
+        );
+        escrow.set_last_update_seq(next_seq(self.accounts.config)?);
+        Ok(())
+    }
+}
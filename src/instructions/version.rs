@@ -0,0 +1,32 @@
+use pinocchio::{AccountView, ProgramResult, cpi::set_return_data, error::ProgramError};
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const GIT_HASH: &str = env!("ESCROW_GIT_HASH");
+pub const VERSION_VIEW_LEN: usize = VERSION.len() + 1 + GIT_HASH.len();
+
+pub fn serialize_version() -> [u8; VERSION_VIEW_LEN] {
+    let mut view = [0u8; VERSION_VIEW_LEN];
+    view[..VERSION.len()].copy_from_slice(VERSION.as_bytes());
+    view[VERSION.len() + 1..].copy_from_slice(GIT_HASH.as_bytes());
+    view
+}
+
+pub struct Version<'a> {
+    pub remaining_accounts: &'a [AccountView],
+}
+impl<'a> TryFrom<&'a [AccountView]> for Version<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            remaining_accounts: accounts,
+        })
+    }
+}
+
+impl<'a> Version<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &20;
+    pub fn process(&mut self) -> ProgramResult {
+        set_return_data(&serialize_version());
+        Ok(())
+    }
+}
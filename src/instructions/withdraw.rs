@@ -0,0 +1,144 @@
+use pinocchio::{AccountView, ProgramResult, cpi::Signer, error::ProgramError};
+
+use crate::helpers::*;
+
+pub struct WithdrawAccounts<'a> {
+    pub maker: &'a AccountView,
+    pub escrow: &'a AccountView,
+    pub mint_a: &'a AccountView,
+    pub maker_ata_a: &'a AccountView,
+    pub vault: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub config: &'a AccountView,
+    pub remaining_accounts: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for WithdrawAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let mut iter = accounts.iter();
+        let maker = next_account(&mut iter)?;
+        let escrow = next_account(&mut iter)?;
+        let mint_a = next_account(&mut iter)?;
+        let maker_ata_a = next_account(&mut iter)?;
+        let vault = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let config = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let remaining_accounts = iter.as_slice();
+
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_a)?;
+        OwnedTokenAccount::check(maker_ata_a, maker, mint_a)?;
+        EscrowVault::check(vault, escrow, mint_a, token_program)?;
+        ConfigAccount::check(config)?;
+        TokenProgramAccount::check(token_program)?;
+        AssociatedTokenProgramAccount::check(associated_token_program)?;
+
+        Ok(Self {
+            maker,
+            escrow,
+            mint_a,
+            maker_ata_a,
+            vault,
+            token_program,
+            config,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct WithdrawInstructionData {
+    pub amount: u64,
+    pub receive: u64,
+}
+impl<'a> TryFrom<&'a [u8]> for WithdrawInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u64>() * 2 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let amount = u64::from_le_bytes(instruction_bytes(data, 0)?);
+        let receive = u64::from_le_bytes(instruction_bytes(data, 8)?);
+        if amount == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self { amount, receive })
+    }
+}
+
+pub struct Withdraw<'a> {
+    pub accounts: WithdrawAccounts<'a>,
+    pub instruction_data: WithdrawInstructionData,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Withdraw<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+        let accounts = WithdrawAccounts::try_from(accounts)?;
+        let instruction_data = WithdrawInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> Withdraw<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &13;
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.escrow.try_borrow()?;
+        let escrow = crate::state::Escrow::load(&data)?;
+
+        if escrow.is_auction() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.is_vesting() && escrow.vesting_start != 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.maker.ne(self.accounts.maker.address())
+            || escrow.mint_a.ne(self.accounts.mint_a.address())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let vault_amount = TokenInterface::get_amount(self.accounts.vault)?;
+        let withdrawn = self.instruction_data.amount;
+        if withdrawn >= vault_amount {
+            return Err(ProgramError::InsufficientFunds);
+        }
+        let remaining = vault_amount - withdrawn;
+        let standing = escrow.is_standing();
+        let receive = match self.instruction_data.receive {
+            0 if standing || escrow.is_ratio_priced() => escrow.receive,
+            0 => (escrow.receive as u128 * remaining as u128 / vault_amount as u128) as u64,
+            receive => receive,
+        };
+        if receive == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let escrow_seeds = EscrowSeeds::from_escrow(escrow);
+        let seeds = escrow_seeds.seeds();
+        let signer = Signer::from(&seeds);
+        TokenInterface::transfer_checked(
+            self.accounts.vault,
+            self.accounts.mint_a,
+            self.accounts.maker_ata_a,
+            self.accounts.escrow,
+            withdrawn,
+            core::slice::from_ref(&signer),
+        )?;
+
+        drop(data);
+        let mut data = self.accounts.escrow.try_borrow_mut()?;
+        let escrow = crate::state::Escrow::load_mut(data.as_mut())?;
+        escrow.set_receive(receive);
+        if !standing && !escrow.is_ratio_priced() {
+            escrow.set_amount(remaining);
+        }
+        escrow.set_last_update_seq(next_seq(self.accounts.config)?);
+        Ok(())
+    }
+}
@@ -1,28 +1,175 @@
 #![no_std]
-use pinocchio::{
-    AccountView, Address, ProgramResult, entrypoint, error::ProgramError, nostd_panic_handler,
-};
+#[cfg(feature = "no-entrypoint")]
+extern crate std;
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
 
-mod helpers;
+#[cfg(feature = "borsh")]
+pub mod borsh;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod cpi;
+pub mod errors;
+pub mod events;
+pub mod helpers;
 mod instructions;
-mod state;
+#[cfg(feature = "lib")]
+pub mod processor;
+pub mod state;
 pub use instructions::*;
 
-entrypoint!(process_instruction);
-nostd_panic_handler!();
+#[cfg(not(feature = "no-entrypoint"))]
+pinocchio::program_entrypoint!(process_instruction);
+#[cfg(all(not(feature = "no-entrypoint"), feature = "alloc"))]
+pinocchio::default_allocator!();
+#[cfg(all(not(feature = "no-entrypoint"), not(feature = "alloc")))]
+pinocchio::no_allocator!();
+#[cfg(not(feature = "no-entrypoint"))]
+pinocchio::nostd_panic_handler!();
+
+#[cfg(not(feature = "no-entrypoint"))]
+#[unsafe(no_mangle)]
+#[unsafe(link_section = ".security.txt")]
+#[allow(dead_code, non_upper_case_globals)]
+pub static security_txt: &str = concat!(
+    "=======BEGIN SECURITY.TXT V1=======\0",
+    "name\0",
+    "Blueshift Escrow\0",
+    "project_url\0",
+    "https://github.com/kervinct/blueshift-escrow\0",
+    "contacts\0",
+    "link:https://github.com/kervinct/blueshift-escrow/security/advisories/new\0",
+    "policy\0",
+    "https://github.com/kervinct/blueshift-escrow/security/policy\0",
+    "source_code\0",
+    "https://github.com/kervinct/blueshift-escrow\0",
+    "source_revision\0",
+    env!("ESCROW_GIT_HASH"),
+    "\0",
+    "=======END SECURITY.TXT V1=======\0"
+);
 
-pub const ID: Address =
-    pinocchio::address::address!("22222222222222222222222222222222222222222222");
+pub const ID: Address = match option_env!("ESCROW_PROGRAM_ID") {
+    Some(id) => Address::from_str_const(id),
+    None => pinocchio::address::address!("22222222222222222222222222222222222222222222"),
+};
 
-fn process_instruction(
+pub fn process_instruction(
     _program_id: &Address,
     accounts: &[AccountView],
     instruction_data: &[u8],
 ) -> ProgramResult {
+    #[cfg(feature = "borsh")]
+    if let Some(result) = borsh::process_instruction(accounts, instruction_data) {
+        return result;
+    }
     match instruction_data.split_first() {
         Some((Make::DISCRIMINATOR, data)) => Make::try_from((data, accounts))?.process(),
-        Some((Take::DISCRIMINATOR, _)) => Take::try_from(accounts)?.process(),
-        Some((Refund::DISCRIMINATOR, _)) => Refund::try_from(accounts)?.process(),
+        Some((Make::PAIR_DISCRIMINATOR, data)) => {
+            Make::new(data, accounts, state::Escrow::NAMESPACE_PAIR)?.process()
+        }
+        Some((Make::NONCE_DISCRIMINATOR, data)) => {
+            Make::new(data, accounts, state::Escrow::NAMESPACE_NONCE)?.process()
+        }
+        Some((Take::DISCRIMINATOR, data)) => Take::try_from((data, accounts))?.process(),
+        Some((Refund::DISCRIMINATOR, data)) => Refund::try_from((data, accounts))?.process(),
+        #[cfg(feature = "auction")]
+        Some((Bid::DISCRIMINATOR, data)) => Bid::try_from((data, accounts))?.process(),
+        #[cfg(feature = "auction")]
+        Some((OutbidRefund::DISCRIMINATOR, _)) => OutbidRefund::try_from(accounts)?.process(),
+        #[cfg(feature = "auction")]
+        Some((Settle::DISCRIMINATOR, _)) => Settle::try_from(accounts)?.process(),
+        Some((Counter::DISCRIMINATOR, data)) => Counter::try_from((data, accounts))?.process(),
+        Some((AcceptCounter::DISCRIMINATOR, _)) => AcceptCounter::try_from(accounts)?.process(),
+        Some((CancelCounter::DISCRIMINATOR, _)) => CancelCounter::try_from(accounts)?.process(),
+        Some((TopUp::DISCRIMINATOR, data)) => TopUp::try_from((data, accounts))?.process(),
+        Some((Claim::DISCRIMINATOR, _)) => Claim::try_from(accounts)?.process(),
+        Some((InitializeConfig::DISCRIMINATOR, data)) => {
+            InitializeConfig::try_from((data, accounts))?.process()
+        }
+        Some((Migrate::DISCRIMINATOR, _)) => Migrate::try_from(accounts)?.process(),
+        Some((Withdraw::DISCRIMINATOR, data)) => Withdraw::try_from((data, accounts))?.process(),
+        Some((CloseExpired::DISCRIMINATOR, _)) => CloseExpired::try_from(accounts)?.process(),
+        Some((GetEscrow::DISCRIMINATOR, _)) => GetEscrow::try_from(accounts)?.process(),
+        Some((TakeSigned::DISCRIMINATOR, _)) => TakeSigned::try_from(accounts)?.process(),
+        Some((TransferMaker::DISCRIMINATOR, _)) => TransferMaker::try_from(accounts)?.process(),
+        Some((Version::DISCRIMINATOR, _)) => Version::try_from(accounts)?.process(),
+        Some((Freeze::DISCRIMINATOR, data)) => Freeze::try_from((data, accounts))?.process(),
+        Some((SetCondition::DISCRIMINATOR, data)) => {
+            SetCondition::try_from((data, accounts))?.process()
+        }
+        Some((SetAllowlist::DISCRIMINATOR, _)) => SetAllowlist::try_from(accounts)?.process(),
+        Some((AddToDenylist::DISCRIMINATOR, data)) => {
+            AddToDenylist::try_from((data, accounts))?.process()
+        }
+        Some((RemoveFromDenylist::DISCRIMINATOR, data)) => {
+            RemoveFromDenylist::try_from((data, accounts))?.process()
+        }
+        Some((AddToMintList::DISCRIMINATOR, data)) => {
+            AddToMintList::try_from((data, accounts))?.process()
+        }
+        Some((RemoveFromMintList::DISCRIMINATOR, data)) => {
+            RemoveFromMintList::try_from((data, accounts))?.process()
+        }
+        Some((SetRoll::DISCRIMINATOR, data)) => SetRoll::try_from((data, accounts))?.process(),
+        Some((TakeRoll::DISCRIMINATOR, _)) => TakeRoll::try_from(accounts)?.process(),
+        Some((LockProceeds::DISCRIMINATOR, data)) => {
+            LockProceeds::try_from((data, accounts))?.process()
+        }
+        Some((ClaimProceeds::DISCRIMINATOR, _)) => ClaimProceeds::try_from(accounts)?.process(),
+        Some((InitializeStats::DISCRIMINATOR, _)) => InitializeStats::try_from(accounts)?.process(),
+        Some((MakeMany::DISCRIMINATOR, data)) => MakeMany::try_from((data, accounts))?.process(),
+        Some((TakeMany::DISCRIMINATOR, data)) => TakeMany::try_from((data, accounts))?.process(),
+        Some((Remake::DISCRIMINATOR, data)) => Remake::try_from((data, accounts))?.process(),
+        Some((AutoRefund::DISCRIMINATOR, _)) => AutoRefund::try_from(accounts)?.process(),
+        Some((SetCallerPolicy::DISCRIMINATOR, data)) => {
+            SetCallerPolicy::try_from((data, accounts))?.process()
+        }
+        Some((AddToRouterList::DISCRIMINATOR, data)) => {
+            AddToRouterList::try_from((data, accounts))?.process()
+        }
+        Some((RemoveFromRouterList::DISCRIMINATOR, data)) => {
+            RemoveFromRouterList::try_from((data, accounts))?.process()
+        }
+        Some((FillSignedOrder::DISCRIMINATOR, data)) => {
+            FillSignedOrder::try_from((data, accounts))?.process()
+        }
+        Some((CancelOrderNonce::DISCRIMINATOR, data)) => {
+            CancelOrderNonce::try_from((data, accounts))?.process()
+        }
+        Some((EmitEvent::DISCRIMINATOR, _)) => EmitEvent::try_from(accounts)?.process(),
+        Some((MakeStake::DISCRIMINATOR, data)) => MakeStake::try_from((data, accounts))?.process(),
+        Some((TakeStake::DISCRIMINATOR, _)) => TakeStake::try_from(accounts)?.process(),
+        Some((RefundStake::DISCRIMINATOR, _)) => RefundStake::try_from(accounts)?.process(),
+        Some((MakeAccountEscrow::DISCRIMINATOR, data)) => {
+            MakeAccountEscrow::try_from((data, accounts))?.process()
+        }
+        Some((TakeAccountEscrow::DISCRIMINATOR, _)) => {
+            TakeAccountEscrow::try_from(accounts)?.process()
+        }
+        Some((RefundAccountEscrow::DISCRIMINATOR, _)) => {
+            RefundAccountEscrow::try_from(accounts)?.process()
+        }
+        #[cfg(feature = "nft")]
+        Some((MakeCollectionOffer::DISCRIMINATOR, data)) => {
+            MakeCollectionOffer::try_from((data, accounts))?.process()
+        }
+        #[cfg(feature = "nft")]
+        Some((TakeCollectionOffer::DISCRIMINATOR, _)) => {
+            TakeCollectionOffer::try_from(accounts)?.process()
+        }
+        #[cfg(feature = "nft")]
+        Some((RefundCollectionOffer::DISCRIMINATOR, _)) => {
+            RefundCollectionOffer::try_from(accounts)?.process()
+        }
+        Some((SetValueFloor::DISCRIMINATOR, data)) => {
+            SetValueFloor::try_from((data, accounts))?.process()
+        }
+        #[cfg(feature = "dispute")]
+        Some((SetReleaseSigners::DISCRIMINATOR, data)) => {
+            SetReleaseSigners::try_from((data, accounts))?.process()
+        }
+        #[cfg(feature = "dispute")]
+        Some((ApproveRelease::DISCRIMINATOR, _)) => ApproveRelease::try_from(accounts)?.process(),
         _ => Err(ProgramError::InvalidInstructionData),
     }
 }
@@ -0,0 +1,32 @@
+use pinocchio::ProgramResult;
+
+use crate::{
+    Make, MakeAccounts, MakeInstructionData, Refund, RefundAccounts, RefundInstructionData, Take,
+    TakeAccounts, TakeInstructionData,
+};
+
+pub fn process_make(
+    accounts: MakeAccounts,
+    instruction_data: MakeInstructionData,
+) -> ProgramResult {
+    Make::open(
+        accounts,
+        instruction_data,
+        crate::state::Escrow::NAMESPACE_SEED,
+    )?
+    .process()
+}
+
+pub fn process_take(
+    accounts: TakeAccounts,
+    instruction_data: TakeInstructionData,
+) -> ProgramResult {
+    Take::new(accounts, instruction_data)?.process()
+}
+
+pub fn process_refund(
+    accounts: RefundAccounts,
+    instruction_data: RefundInstructionData,
+) -> ProgramResult {
+    Refund::new(accounts, instruction_data)?.process()
+}
@@ -1,35 +1,111 @@
-use pinocchio::{Address, error::ProgramError};
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{Address, error::ProgramError, sysvars::clock::Clock};
+
+use crate::helpers::CLOSED_ACCOUNT_DISCRIMINATOR;
+
+#[inline(always)]
+fn cast<T: Pod>(bytes: &[u8]) -> Result<&T, ProgramError> {
+    bytemuck::try_from_bytes(bytes).map_err(|_| ProgramError::InvalidAccountData)
+}
+#[inline(always)]
+fn cast_mut<T: Pod>(bytes: &mut [u8]) -> Result<&mut T, ProgramError> {
+    bytemuck::try_from_bytes_mut(bytes).map_err(|_| ProgramError::InvalidAccountData)
+}
 
 #[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
 pub struct Escrow {
+    pub discriminator: u8,
+    pub version: u8,
+    pub receive_in_ui: u8,
+    pub splits: u8,
+    pub burn_bps: u16,
+    pub proceeds_locked: u8,
+    pub tracked: u8,
     pub seed: u64,
     pub maker: Address,
+    pub creator: Address,
     pub mint_a: Address,
     pub mint_b: Address,
     pub receive: u64,
+    pub amount: u64,
+    pub deadline: i64,
+    pub highest_bid: u64,
+    pub highest_bidder: Address,
+    pub taker: Address,
+    pub vesting_start: i64,
+    pub vesting_cliff: i64,
+    pub vesting_duration: i64,
+    pub claimed: u64,
+    pub fills: u64,
+    pub min_fill: u64,
+    pub max_fill_per_tx: u64,
+    pub cooldown_slots: u64,
+    pub last_update_seq: u64,
+    pub proceeds: Address,
+    pub vault: Address,
+    pub allowlist: Address,
+    pub label: [u8; 32],
     pub bump: [u8; 1],
+    pub flags: u8,
+    pub deadline_kind: u8,
+    pub namespace: u8,
+    pub frozen: u8,
+    pub overpay_mode: u8,
+    pub conditional: u8,
+    pub indexed: u8,
+    pub category: u16,
+    pub tag_flags: u16,
+    pub price_mode: u8,
+    pub value_floor: u8,
+    pub release_gated: u8,
+    pub _reserved: [u8; 1],
 }
 
 impl Escrow {
-    pub const LEN: usize = size_of::<u64>()
-        + size_of::<Address>()
-        + size_of::<Address>()
-        + size_of::<Address>()
-        + size_of::<u64>()
-        + size_of::<[u8; 1]>();
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const VERSION: u8 = 2;
+    pub const LEN: usize = size_of::<Self>();
+
+    pub const ALLOW_PERMANENT_DELEGATE: u8 = 1 << 0;
+    pub const ALLOW_MINT_CLOSE_AUTHORITY: u8 = 1 << 1;
+    pub const AUCTION: u8 = 1 << 2;
+    pub const STANDING: u8 = 1 << 3;
+    pub const VESTING: u8 = 1 << 4;
+    pub const RENT_TO_TAKER: u8 = 1 << 5;
+    pub const RENT_TO_TREASURY: u8 = 1 << 6;
+    pub const ROYALTY: u8 = 1 << 7;
+
+    pub const DEADLINE_UNIX_TIMESTAMP: u8 = 0;
+    pub const DEADLINE_SLOT: u8 = 1;
+
+    pub const NAMESPACE_SEED: u8 = 0;
+    pub const NAMESPACE_PAIR: u8 = 1;
+    pub const NAMESPACE_NONCE: u8 = 2;
+
+    pub const OVERPAY_REJECT: u8 = 0;
+    pub const OVERPAY_REFUND: u8 = 1;
+    pub const OVERPAY_TO_MAKER: u8 = 2;
+
+    pub const PRICE_ABSOLUTE: u8 = 0;
+    pub const PRICE_RATIO_ROUND_UP: u8 = 1;
+    pub const PRICE_RATIO_ROUND_DOWN: u8 = 2;
+
+    pub const MAX_BURN_BPS: u16 = 10_000;
+
     #[inline(always)]
     pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
-        if bytes.len() != Self::LEN {
+        if bytes.first() == Some(&CLOSED_ACCOUNT_DISCRIMINATOR) {
             return Err(ProgramError::InvalidAccountData);
         }
-        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+        cast_mut(bytes)
     }
     #[inline(always)]
     pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
-        if bytes.len() != Self::LEN {
+        if bytes.first() == Some(&CLOSED_ACCOUNT_DISCRIMINATOR) {
             return Err(ProgramError::InvalidAccountData);
         }
-        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+        cast(bytes)
     }
     #[inline(always)]
     pub fn set_seed(&mut self, seed: u64) {
@@ -52,10 +128,281 @@ impl Escrow {
         self.receive = receive;
     }
     #[inline(always)]
+    pub fn set_amount(&mut self, amount: u64) {
+        self.amount = amount;
+    }
+    #[inline(always)]
+    pub fn set_deadline(&mut self, deadline: i64) {
+        self.deadline = deadline;
+    }
+    #[inline(always)]
+    pub fn set_highest_bid(&mut self, highest_bid: u64, highest_bidder: Address) {
+        self.highest_bid = highest_bid;
+        self.highest_bidder = highest_bidder;
+    }
+    #[inline(always)]
+    pub fn set_deadline_kind(&mut self, deadline_kind: u8) {
+        self.deadline_kind = deadline_kind;
+    }
+    #[inline(always)]
+    pub fn set_namespace(&mut self, namespace: u8) {
+        self.namespace = namespace;
+    }
+    #[inline(always)]
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.frozen = frozen as u8;
+    }
+    #[inline(always)]
+    pub fn is_frozen(&self) -> bool {
+        self.frozen != 0
+    }
+    #[inline(always)]
+    pub fn set_conditional(&mut self, conditional: bool) {
+        self.conditional = conditional as u8;
+    }
+    #[inline(always)]
+    pub fn is_conditional(&self) -> bool {
+        self.conditional != 0
+    }
+    #[inline(always)]
+    pub fn set_value_floor(&mut self, value_floor: bool) {
+        self.value_floor = value_floor as u8;
+    }
+    #[inline(always)]
+    pub fn has_value_floor(&self) -> bool {
+        self.value_floor != 0
+    }
+    #[inline(always)]
+    pub fn set_release_gated(&mut self, release_gated: bool) {
+        self.release_gated = release_gated as u8;
+    }
+    #[inline(always)]
+    pub fn is_release_gated(&self) -> bool {
+        self.release_gated != 0
+    }
+    #[inline(always)]
+    pub fn set_indexed(&mut self, indexed: bool) {
+        self.indexed = indexed as u8;
+    }
+    #[inline(always)]
+    pub fn is_indexed(&self) -> bool {
+        self.indexed != 0
+    }
+    #[inline(always)]
+    pub fn set_tags(&mut self, category: u16, tag_flags: u16) {
+        self.category = category;
+        self.tag_flags = tag_flags;
+    }
+    #[inline(always)]
+    pub fn set_price_mode(&mut self, price_mode: u8) {
+        self.price_mode = price_mode;
+    }
+    #[inline(always)]
+    pub fn is_ratio_priced(&self) -> bool {
+        self.price_mode != Self::PRICE_ABSOLUTE
+    }
+    pub fn ratio_quote(&self, amount: u64) -> Option<u64> {
+        if self.amount == 0 {
+            return None;
+        }
+        let numerator = self.receive as u128 * amount as u128;
+        let quote = match self.price_mode {
+            Self::PRICE_RATIO_ROUND_DOWN => numerator / self.amount as u128,
+            _ => numerator.div_ceil(self.amount as u128),
+        };
+        u64::try_from(quote).ok()
+    }
+    #[inline(always)]
+    pub fn set_receive_in_ui(&mut self, receive_in_ui: bool) {
+        self.receive_in_ui = receive_in_ui as u8;
+    }
+    #[inline(always)]
+    pub fn is_receive_in_ui(&self) -> bool {
+        self.receive_in_ui != 0
+    }
+    #[inline(always)]
+    pub fn set_burn_bps(&mut self, burn_bps: u16) {
+        self.burn_bps = burn_bps;
+    }
+    #[inline(always)]
+    pub fn has_burn(&self) -> bool {
+        self.burn_bps != 0
+    }
+    pub fn terms_hash(&self) -> [u8; 32] {
+        solana_sha256_hasher::hashv(&[
+            self.mint_a.as_ref(),
+            self.mint_b.as_ref(),
+            &self.receive.to_le_bytes(),
+            &self.amount.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+    #[inline(always)]
+    pub fn burn_amount(&self, receive: u64) -> u64 {
+        (receive as u128 * self.burn_bps as u128 / 10_000) as u64
+    }
+    #[inline(always)]
+    pub fn set_overpay_mode(&mut self, overpay_mode: u8) {
+        self.overpay_mode = overpay_mode;
+    }
+    #[inline(always)]
+    pub fn payment(&self, receive: u64, pay: u64) -> Result<u64, ProgramError> {
+        if pay < receive {
+            return Err(ProgramError::InsufficientFunds);
+        }
+        match self.overpay_mode {
+            Self::OVERPAY_REFUND => Ok(receive),
+            Self::OVERPAY_TO_MAKER => Ok(pay),
+            _ if pay > receive => Err(ProgramError::InvalidArgument),
+            _ => Ok(receive),
+        }
+    }
+    #[inline(always)]
+    pub fn clock_for(deadline_kind: u8, clock: &Clock) -> i64 {
+        match deadline_kind {
+            Self::DEADLINE_SLOT => clock.slot as i64,
+            _ => clock.unix_timestamp,
+        }
+    }
+    #[inline(always)]
+    pub fn has_deadline(&self) -> bool {
+        self.deadline != 0
+    }
+    #[inline(always)]
+    pub fn is_expired(&self, clock: &Clock) -> bool {
+        Self::clock_for(self.deadline_kind, clock) >= self.deadline
+    }
+    #[inline(always)]
+    pub fn is_auction(&self) -> bool {
+        self.flags & Self::AUCTION != 0
+    }
+    #[inline(always)]
+    pub fn is_standing(&self) -> bool {
+        self.flags & Self::STANDING != 0
+    }
+    #[inline(always)]
+    pub fn is_vesting(&self) -> bool {
+        self.flags & Self::VESTING != 0
+    }
+    #[inline(always)]
+    pub fn has_royalty(&self) -> bool {
+        self.flags & Self::ROYALTY != 0
+    }
+    #[inline(always)]
+    pub fn set_vesting_schedule(&mut self, cliff: i64, duration: i64) {
+        self.vesting_cliff = cliff;
+        self.vesting_duration = duration;
+    }
+    #[inline(always)]
+    pub fn start_vesting(&mut self, taker: Address, amount: u64, now: i64) {
+        self.taker = taker;
+        self.amount = amount;
+        self.vesting_start = now;
+    }
+    #[inline(always)]
+    pub fn set_claimed(&mut self, claimed: u64) {
+        self.claimed = claimed;
+    }
+    #[inline(always)]
+    pub fn set_fills(&mut self, fills: u64) {
+        self.fills = fills;
+    }
+    #[inline(always)]
+    pub fn set_fill_limits(&mut self, min_fill: u64, max_fill_per_tx: u64) {
+        self.min_fill = min_fill;
+        self.max_fill_per_tx = max_fill_per_tx;
+    }
+    #[inline(always)]
+    pub fn set_cooldown_slots(&mut self, cooldown_slots: u64) {
+        self.cooldown_slots = cooldown_slots;
+    }
+    #[inline(always)]
+    pub fn has_cooldown(&self) -> bool {
+        self.cooldown_slots != 0
+    }
+    #[inline(always)]
+    pub fn is_valid_fill(&self, amount: u64, remaining: u64) -> bool {
+        (amount >= self.min_fill || amount == remaining)
+            && (self.max_fill_per_tx == 0 || amount <= self.max_fill_per_tx)
+    }
+    #[inline(always)]
+    pub fn clamp_fill(&self, amount: u64, remaining: u64) -> u64 {
+        match self.max_fill_per_tx {
+            0 => amount.min(remaining),
+            max => amount.min(remaining).min(max),
+        }
+    }
+    #[inline(always)]
+    pub fn set_last_update_seq(&mut self, last_update_seq: u64) {
+        self.last_update_seq = last_update_seq;
+    }
+    #[inline(always)]
+    pub fn set_proceeds(&mut self, proceeds: Address) {
+        self.proceeds = proceeds;
+    }
+    #[inline(always)]
+    pub fn set_vault(&mut self, vault: Address) {
+        self.vault = vault;
+    }
+    #[inline(always)]
+    pub fn set_allowlist(&mut self, allowlist: Address) {
+        self.allowlist = allowlist;
+    }
+    #[inline(always)]
+    pub fn has_allowlist(&self) -> bool {
+        self.allowlist.ne(&Address::default())
+    }
+    #[inline(always)]
+    pub fn set_label(&mut self, label: [u8; 32]) {
+        self.label = label;
+    }
+    #[inline(always)]
+    pub fn set_tracked(&mut self, tracked: bool) {
+        self.tracked = tracked as u8;
+    }
+    #[inline(always)]
+    pub fn is_tracked(&self) -> bool {
+        self.tracked != 0
+    }
+    #[inline(always)]
+    pub fn set_splits(&mut self, splits: u8) {
+        self.splits = splits;
+    }
+    #[inline(always)]
+    pub fn has_split(&self) -> bool {
+        self.splits != 0
+    }
+    #[inline(always)]
+    pub fn set_proceeds_locked(&mut self, proceeds_locked: bool) {
+        self.proceeds_locked = proceeds_locked as u8;
+    }
+    #[inline(always)]
+    pub fn is_proceeds_locked(&self) -> bool {
+        self.proceeds_locked != 0
+    }
+    #[inline(always)]
+    pub fn has_proceeds(&self) -> bool {
+        self.proceeds.ne(&Address::default())
+    }
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.vesting_start);
+        if elapsed < self.vesting_cliff {
+            return 0;
+        }
+        if elapsed >= self.vesting_duration {
+            return self.amount;
+        }
+        (self.amount as u128 * elapsed as u128 / self.vesting_duration as u128) as u64
+    }
+    #[inline(always)]
     pub fn set_bump(&mut self, bump: [u8; 1]) {
         self.bump = bump;
     }
     #[inline(always)]
+    pub fn set_flags(&mut self, flags: u8) {
+        self.flags = flags;
+    }
+    #[inline(always)]
     pub fn set_inner(
         &mut self,
         seed: u64,
@@ -65,11 +412,873 @@ impl Escrow {
         receive: u64,
         bump: [u8; 1],
     ) {
+        self.discriminator = Self::DISCRIMINATOR;
+        self.version = Self::VERSION;
         self.seed = seed;
         self.maker = maker;
+        self.creator = maker;
         self.mint_a = mint_a;
         self.mint_b = mint_b;
         self.receive = receive;
         self.bump = bump;
     }
 }
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct EscrowV1 {
+    pub seed: [u8; 8],
+    pub maker: Address,
+    pub mint_a: Address,
+    pub mint_b: Address,
+    pub receive: [u8; 8],
+    pub bump: [u8; 1],
+}
+
+impl EscrowV1 {
+    pub const LEN: usize = size_of::<Self>();
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct AuctionBid {
+    pub escrow: Address,
+    pub bidder: Address,
+    pub amount: u64,
+    pub bump: [u8; 1],
+    pub _padding: [u8; 7],
+}
+
+impl AuctionBid {
+    pub const LEN: usize = size_of::<Self>();
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        cast_mut(bytes)
+    }
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+    #[inline(always)]
+    pub fn set_inner(&mut self, escrow: Address, bidder: Address, amount: u64, bump: [u8; 1]) {
+        self.escrow = escrow;
+        self.bidder = bidder;
+        self.amount = amount;
+        self.bump = bump;
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct CounterOffer {
+    pub escrow: Address,
+    pub taker: Address,
+    pub receive: u64,
+    pub bump: [u8; 1],
+    pub _padding: [u8; 7],
+}
+
+impl CounterOffer {
+    pub const LEN: usize = size_of::<Self>();
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        cast_mut(bytes)
+    }
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+    #[inline(always)]
+    pub fn set_inner(&mut self, escrow: Address, taker: Address, receive: u64, bump: [u8; 1]) {
+        self.escrow = escrow;
+        self.taker = taker;
+        self.receive = receive;
+        self.bump = bump;
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct FillReceipt {
+    pub escrow: Address,
+    pub taker: Address,
+    pub mint_a: Address,
+    pub mint_b: Address,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub slot: u64,
+    pub bump: [u8; 1],
+    pub _padding: [u8; 7],
+}
+
+impl FillReceipt {
+    pub const LEN: usize = size_of::<Self>();
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        cast_mut(bytes)
+    }
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct Config {
+    pub admin: Address,
+    pub treasury: Address,
+    pub seq: u64,
+    pub bump: [u8; 1],
+    pub denylist: u8,
+    pub referral_bps: u16,
+    pub mint_list: u8,
+    pub closer_pct: u8,
+    pub max_open_escrows: u16,
+    pub caller_policy: u8,
+    pub _reserved: [u8; 7],
+}
+
+impl Config {
+    pub const LEN: usize = size_of::<Self>();
+    pub const MAX_REFERRAL_BPS: u16 = 1_000;
+    pub const MAX_CLOSER_PCT: u8 = 50;
+    pub const MINT_LIST_NONE: u8 = 0;
+    pub const MINT_LIST_ALLOW: u8 = 1;
+    pub const MINT_LIST_DENY: u8 = 2;
+    pub const CALLER_ANY: u8 = 0;
+    pub const CALLER_TOP_LEVEL: u8 = 1;
+    pub const CALLER_ROUTERS: u8 = 2;
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        cast_mut(bytes)
+    }
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+    #[inline(always)]
+    pub fn set_inner(&mut self, admin: Address, treasury: Address, bump: [u8; 1]) {
+        self.admin = admin;
+        self.treasury = treasury;
+        self.seq = 0;
+        self.bump = bump;
+    }
+    #[inline(always)]
+    pub fn set_denylist(&mut self, denylist: bool) {
+        self.denylist = denylist as u8;
+    }
+    #[inline(always)]
+    pub fn has_denylist(&self) -> bool {
+        self.denylist != 0
+    }
+    #[inline(always)]
+    pub fn set_mint_list(&mut self, mint_list: u8) {
+        self.mint_list = mint_list;
+    }
+    #[inline(always)]
+    pub fn set_referral_bps(&mut self, referral_bps: u16) {
+        self.referral_bps = referral_bps;
+    }
+    #[inline(always)]
+    pub fn set_max_open_escrows(&mut self, max_open_escrows: u16) {
+        self.max_open_escrows = max_open_escrows;
+    }
+    #[inline(always)]
+    pub fn set_caller_policy(&mut self, caller_policy: u8) {
+        self.caller_policy = caller_policy;
+    }
+    #[inline(always)]
+    pub fn set_closer_pct(&mut self, closer_pct: u8) {
+        self.closer_pct = closer_pct;
+    }
+    #[inline(always)]
+    pub fn closer_reward(&self, lamports: u64) -> u64 {
+        (lamports as u128 * self.closer_pct as u128 / 100) as u64
+    }
+    #[inline(always)]
+    pub fn referral_fee(&self, amount: u64) -> u64 {
+        (amount as u128 * self.referral_bps as u128 / 10_000) as u64
+    }
+    #[inline(always)]
+    pub fn next_seq(&mut self) -> Option<u64> {
+        self.seq = self.seq.checked_add(1)?;
+        Some(self.seq)
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct MakerNonce {
+    pub maker: Address,
+    pub nonce: u64,
+    pub bump: [u8; 1],
+    pub _padding: [u8; 3],
+    pub open_escrows: u32,
+}
+
+impl MakerNonce {
+    pub const LEN: usize = size_of::<Self>();
+    pub const PREFIX: &'static [u8] = b"nonce";
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        cast_mut(bytes)
+    }
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+    #[inline(always)]
+    pub fn set_inner(&mut self, maker: Address, bump: [u8; 1]) {
+        self.maker = maker;
+        self.nonce = 0;
+        self.bump = bump;
+    }
+    #[inline(always)]
+    pub fn next_nonce(&mut self) -> u64 {
+        let nonce = self.nonce;
+        self.nonce += 1;
+        nonce
+    }
+    #[inline(always)]
+    pub fn open(&mut self, max_open_escrows: u16) -> bool {
+        if max_open_escrows != 0 && self.open_escrows >= max_open_escrows as u32 {
+            return false;
+        }
+        self.open_escrows += 1;
+        true
+    }
+    #[inline(always)]
+    pub fn release(&mut self) {
+        self.open_escrows = self.open_escrows.saturating_sub(1);
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct OrderNonce {
+    pub maker: Address,
+    pub nonce: u64,
+    pub bump: [u8; 1],
+    pub _padding: [u8; 7],
+}
+
+impl OrderNonce {
+    pub const LEN: usize = size_of::<Self>();
+    pub const PREFIX: &'static [u8] = b"orders";
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        cast_mut(bytes)
+    }
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+    #[inline(always)]
+    pub fn set_inner(&mut self, maker: Address, bump: [u8; 1]) {
+        self.maker = maker;
+        self.nonce = 0;
+        self.bump = bump;
+    }
+    #[inline(always)]
+    pub fn consume(&mut self, nonce: u64) -> bool {
+        match nonce.checked_add(1) {
+            Some(next) if nonce >= self.nonce => {
+                self.nonce = next;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct AccountEscrow {
+    pub maker: Address,
+    pub token_account: Address,
+    pub mint_b: Address,
+    pub receive: u64,
+    pub bump: [u8; 1],
+    pub _padding: [u8; 7],
+}
+
+impl AccountEscrow {
+    pub const LEN: usize = size_of::<Self>();
+    pub const PREFIX: &'static [u8] = b"account_escrow";
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        cast_mut(bytes)
+    }
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        maker: Address,
+        token_account: Address,
+        mint_b: Address,
+        receive: u64,
+        bump: [u8; 1],
+    ) {
+        self.maker = maker;
+        self.token_account = token_account;
+        self.mint_b = mint_b;
+        self.receive = receive;
+        self.bump = bump;
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct CollectionOffer {
+    pub maker: Address,
+    pub collection: Address,
+    pub mint_b: Address,
+    pub attester: Address,
+    pub attributes_hash: [u8; 32],
+    pub seed: u64,
+    pub amount: u64,
+    pub bump: [u8; 1],
+    pub _padding: [u8; 7],
+}
+
+impl CollectionOffer {
+    pub const LEN: usize = size_of::<Self>();
+    pub const PREFIX: &'static [u8] = b"collection_offer";
+    pub const ATTESTATION_LEN: usize = 64;
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        cast_mut(bytes)
+    }
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+    #[inline(always)]
+    pub fn has_attributes(&self) -> bool {
+        self.attributes_hash != [0u8; 32]
+    }
+    #[inline(always)]
+    pub fn attestation(&self, nft_mint: &Address) -> [u8; Self::ATTESTATION_LEN] {
+        let mut message = [0u8; Self::ATTESTATION_LEN];
+        message[0..32].copy_from_slice(nft_mint.as_ref());
+        message[32..64].copy_from_slice(&self.attributes_hash);
+        message
+    }
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        maker: Address,
+        collection: Address,
+        mint_b: Address,
+        seed: u64,
+        amount: u64,
+        bump: [u8; 1],
+    ) {
+        self.maker = maker;
+        self.collection = collection;
+        self.mint_b = mint_b;
+        self.seed = seed;
+        self.amount = amount;
+        self.bump = bump;
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct StakeEscrow {
+    pub maker: Address,
+    pub stake: Address,
+    pub mint_b: Address,
+    pub receive: u64,
+    pub bump: [u8; 1],
+    pub _padding: [u8; 7],
+}
+
+impl StakeEscrow {
+    pub const LEN: usize = size_of::<Self>();
+    pub const PREFIX: &'static [u8] = b"stake_escrow";
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        cast_mut(bytes)
+    }
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        maker: Address,
+        stake: Address,
+        mint_b: Address,
+        receive: u64,
+        bump: [u8; 1],
+    ) {
+        self.maker = maker;
+        self.stake = stake;
+        self.mint_b = mint_b;
+        self.receive = receive;
+        self.bump = bump;
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct TakerCooldown {
+    pub escrow: Address,
+    pub taker: Address,
+    pub last_fill_slot: u64,
+    pub bump: [u8; 1],
+    pub _padding: [u8; 7],
+}
+
+impl TakerCooldown {
+    pub const LEN: usize = size_of::<Self>();
+    pub const PREFIX: &'static [u8] = b"cooldown";
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        cast_mut(bytes)
+    }
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+    #[inline(always)]
+    pub fn set_inner(&mut self, escrow: Address, taker: Address, bump: [u8; 1]) {
+        self.escrow = escrow;
+        self.taker = taker;
+        self.last_fill_slot = 0;
+        self.bump = bump;
+    }
+    #[inline(always)]
+    pub fn is_cooling_down(&self, cooldown_slots: u64, slot: u64) -> bool {
+        self.last_fill_slot != 0 && slot < self.last_fill_slot.saturating_add(cooldown_slots)
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct EscrowCondition {
+    pub escrow: Address,
+    pub account: Address,
+    pub program: Address,
+    pub expected: [u8; 32],
+    pub offset: u32,
+    pub len: u8,
+    pub bump: [u8; 1],
+    pub _padding: [u8; 2],
+}
+
+impl EscrowCondition {
+    pub const LEN: usize = size_of::<Self>();
+    pub const PREFIX: &'static [u8] = b"condition";
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        cast_mut(bytes)
+    }
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        escrow: Address,
+        account: Address,
+        program: Address,
+        offset: u32,
+        expected: &[u8],
+        bump: [u8; 1],
+    ) {
+        self.escrow = escrow;
+        self.account = account;
+        self.program = program;
+        self.offset = offset;
+        self.len = expected.len() as u8;
+        if let Some(slot) = self.expected.get_mut(..expected.len()) {
+            slot.copy_from_slice(expected);
+        }
+        self.bump = bump;
+    }
+    #[inline(always)]
+    pub fn expected(&self) -> &[u8] {
+        self.expected.get(..self.len as usize).unwrap_or_default()
+    }
+    #[inline(always)]
+    pub fn is_met(&self, data: &[u8]) -> bool {
+        let start = self.offset as usize;
+        data.get(start..start + self.len as usize) == Some(self.expected())
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct ValueFloor {
+    pub escrow: Address,
+    pub oracle: Address,
+    pub program: Address,
+    pub max_age: u64,
+    pub price_offset: u32,
+    pub timestamp_offset: u32,
+    pub min_value_bps: u16,
+    pub expo: i8,
+    pub bump: [u8; 1],
+    pub _padding: [u8; 4],
+}
+
+impl ValueFloor {
+    pub const LEN: usize = size_of::<Self>();
+    pub const PREFIX: &'static [u8] = b"value_floor";
+    pub const BPS: u128 = 10_000;
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        cast_mut(bytes)
+    }
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+    #[inline(always)]
+    pub fn scaled_price(&self, price: u64, scale_one: u128) -> Option<u128> {
+        let exponent = scale_one.checked_ilog10()? as i32 + self.expo as i32;
+        if exponent >= 0 {
+            (price as u128).checked_mul(10u128.checked_pow(exponent as u32)?)
+        } else {
+            Some(price as u128 / 10u128.checked_pow(exponent.unsigned_abs())?)
+        }
+    }
+    #[inline(always)]
+    pub fn is_met(&self, ui_price: u128, oracle_price: u128) -> bool {
+        ui_price.saturating_mul(Self::BPS)
+            >= oracle_price.saturating_mul(self.min_value_bps as u128)
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct AddressList {
+    pub len: u64,
+    pub bump: [u8; 1],
+    pub _padding: [u8; 7],
+}
+
+impl AddressList {
+    pub const LEN: usize = size_of::<Self>();
+    pub const DENYLIST_PREFIX: &'static [u8] = b"denylist";
+    pub const MINT_LIST_PREFIX: &'static [u8] = b"mint_list";
+    pub const ROUTER_LIST_PREFIX: &'static [u8] = b"routers";
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        cast_mut(bytes)
+    }
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+    #[inline(always)]
+    pub fn space(len: usize) -> usize {
+        Self::LEN + len * size_of::<Address>()
+    }
+    pub fn entries(bytes: &[u8]) -> Result<&[Address], ProgramError> {
+        let (header, entries) = bytes
+            .split_at_checked(Self::LEN)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let len = Self::load(header)?.len as usize;
+        bytemuck::try_cast_slice::<u8, Address>(entries)
+            .ok()
+            .and_then(|entries| entries.get(..len))
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct RollConfig {
+    pub maker: Address,
+    pub mint_a: Address,
+    pub mint_b: Address,
+    pub receive_num: u64,
+    pub receive_den: u64,
+    pub bump: [u8; 1],
+    pub _padding: [u8; 7],
+}
+
+impl RollConfig {
+    pub const LEN: usize = size_of::<Self>();
+    pub const PREFIX: &'static [u8] = b"roll";
+    pub const SEED_BIT: u64 = 1 << 63;
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        cast_mut(bytes)
+    }
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+    #[inline(always)]
+    pub fn set_inner(&mut self, maker: Address, mint_a: Address, mint_b: Address, bump: [u8; 1]) {
+        self.maker = maker;
+        self.mint_a = mint_a;
+        self.mint_b = mint_b;
+        self.bump = bump;
+    }
+    #[inline(always)]
+    pub fn set_price(&mut self, receive_num: u64, receive_den: u64) {
+        self.receive_num = receive_num;
+        self.receive_den = receive_den;
+    }
+    #[inline(always)]
+    pub fn rolled_receive(&self, amount: u64) -> Option<u64> {
+        if self.receive_den == 0 {
+            return None;
+        }
+        let receive = amount as u128 * self.receive_num as u128 / self.receive_den as u128;
+        u64::try_from(receive).ok().filter(|receive| *receive != 0)
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct ProceedsLock {
+    pub escrow: Address,
+    pub maker: Address,
+    pub vault: Address,
+    pub start: i64,
+    pub duration: i64,
+    pub claimed: u64,
+    pub bump: [u8; 1],
+    pub _padding: [u8; 7],
+}
+
+impl ProceedsLock {
+    pub const LEN: usize = size_of::<Self>();
+    pub const PREFIX: &'static [u8] = b"proceeds_lock";
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        cast_mut(bytes)
+    }
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        escrow: Address,
+        maker: Address,
+        vault: Address,
+        start: i64,
+        duration: i64,
+        bump: [u8; 1],
+    ) {
+        self.escrow = escrow;
+        self.maker = maker;
+        self.vault = vault;
+        self.start = start;
+        self.duration = duration;
+        self.claimed = 0;
+        self.bump = bump;
+    }
+    #[inline(always)]
+    pub fn claimable(&self, balance: u64, now: i64) -> u64 {
+        let total = balance as u128 + self.claimed as u128;
+        let elapsed = now.saturating_sub(self.start).clamp(0, self.duration) as u128;
+        let vested = total * elapsed / self.duration as u128;
+        (vested as u64).saturating_sub(self.claimed)
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct ProceedsSplit {
+    pub escrow: Address,
+    pub recipients: [Address; 4],
+    pub bps: [u16; 4],
+    pub count: u8,
+    pub bump: [u8; 1],
+    pub _padding: [u8; 6],
+}
+
+impl ProceedsSplit {
+    pub const LEN: usize = size_of::<Self>();
+    pub const PREFIX: &'static [u8] = b"split";
+    pub const MAX_RECIPIENTS: usize = 4;
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        cast_mut(bytes)
+    }
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+    #[inline(always)]
+    pub fn set_inner(&mut self, escrow: Address, bps: [u16; 4], bump: [u8; 1]) {
+        self.escrow = escrow;
+        self.recipients = [Address::default(); 4];
+        self.bps = bps;
+        self.count = 0;
+        self.bump = bump;
+    }
+    #[inline(always)]
+    pub fn push(&mut self, recipient: Address) {
+        if let Some(slot) = self.recipients.get_mut(self.count as usize) {
+            *slot = recipient;
+            self.count += 1;
+        }
+    }
+    #[inline(always)]
+    pub fn recipients(&self) -> &[Address] {
+        self.recipients
+            .get(..self.count as usize)
+            .unwrap_or_default()
+    }
+    #[inline(always)]
+    pub fn share(&self, index: usize, amount: u64) -> u64 {
+        let bps = self.bps.get(index).copied().unwrap_or(0);
+        (amount as u128 * bps as u128 / 10_000) as u64
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct ReleaseApprovals {
+    pub escrow: Address,
+    pub signers: [Address; 5],
+    pub threshold: u8,
+    pub count: u8,
+    pub approved: u8,
+    pub bump: [u8; 1],
+    pub _padding: [u8; 4],
+}
+
+impl ReleaseApprovals {
+    pub const LEN: usize = size_of::<Self>();
+    pub const PREFIX: &'static [u8] = b"release";
+    pub const MAX_SIGNERS: usize = 5;
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        cast_mut(bytes)
+    }
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+    #[inline(always)]
+    pub fn set_inner(&mut self, escrow: Address, threshold: u8, bump: [u8; 1]) {
+        self.escrow = escrow;
+        self.signers = [Address::default(); 5];
+        self.threshold = threshold;
+        self.count = 0;
+        self.approved = 0;
+        self.bump = bump;
+    }
+    #[inline(always)]
+    pub fn push(&mut self, signer: Address) {
+        if let Some(slot) = self.signers.get_mut(self.count as usize) {
+            *slot = signer;
+            self.count += 1;
+        }
+    }
+    #[inline(always)]
+    pub fn signers(&self) -> &[Address] {
+        self.signers.get(..self.count as usize).unwrap_or_default()
+    }
+    #[inline(always)]
+    pub fn approve(&mut self, signer: &Address) -> bool {
+        match self.signers().iter().position(|listed| listed.eq(signer)) {
+            Some(index) => {
+                self.approved |= 1 << index;
+                true
+            }
+            None => false,
+        }
+    }
+    #[inline(always)]
+    pub fn is_released(&self) -> bool {
+        self.approved.count_ones() >= self.threshold as u32
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct MintVolume {
+    pub mint: Address,
+    pub volume: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct EscrowStats {
+    pub discriminator: u8,
+    pub bump: [u8; 1],
+    pub _padding: [u8; 2],
+    pub cursor: u32,
+    pub created: u64,
+    pub filled: u64,
+    pub volumes: [MintVolume; 8],
+}
+
+impl EscrowStats {
+    pub const DISCRIMINATOR: u8 = 2;
+    pub const LEN: usize = size_of::<Self>();
+    pub const PREFIX: &'static [u8] = b"stats";
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        cast_mut(bytes)
+    }
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        cast(bytes)
+    }
+    #[inline(always)]
+    pub fn set_inner(&mut self, bump: [u8; 1]) {
+        self.discriminator = Self::DISCRIMINATOR;
+        self.bump = bump;
+    }
+    #[inline(always)]
+    pub fn record_created(&mut self) {
+        self.created += 1;
+    }
+    #[inline(always)]
+    pub fn record_fill(
+        &mut self,
+        mint_a: &Address,
+        amount_a: u64,
+        mint_b: &Address,
+        amount_b: u64,
+    ) {
+        self.filled += 1;
+        self.record_volume(mint_a, amount_a);
+        self.record_volume(mint_b, amount_b);
+    }
+    pub fn record_volume(&mut self, mint: &Address, amount: u64) {
+        if let Some(entry) = self.volumes.iter_mut().find(|entry| entry.mint.eq(mint)) {
+            entry.volume = entry.volume.saturating_add(amount);
+            return;
+        }
+        let len = self.volumes.len() as u32;
+        let index = self.cursor % len;
+        if let Some(entry) = self.volumes.get_mut(index as usize) {
+            *entry = MintVolume {
+                mint: *mint,
+                volume: amount,
+            };
+        }
+        self.cursor = (index + 1) % len;
+    }
+    #[inline(always)]
+    pub fn volume(&self, mint: &Address) -> u64 {
+        self.volumes
+            .iter()
+            .find(|entry| entry.mint.eq(mint))
+            .map_or(0, |entry| entry.volume)
+    }
+}
@@ -0,0 +1,1021 @@
+use core::mem::offset_of;
+
+#[cfg(feature = "token2022")]
+use blueshift_escrow::helpers::token_account_2022_len;
+use blueshift_escrow::{
+    AcceptCounter, AddToDenylist, AddToMintList, AddToRouterList, AutoRefund, CancelCounter,
+    CancelOrderNonce, CancelOrderNonceInstructionData, Claim, ClaimProceeds, CloseExpired, Counter,
+    CounterInstructionData, ESCROW_UI_VIEW_LEN, ESCROW_VIEW_LEN, EmitEvent, FillSignedOrder,
+    FillSignedOrderInstructionData, Freeze, GetEscrow, InitializeConfig,
+    InitializeConfigInstructionData, InitializeStats, LockProceeds, LockProceedsInstructionData,
+    MAKE_RETURN_LEN, Make, MakeAccountEscrow, MakeInstructionData, MakeMany,
+    MakeManyInstructionData, MakeStake, Migrate, Refund, RefundAccountEscrow,
+    RefundInstructionData, RefundStake, Remake, RemakeInstructionData, RemoveFromDenylist,
+    RemoveFromMintList, RemoveFromRouterList, SetAllowlist, SetCallerPolicy,
+    SetCallerPolicyInstructionData, SetCondition, SetRoll, SetRollInstructionData, SetValueFloor,
+    SetValueFloorInstructionData, Take, TakeAccountEscrow, TakeInstructionData, TakeMany,
+    TakeManyInstructionData, TakeRoll, TakeSigned, TakeStake, TopUp, TopUpInstructionData,
+    TransferMaker, Version, Withdraw, WithdrawInstructionData,
+    events::{FillEvent, GroupMemberEvent},
+    helpers::{GroupMemberMint, PRICE_SCALE_ONE, PriceBounds, UI_SCALE_ONE, UiAmount},
+    serialize_escrow_ui_view, serialize_escrow_view, serialize_make_return,
+    state::{
+        AccountEscrow, AddressList, AuctionBid, CollectionOffer, Config, CounterOffer, Escrow,
+        EscrowCondition, EscrowStats, FillReceipt, MakerNonce, OrderNonce, ProceedsLock,
+        ProceedsSplit, ReleaseApprovals, RollConfig, StakeEscrow, TakerCooldown, ValueFloor,
+    },
+};
+#[cfg(feature = "dispute")]
+use blueshift_escrow::{ApproveRelease, SetReleaseSigners, SetReleaseSignersInstructionData};
+#[cfg(feature = "auction")]
+use blueshift_escrow::{Bid, BidInstructionData, OutbidRefund, Settle};
+#[cfg(feature = "nft")]
+use blueshift_escrow::{MakeCollectionOffer, RefundCollectionOffer, TakeCollectionOffer};
+use bytemuck::Zeroable;
+use solana_address::Address;
+
+fn hex(s: &str) -> Vec<u8> {
+    let s: String = s.split_whitespace().collect();
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn instruction_discriminators() {
+    assert_eq!(*Make::DISCRIMINATOR, 0);
+    assert_eq!(*Take::DISCRIMINATOR, 1);
+    assert_eq!(*Refund::DISCRIMINATOR, 2);
+    #[cfg(feature = "auction")]
+    assert_eq!(*Bid::DISCRIMINATOR, 3);
+    #[cfg(feature = "auction")]
+    assert_eq!(*OutbidRefund::DISCRIMINATOR, 4);
+    #[cfg(feature = "auction")]
+    assert_eq!(*Settle::DISCRIMINATOR, 5);
+    assert_eq!(*Counter::DISCRIMINATOR, 6);
+    assert_eq!(*AcceptCounter::DISCRIMINATOR, 7);
+    assert_eq!(*CancelCounter::DISCRIMINATOR, 8);
+    assert_eq!(*TopUp::DISCRIMINATOR, 9);
+    assert_eq!(*Claim::DISCRIMINATOR, 10);
+    assert_eq!(*InitializeConfig::DISCRIMINATOR, 11);
+    assert_eq!(*Migrate::DISCRIMINATOR, 12);
+    assert_eq!(*Withdraw::DISCRIMINATOR, 13);
+    assert_eq!(*CloseExpired::DISCRIMINATOR, 14);
+    assert_eq!(*GetEscrow::DISCRIMINATOR, 15);
+    assert_eq!(*TakeSigned::DISCRIMINATOR, 16);
+    assert_eq!(*TransferMaker::DISCRIMINATOR, 17);
+    assert_eq!(*Make::PAIR_DISCRIMINATOR, 18);
+    assert_eq!(*Make::NONCE_DISCRIMINATOR, 19);
+    assert_eq!(*Version::DISCRIMINATOR, 20);
+    assert_eq!(*Freeze::DISCRIMINATOR, 21);
+    assert_eq!(*SetCondition::DISCRIMINATOR, 22);
+    assert_eq!(*SetAllowlist::DISCRIMINATOR, 23);
+    assert_eq!(*AddToDenylist::DISCRIMINATOR, 24);
+    assert_eq!(*RemoveFromDenylist::DISCRIMINATOR, 25);
+    assert_eq!(*AddToMintList::DISCRIMINATOR, 26);
+    assert_eq!(*RemoveFromMintList::DISCRIMINATOR, 27);
+    assert_eq!(*SetRoll::DISCRIMINATOR, 28);
+    assert_eq!(*TakeRoll::DISCRIMINATOR, 29);
+    assert_eq!(*LockProceeds::DISCRIMINATOR, 30);
+    assert_eq!(*ClaimProceeds::DISCRIMINATOR, 31);
+    assert_eq!(*InitializeStats::DISCRIMINATOR, 32);
+    assert_eq!(*MakeMany::DISCRIMINATOR, 33);
+    assert_eq!(*TakeMany::DISCRIMINATOR, 34);
+    assert_eq!(*Remake::DISCRIMINATOR, 35);
+    assert_eq!(*AutoRefund::DISCRIMINATOR, 36);
+    assert_eq!(*SetCallerPolicy::DISCRIMINATOR, 37);
+    assert_eq!(*AddToRouterList::DISCRIMINATOR, 38);
+    assert_eq!(*RemoveFromRouterList::DISCRIMINATOR, 39);
+    assert_eq!(*FillSignedOrder::DISCRIMINATOR, 40);
+    assert_eq!(*CancelOrderNonce::DISCRIMINATOR, 41);
+    assert_eq!(*EmitEvent::DISCRIMINATOR, 42);
+    assert_eq!(*MakeStake::DISCRIMINATOR, 43);
+    assert_eq!(*TakeStake::DISCRIMINATOR, 44);
+    assert_eq!(*RefundStake::DISCRIMINATOR, 45);
+    assert_eq!(*MakeAccountEscrow::DISCRIMINATOR, 46);
+    assert_eq!(*TakeAccountEscrow::DISCRIMINATOR, 47);
+    assert_eq!(*RefundAccountEscrow::DISCRIMINATOR, 48);
+    #[cfg(feature = "nft")]
+    assert_eq!(*MakeCollectionOffer::DISCRIMINATOR, 49);
+    #[cfg(feature = "nft")]
+    assert_eq!(*TakeCollectionOffer::DISCRIMINATOR, 50);
+    #[cfg(feature = "nft")]
+    assert_eq!(*RefundCollectionOffer::DISCRIMINATOR, 51);
+    assert_eq!(*SetValueFloor::DISCRIMINATOR, 52);
+    #[cfg(feature = "dispute")]
+    assert_eq!(*SetReleaseSigners::DISCRIMINATOR, 53);
+    #[cfg(feature = "dispute")]
+    assert_eq!(*ApproveRelease::DISCRIMINATOR, 54);
+}
+
+#[test]
+fn make_many_instruction_data() {
+    let data = hex("
+        0100000000000000 0200000000000000 0300000000000000
+        0400000000000000 0500000000000000 0600000000000000
+    ");
+    let parsed = MakeManyInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(parsed.len(), 2);
+    let offer = parsed.offer(1).unwrap();
+    assert_eq!(offer.seed, 4);
+    assert_eq!(offer.receive, 5);
+    assert_eq!(offer.amount, 6);
+    assert_eq!(offer.deadline, 0);
+    assert_eq!(offer.flags, 0);
+
+    assert!(MakeManyInstructionData::try_from(&data[..0]).is_err());
+    assert!(MakeManyInstructionData::try_from(&data[..47]).is_err());
+    assert!(MakeManyInstructionData::try_from([0u8; 24 * 9].as_slice()).is_err());
+    assert!(parsed.offer(0).is_ok());
+    let zero_amount = [0u8; 24];
+    let parsed = MakeManyInstructionData::try_from(zero_amount.as_slice()).unwrap();
+    assert!(parsed.offer(0).is_err());
+}
+
+#[test]
+fn take_many_instruction_data() {
+    let data = hex("e803000000000000 04 06");
+    let parsed = TakeManyInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(parsed.max_pay, 1_000);
+    assert_eq!(parsed.group_lens, &[4, 6]);
+
+    assert!(TakeManyInstructionData::try_from(&data[..8]).is_err());
+    assert!(TakeManyInstructionData::try_from(hex("e803000000000000 03").as_slice()).is_err());
+    assert!(TakeManyInstructionData::try_from([4u8; 17].as_slice()).is_err());
+}
+
+#[test]
+fn remake_instruction_data() {
+    let data = hex("e803000000000000 6400000000000000");
+    let parsed = RemakeInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(parsed.receive, 1_000);
+    assert_eq!(parsed.deadline, 100);
+    assert!(RemakeInstructionData::try_from(&data[..15]).is_err());
+}
+
+#[test]
+fn make_instruction_data() {
+    let mut data = hex("
+        01
+        0100000000000000
+        0200000000000000
+        0300000000000000
+        0400000000000000
+        0500000000000000
+        0600000000000000
+        28
+        676f6c64656e0000000000000000000000000000000000000000000000000000
+        01
+        0700000000000000
+        0800000000000000
+        0900000000000000
+        02
+        01
+        f401
+        b80b e803 0000 0000
+        0300 0500
+        00
+        00ca9a3b00000000
+        00e40b5402000000
+        01
+    ");
+    assert_eq!(data.len(), MakeInstructionData::LEN);
+    let parsed = MakeInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(parsed.seed, 1);
+    assert_eq!(parsed.receive, 2);
+    assert_eq!(parsed.amount, 3);
+    assert_eq!(parsed.deadline, 4);
+    assert_eq!(parsed.vesting_cliff, 5);
+    assert_eq!(parsed.vesting_duration, 6);
+    assert_eq!(parsed.flags, Escrow::STANDING | Escrow::RENT_TO_TAKER);
+    assert_eq!(&parsed.label[..6], b"golden");
+    assert_eq!(parsed.deadline_kind, Escrow::DEADLINE_SLOT);
+    assert_eq!(parsed.min_fill, 7);
+    assert_eq!(parsed.max_fill_per_tx, 8);
+    assert_eq!(parsed.cooldown_slots, 9);
+    assert_eq!(parsed.overpay_mode, Escrow::OVERPAY_TO_MAKER);
+    assert!(parsed.receive_in_ui);
+    assert_eq!(parsed.burn_bps, 500);
+    assert_eq!(parsed.split_bps, [3_000, 1_000, 0, 0]);
+    assert_eq!(parsed.splits(), 2);
+    assert_eq!(parsed.category, 3);
+    assert_eq!(parsed.tag_flags, 5);
+    assert_eq!(parsed.price_mode, Escrow::PRICE_ABSOLUTE);
+    assert_eq!(
+        (parsed.min_price, parsed.max_price),
+        (1_000_000_000, 10_000_000_000)
+    );
+    assert_eq!(parsed.account_flags, MakeInstructionData::WITH_PROCEEDS);
+
+    assert!(MakeInstructionData::try_from(&data[..MakeInstructionData::LEN - 1]).is_err());
+    let mut longer = data.clone();
+    longer.push(0);
+    assert!(MakeInstructionData::try_from(longer.as_slice()).is_err());
+
+    let reject = |offset: usize, bytes: &[u8]| {
+        let mut data = data.clone();
+        data[offset..offset + bytes.len()].copy_from_slice(bytes);
+        assert!(
+            MakeInstructionData::try_from(data.as_slice()).is_err(),
+            "{offset}"
+        );
+    };
+    reject(0, &[2]);
+    reject(83, &hex("0900000000000000"));
+    reject(107, &[3]);
+    reject(108, &[2]);
+    reject(109, &hex("1127"));
+    reject(111, &hex("b80b 0000 e803 0000"));
+    reject(111, &hex("1027 0100 0000 0000"));
+    reject(123, &[3]);
+    reject(124, &hex("00e40b5402000000 00ca9a3b00000000"));
+    reject(140, &[0x80]);
+
+    data[123] = Escrow::PRICE_RATIO_ROUND_DOWN;
+    data[140] = MakeInstructionData::WITH_PROCEEDS
+        | MakeInstructionData::WITH_STATS
+        | MakeInstructionData::WITH_MAKER_INDEX;
+    let parsed = MakeInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(parsed.price_mode, Escrow::PRICE_RATIO_ROUND_DOWN);
+    assert_eq!(
+        parsed.account_flags,
+        MakeInstructionData::WITH_PROCEEDS
+            | MakeInstructionData::WITH_STATS
+            | MakeInstructionData::WITH_MAKER_INDEX
+    );
+}
+
+#[cfg(feature = "client")]
+#[test]
+fn client_make_round_trips() {
+    let args = blueshift_escrow::client::MakeArgs {
+        seed: 1,
+        receive: 2,
+        amount: 3,
+        min_fill: 4,
+        split_bps: [5, 6, 0, 0],
+        price_mode: Escrow::PRICE_RATIO_ROUND_DOWN,
+        max_price: 7,
+        account_flags: MakeInstructionData::WITH_STATS,
+        ..Default::default()
+    };
+    let mint = Address::new_from_array([1; 32]);
+    let instruction =
+        blueshift_escrow::client::make(&mint, &mint, &mint, &pinocchio_token::ID, &args);
+    assert_eq!(instruction.data[0], *Make::DISCRIMINATOR);
+    let parsed = MakeInstructionData::try_from(&instruction.data[1..]).unwrap();
+    assert_eq!((parsed.seed, parsed.receive, parsed.amount), (1, 2, 3));
+    assert_eq!(parsed.min_fill, 4);
+    assert_eq!(parsed.split_bps, [5, 6, 0, 0]);
+    assert_eq!(parsed.price_mode, Escrow::PRICE_RATIO_ROUND_DOWN);
+    assert_eq!(parsed.max_price, 7);
+    assert_eq!(parsed.account_flags, MakeInstructionData::WITH_STATS);
+}
+
+#[test]
+fn price_bounds_ui_price() {
+    assert_eq!(
+        PriceBounds::ui_price(150_000_000, 1_000_000_000, 9, 6),
+        Some(150 * PRICE_SCALE_ONE)
+    );
+    assert_eq!(
+        PriceBounds::ui_price(150, 1_000_000_000, 9, 6),
+        Some(150 * PRICE_SCALE_ONE / 1_000_000)
+    );
+    assert_eq!(
+        PriceBounds::ui_price(2_000_000_000, 1_000_000, 6, 9),
+        Some(2 * PRICE_SCALE_ONE)
+    );
+    assert_eq!(PriceBounds::ui_price(u64::MAX, 1, 255, 0), None);
+}
+
+#[test]
+fn value_floor_check() {
+    let parsed = SetValueFloorInstructionData::try_from(
+        hex("2000 0000 2800 0000 3c00 0000 0000 0000 2823 f8").as_slice(),
+    )
+    .unwrap();
+    assert_eq!(parsed.price_offset, 32);
+    assert_eq!(parsed.timestamp_offset, 40);
+    assert_eq!(parsed.max_age, 60);
+    assert_eq!(parsed.min_value_bps, 9_000);
+    assert_eq!(parsed.expo, -8);
+    assert!(
+        SetValueFloorInstructionData::try_from(
+            hex("2000 0000 2800 0000 3c00 0000 0000 0000 0000 f8").as_slice()
+        )
+        .is_err()
+    );
+
+    let mut floor = ValueFloor::zeroed();
+    floor.expo = parsed.expo;
+    floor.min_value_bps = parsed.min_value_bps;
+    let oracle_price = floor.scaled_price(15_000_000_000, PRICE_SCALE_ONE).unwrap();
+    assert_eq!(oracle_price, 150 * PRICE_SCALE_ONE);
+    assert!(floor.is_met(135 * PRICE_SCALE_ONE, oracle_price));
+    assert!(!floor.is_met(134 * PRICE_SCALE_ONE, oracle_price));
+    floor.expo = -12;
+    assert_eq!(floor.scaled_price(1_500, PRICE_SCALE_ONE), Some(1));
+}
+
+#[test]
+fn release_approvals_threshold() {
+    let maker = Address::new_from_array([1; 32]);
+    let officer = Address::new_from_array([2; 32]);
+    #[cfg(feature = "dispute")]
+    {
+        let mut data = hex("02");
+        data.extend_from_slice(maker.as_ref());
+        data.extend_from_slice(officer.as_ref());
+        let parsed = SetReleaseSignersInstructionData::try_from(data.as_slice()).unwrap();
+        assert_eq!(parsed.threshold, 2);
+        assert_eq!(parsed.signers, &[maker, officer]);
+        assert!(SetReleaseSignersInstructionData::try_from(&data[..33]).is_err());
+        data.extend_from_slice(officer.as_ref());
+        assert!(SetReleaseSignersInstructionData::try_from(data.as_slice()).is_err());
+    }
+
+    let mut approvals = ReleaseApprovals::zeroed();
+    approvals.set_inner(Address::default(), 2, [255]);
+    approvals.push(maker);
+    approvals.push(officer);
+    assert!(!approvals.is_released());
+    assert!(approvals.approve(&maker));
+    assert!(approvals.approve(&maker));
+    assert!(!approvals.is_released());
+    assert!(!approvals.approve(&Address::new_from_array([3; 32])));
+    assert!(approvals.approve(&officer));
+    assert!(approvals.is_released());
+}
+
+#[test]
+fn initialize_config_instruction_data() {
+    let parsed = InitializeConfigInstructionData::try_from(hex("3200 0400 19").as_slice()).unwrap();
+    assert_eq!(
+        (
+            parsed.referral_bps,
+            parsed.max_open_escrows,
+            parsed.closer_pct
+        ),
+        (50, 4, 25)
+    );
+    assert_eq!(
+        InitializeConfigInstructionData::try_from(hex("3200 0400").as_slice())
+            .unwrap()
+            .closer_pct,
+        0
+    );
+    assert!(InitializeConfigInstructionData::try_from(hex("3200 0400 33").as_slice()).is_err());
+
+    let mut config = Config::zeroed();
+    config.set_closer_pct(25);
+    assert_eq!(config.closer_reward(3_089_520), 772_380);
+    assert_eq!(config.next_seq(), Some(1));
+    config.seq = u64::MAX;
+    assert_eq!(config.next_seq(), None);
+    assert_eq!(config.seq, u64::MAX);
+}
+
+#[test]
+fn set_caller_policy_instruction_data() {
+    assert_eq!(
+        SetCallerPolicyInstructionData::try_from(hex("02").as_slice())
+            .unwrap()
+            .policy,
+        Config::CALLER_ROUTERS
+    );
+    assert!(SetCallerPolicyInstructionData::try_from(hex("03").as_slice()).is_err());
+    assert!(SetCallerPolicyInstructionData::try_from(hex("0100").as_slice()).is_err());
+}
+
+#[test]
+fn take_instruction_data() {
+    let data = hex("09 aabb");
+    let parsed = TakeInstructionData::try_from(data.as_slice()).unwrap();
+    assert!(parsed.receipt);
+    assert!(!parsed.recipient);
+    assert!(!parsed.treasury);
+    assert!(parsed.flash);
+    assert_eq!(parsed.callback_data, &[0xaa, 0xbb]);
+
+    let data = hex("06");
+    let parsed = TakeInstructionData::try_from(data.as_slice()).unwrap();
+    assert!(!parsed.receipt && parsed.recipient && parsed.treasury && !parsed.flash);
+    assert!(!parsed.referrer);
+
+    let data = hex("10");
+    assert!(
+        TakeInstructionData::try_from(data.as_slice())
+            .unwrap()
+            .referrer
+    );
+
+    let data = hex("28 0500000000000000 aabb");
+    let parsed = TakeInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(parsed.fill, Some(5));
+    assert!(parsed.flash);
+    assert_eq!(parsed.callback_data, &[0xaa, 0xbb]);
+
+    let data = hex("60 0500000000000000 0600000000000000");
+    let parsed = TakeInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(parsed.fill, Some(5));
+    assert_eq!(parsed.pay, Some(6));
+
+    assert!(TakeInstructionData::try_from(&[][..]).is_ok());
+    let data = hex("88 03 6f6b21 aabb");
+    let parsed = TakeInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(parsed.memo, Some(&b"ok!"[..]));
+    assert_eq!(parsed.callback_data, &[0xaa, 0xbb]);
+    assert!(TakeInstructionData::try_from(hex("80").as_slice()).is_err());
+    assert!(TakeInstructionData::try_from(hex("80 02 aa").as_slice()).is_err());
+    assert!(TakeInstructionData::try_from(hex("80 01 ff").as_slice()).is_err());
+    assert!(TakeInstructionData::try_from(hex("48 0500000000000000").as_slice()).is_err());
+    assert!(TakeInstructionData::try_from(hex("20 05").as_slice()).is_err());
+    assert!(TakeInstructionData::try_from(hex("01 aa").as_slice()).is_err());
+
+    let data = [&[0x01][..], &[0xcd; 32]].concat();
+    let parsed = TakeInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(parsed.terms, Some([0xcd; 32]));
+    assert!(parsed.callback_data.is_empty());
+    let data = [&[0x08][..], &[0xcd; 32]].concat();
+    let parsed = TakeInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(parsed.terms, None);
+    assert_eq!(parsed.callback_data, &[0xcd; 32]);
+    assert!(TakeInstructionData::try_from(&[0x00; 32][..]).is_err());
+
+    let data = hex("20 0500000000000000 01");
+    let parsed = TakeInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(parsed.fill, Some(5));
+    assert_eq!(parsed.fill_mode, TakeInstructionData::IMMEDIATE_OR_CANCEL);
+    assert_eq!(parsed.terms, None);
+    let data = [&hex("20 0500000000000000 00")[..], &[0xcd; 32]].concat();
+    let parsed = TakeInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(parsed.fill_mode, TakeInstructionData::FILL_OR_KILL);
+    assert_eq!(parsed.terms, Some([0xcd; 32]));
+    assert!(TakeInstructionData::try_from(hex("00 01").as_slice()).is_err());
+    assert!(TakeInstructionData::try_from(hex("20 0500000000000000 02").as_slice()).is_err());
+
+    let mut escrow = Escrow::zeroed();
+    escrow.receive = 10;
+    escrow.amount = 3;
+    escrow.price_mode = Escrow::PRICE_RATIO_ROUND_UP;
+    assert!(escrow.is_ratio_priced());
+    assert_eq!(escrow.ratio_quote(1), Some(4));
+    assert_eq!(escrow.ratio_quote(3), Some(10));
+    escrow.price_mode = Escrow::PRICE_RATIO_ROUND_DOWN;
+    assert_eq!(escrow.ratio_quote(1), Some(3));
+    escrow.receive = u64::MAX;
+    escrow.amount = 1;
+    assert_eq!(escrow.ratio_quote(2), None);
+    assert_eq!(escrow.clamp_fill(10, 4), 4);
+    escrow.max_fill_per_tx = 3;
+    assert_eq!(escrow.clamp_fill(10, 4), 3);
+    assert_eq!(escrow.clamp_fill(2, 4), 2);
+}
+
+#[test]
+fn refund_instruction_data() {
+    assert!(
+        RefundInstructionData::try_from(&[][..])
+            .unwrap()
+            .memo
+            .is_none()
+    );
+    let data = hex("02 6f6b");
+    assert_eq!(
+        RefundInstructionData::try_from(data.as_slice())
+            .unwrap()
+            .memo,
+        Some(&b"ok"[..])
+    );
+    assert!(RefundInstructionData::try_from(hex("01 6f6b").as_slice()).is_err());
+}
+
+#[test]
+fn roll_price() {
+    let data = hex("0300000000000000 0200000000000000");
+    let parsed = SetRollInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!((parsed.receive_num, parsed.receive_den), (3, 2));
+    assert!(
+        SetRollInstructionData::try_from(hex("0100000000000000 0000000000000000").as_slice())
+            .is_err()
+    );
+    assert!(
+        SetRollInstructionData::try_from(hex("0000000000000000 0000000000000000").as_slice())
+            .is_ok()
+    );
+
+    let mut roll = RollConfig::zeroed();
+    roll.set_price(3, 2);
+    assert_eq!(roll.rolled_receive(10), Some(15));
+    roll.set_price(1, 3);
+    assert_eq!(roll.rolled_receive(2), None);
+}
+
+#[test]
+fn proceeds_lock() {
+    let data = hex("1027000000000000");
+    assert_eq!(
+        LockProceedsInstructionData::try_from(data.as_slice())
+            .unwrap()
+            .duration,
+        10_000
+    );
+    assert!(LockProceedsInstructionData::try_from(hex("0000000000000000").as_slice()).is_err());
+
+    let mut lock = ProceedsLock::zeroed();
+    lock.start = 100;
+    lock.duration = 100;
+    assert_eq!(lock.claimable(1_000, 50), 0);
+    assert_eq!(lock.claimable(1_000, 125), 250);
+    lock.claimed = 250;
+    assert_eq!(lock.claimable(1_750, 150), 750);
+    assert_eq!(lock.claimable(1_750, 400), 1_750);
+}
+
+#[test]
+fn escrow_stats() {
+    let mints: Vec<Address> = (1..=9).map(|i| Address::new_from_array([i; 32])).collect();
+    let mut stats = EscrowStats::zeroed();
+    stats.record_created();
+    stats.record_fill(&mints[0], 10, &mints[1], 20);
+    stats.record_fill(&mints[0], 5, &mints[1], 7);
+    assert_eq!((stats.created, stats.filled), (1, 2));
+    assert_eq!(stats.volume(&mints[0]), 15);
+    assert_eq!(stats.volume(&mints[1]), 27);
+    for mint in &mints[2..] {
+        stats.record_volume(mint, 1);
+    }
+    assert_eq!(stats.volume(&mints[0]), 0);
+    assert_eq!(stats.volume(&mints[8]), 1);
+    assert_eq!(stats.cursor, 1);
+    stats.cursor = u32::MAX;
+    stats.record_volume(&mints[0], 3);
+    assert_eq!(stats.volume(&mints[0]), 3);
+    assert!(stats.cursor < stats.volumes.len() as u32);
+}
+
+#[test]
+fn proceeds_split() {
+    let mut split = ProceedsSplit::zeroed();
+    split.set_inner(
+        Address::new_from_array([1; 32]),
+        [3_000, 1_000, 0, 0],
+        [255],
+    );
+    split.push(Address::new_from_array([2; 32]));
+    split.push(Address::new_from_array([3; 32]));
+    assert_eq!(split.recipients().len(), 2);
+    assert_eq!(split.share(0, 1_001), 300);
+    assert_eq!(split.share(1, 1_001), 100);
+}
+
+#[test]
+fn amount_instruction_data() {
+    let data = hex("efcdab8967452301");
+    let expected = 0x0123_4567_89ab_cdef;
+    #[cfg(feature = "auction")]
+    assert_eq!(
+        BidInstructionData::try_from(data.as_slice())
+            .unwrap()
+            .amount,
+        expected
+    );
+    assert_eq!(
+        CounterInstructionData::try_from(data.as_slice())
+            .unwrap()
+            .receive,
+        expected
+    );
+    assert_eq!(
+        TopUpInstructionData::try_from(data.as_slice())
+            .unwrap()
+            .amount,
+        expected
+    );
+
+    let parsed =
+        WithdrawInstructionData::try_from(hex("0a00000000000000 1400000000000000").as_slice())
+            .unwrap();
+    assert_eq!(parsed.amount, 10);
+    assert_eq!(parsed.receive, 20);
+}
+
+#[test]
+fn take_signed_message() {
+    let message = TakeSigned::message(&Address::new_from_array([7; 32]), 0x0102, 10, 20);
+    assert_eq!(message[..32], [7; 32]);
+    assert_eq!(
+        message[32..],
+        hex("0201000000000000 0a00000000000000 1400000000000000")[..]
+    );
+}
+
+#[test]
+fn escrow_layout() {
+    assert_eq!(Escrow::DISCRIMINATOR, 1);
+    assert_eq!(Escrow::VERSION, 2);
+    assert_eq!(Escrow::LEN, 456);
+
+    assert_eq!(offset_of!(Escrow, discriminator), 0);
+    assert_eq!(offset_of!(Escrow, version), 1);
+    assert_eq!(offset_of!(Escrow, receive_in_ui), 2);
+    assert_eq!(offset_of!(Escrow, splits), 3);
+    assert_eq!(offset_of!(Escrow, burn_bps), 4);
+    assert_eq!(offset_of!(Escrow, proceeds_locked), 6);
+    assert_eq!(offset_of!(Escrow, tracked), 7);
+    assert_eq!(offset_of!(Escrow, seed), 8);
+    assert_eq!(offset_of!(Escrow, maker), 16);
+    assert_eq!(offset_of!(Escrow, creator), 48);
+    assert_eq!(offset_of!(Escrow, mint_a), 80);
+    assert_eq!(offset_of!(Escrow, mint_b), 112);
+    assert_eq!(offset_of!(Escrow, receive), 144);
+    assert_eq!(offset_of!(Escrow, amount), 152);
+    assert_eq!(offset_of!(Escrow, deadline), 160);
+    assert_eq!(offset_of!(Escrow, highest_bid), 168);
+    assert_eq!(offset_of!(Escrow, highest_bidder), 176);
+    assert_eq!(offset_of!(Escrow, taker), 208);
+    assert_eq!(offset_of!(Escrow, vesting_start), 240);
+    assert_eq!(offset_of!(Escrow, vesting_cliff), 248);
+    assert_eq!(offset_of!(Escrow, vesting_duration), 256);
+    assert_eq!(offset_of!(Escrow, claimed), 264);
+    assert_eq!(offset_of!(Escrow, fills), 272);
+    assert_eq!(offset_of!(Escrow, min_fill), 280);
+    assert_eq!(offset_of!(Escrow, max_fill_per_tx), 288);
+    assert_eq!(offset_of!(Escrow, cooldown_slots), 296);
+    assert_eq!(offset_of!(Escrow, last_update_seq), 304);
+    assert_eq!(offset_of!(Escrow, proceeds), 312);
+    assert_eq!(offset_of!(Escrow, vault), 344);
+    assert_eq!(offset_of!(Escrow, allowlist), 376);
+    assert_eq!(offset_of!(Escrow, label), 408);
+    assert_eq!(offset_of!(Escrow, bump), 440);
+    assert_eq!(offset_of!(Escrow, flags), 441);
+    assert_eq!(offset_of!(Escrow, deadline_kind), 442);
+    assert_eq!(offset_of!(Escrow, namespace), 443);
+    assert_eq!(offset_of!(Escrow, frozen), 444);
+    assert_eq!(offset_of!(Escrow, overpay_mode), 445);
+    assert_eq!(offset_of!(Escrow, conditional), 446);
+    assert_eq!(offset_of!(Escrow, indexed), 447);
+    assert_eq!(offset_of!(Escrow, category), 448);
+    assert_eq!(offset_of!(Escrow, tag_flags), 450);
+    assert_eq!(offset_of!(Escrow, price_mode), 452);
+    assert_eq!(offset_of!(Escrow, value_floor), 453);
+    assert_eq!(offset_of!(Escrow, release_gated), 454);
+
+    assert_eq!(Escrow::ALLOW_PERMANENT_DELEGATE, 0x01);
+    assert_eq!(Escrow::ALLOW_MINT_CLOSE_AUTHORITY, 0x02);
+    assert_eq!(Escrow::AUCTION, 0x04);
+    assert_eq!(Escrow::STANDING, 0x08);
+    assert_eq!(Escrow::VESTING, 0x10);
+    assert_eq!(Escrow::RENT_TO_TAKER, 0x20);
+    assert_eq!(Escrow::RENT_TO_TREASURY, 0x40);
+    assert_eq!(Escrow::ROYALTY, 0x80);
+    assert_eq!(Escrow::NAMESPACE_SEED, 0);
+    assert_eq!(Escrow::NAMESPACE_PAIR, 1);
+    assert_eq!(Escrow::NAMESPACE_NONCE, 2);
+}
+
+#[test]
+fn escrow_encoding() {
+    let mut escrow = Escrow::zeroed();
+    escrow.set_inner(
+        0x11,
+        Address::new_from_array([0xaa; 32]),
+        Address::new_from_array([0xbb; 32]),
+        Address::new_from_array([0xcc; 32]),
+        0x22,
+        [0xfe],
+    );
+    escrow.set_amount(0x33);
+    escrow.set_flags(Escrow::STANDING);
+
+    let bytes = bytemuck::bytes_of(&escrow);
+    assert_eq!(bytes[..16], hex("0102000000000000 1100000000000000")[..]);
+    assert_eq!(bytes[16..48], [0xaa; 32]);
+    assert_eq!(bytes[48..80], [0xaa; 32]);
+    assert_eq!(bytes[80..112], [0xbb; 32]);
+    assert_eq!(bytes[112..144], [0xcc; 32]);
+    assert_eq!(
+        bytes[144..160],
+        hex("2200000000000000 3300000000000000")[..]
+    );
+    assert_eq!(bytes[440..444], hex("fe080000")[..]);
+    assert_eq!(Escrow::load(bytes).unwrap().seed, 0x11);
+}
+
+#[test]
+fn escrow_view_encoding() {
+    let mut escrow = Escrow::zeroed();
+    escrow.set_inner(
+        0x11,
+        Address::new_from_array([0xaa; 32]),
+        Address::new_from_array([0xbb; 32]),
+        Address::new_from_array([0xcc; 32]),
+        0x22,
+        [0xfe],
+    );
+    escrow.set_amount(0x33);
+    escrow.set_deadline(0x44);
+    escrow.set_deadline_kind(Escrow::DEADLINE_SLOT);
+    escrow.set_flags(Escrow::STANDING);
+    escrow.set_fills(0x55);
+    escrow.set_last_update_seq(0x66);
+    escrow.set_label([0x77; 32]);
+
+    let view = serialize_escrow_view(&escrow);
+    assert_eq!(ESCROW_VIEW_LEN, 179);
+    assert_eq!(view[..9], hex("01 1100000000000000")[..]);
+    assert_eq!(view[9..41], [0xaa; 32]);
+    assert_eq!(view[41..73], [0xbb; 32]);
+    assert_eq!(view[73..105], [0xcc; 32]);
+    assert_eq!(
+        view[105..147],
+        hex("
+            2200000000000000
+            3300000000000000
+            4400000000000000
+            0108
+            5500000000000000
+            6600000000000000
+        ")[..]
+    );
+    assert_eq!(view[147..], [0x77; 32]);
+
+    escrow.set_receive_in_ui(true);
+    let view = serialize_escrow_ui_view(&escrow, UI_SCALE_ONE * 2, UI_SCALE_ONE * 2).unwrap();
+    assert_eq!(ESCROW_UI_VIEW_LEN, 204);
+    assert_eq!(view[..ESCROW_VIEW_LEN], serialize_escrow_view(&escrow)[..]);
+    assert_eq!(
+        view[ESCROW_VIEW_LEN..],
+        hex("01 6600000000000000 1100000000000000 2200000000000000")[..]
+    );
+}
+
+#[test]
+fn make_return_encoding() {
+    let view = serialize_make_return(&Address::new_from_array([0xaa; 32]), 0xfe);
+    assert_eq!(MAKE_RETURN_LEN, 33);
+    assert_eq!(view[..32], [0xaa; 32]);
+    assert_eq!(view[32], 0xfe);
+}
+
+#[cfg(feature = "token2022")]
+#[test]
+fn token_2022_account_len() {
+    let mut mint = vec![0u8; 82];
+    assert_eq!(token_account_2022_len(&mint), 165);
+    mint.resize(165, 0);
+    mint.push(1);
+    mint.extend(hex("0e00 4000"));
+    mint.extend([0u8; 64]);
+    assert_eq!(token_account_2022_len(&mint), 171);
+    mint.extend(hex("0100 6c00"));
+    mint.extend([0u8; 108]);
+    assert_eq!(token_account_2022_len(&mint), 183);
+}
+
+#[test]
+fn group_member_mint() {
+    let mint = Address::new_from_array([0xaa; 32]);
+    let mut data = vec![0u8; 165];
+    data.push(1);
+    assert!(GroupMemberMint::parse(&mint, &data).unwrap().is_none());
+
+    data.extend(hex("1600 4000"));
+    data.extend([0x11; 32]);
+    data.extend([0xaa; 32]);
+    assert!(GroupMemberMint::parse(&mint, &data).is_err());
+    data.extend(hex("1700 4800"));
+    data.extend([0xaa; 32]);
+    data.extend([0xbb; 32]);
+    data.extend(7u64.to_le_bytes());
+    data.extend(hex("1200 4000"));
+    data.extend([0x11; 32]);
+    data.extend([0xcc; 32]);
+    let member = GroupMemberMint::parse(&mint, &data).unwrap().unwrap();
+    assert_eq!(member.group, Address::new_from_array([0xbb; 32]));
+    assert_eq!(member.member_number, 7);
+    assert_eq!(member.metadata, Address::new_from_array([0xcc; 32]));
+
+    let other = Address::new_from_array([0xdd; 32]);
+    assert!(GroupMemberMint::parse(&other, &data).unwrap().is_none());
+}
+
+#[test]
+fn ui_amount_scale() {
+    let mut config = [0u8; 52];
+    config[50..52].copy_from_slice(&500i16.to_le_bytes());
+    let scale = UiAmount::interest_scale(&config, 31_556_736).unwrap();
+    assert_eq!(scale, 1_051_271_096_374);
+    assert_eq!(UiAmount::to_ui(1_000_000, scale), 1_051_271);
+    assert_eq!(UiAmount::from_ui(1_051_271, scale).unwrap(), 1_000_000);
+
+    config[50..52].copy_from_slice(&(-500i16).to_le_bytes());
+    let scale = UiAmount::interest_scale(&config, 31_556_736).unwrap();
+    assert_eq!(scale, 951_229_424_502);
+    assert_eq!(UiAmount::interest_scale(&config, 0).unwrap(), UI_SCALE_ONE);
+    assert!(UiAmount::interest_scale(&config[..51], 0).is_err());
+
+    let mut config = [0u8; 56];
+    config[32..40].copy_from_slice(&1.5f64.to_le_bytes());
+    config[40..48].copy_from_slice(&100i64.to_le_bytes());
+    config[48..56].copy_from_slice(&2.0f64.to_le_bytes());
+    assert_eq!(
+        UiAmount::multiplier_scale(&config, 99).unwrap(),
+        UI_SCALE_ONE * 3 / 2
+    );
+    assert_eq!(
+        UiAmount::multiplier_scale(&config, 100).unwrap(),
+        UI_SCALE_ONE * 2
+    );
+    config[48..56].copy_from_slice(&0f64.to_le_bytes());
+    assert!(UiAmount::multiplier_scale(&config, 100).is_err());
+}
+
+#[test]
+fn auxiliary_account_layouts() {
+    assert_eq!(AuctionBid::LEN, 80);
+    assert_eq!(CounterOffer::LEN, 80);
+    assert_eq!(FillReceipt::LEN, 160);
+    assert_eq!(Config::LEN, 88);
+    assert_eq!(offset_of!(Config, denylist), 73);
+    assert_eq!(offset_of!(Config, referral_bps), 74);
+    assert_eq!(offset_of!(Config, mint_list), 76);
+    assert_eq!(offset_of!(Config, closer_pct), 77);
+    assert_eq!(offset_of!(Config, max_open_escrows), 78);
+    assert_eq!(offset_of!(Config, caller_policy), 80);
+    assert_eq!(AddressList::LEN, 16);
+    assert_eq!(AddressList::space(2), 80);
+    assert_eq!(MakerNonce::LEN, 48);
+    assert_eq!(OrderNonce::LEN, 48);
+    assert_eq!(StakeEscrow::LEN, 112);
+    assert_eq!(AccountEscrow::LEN, 112);
+    assert_eq!(CollectionOffer::LEN, 184);
+    assert_eq!(offset_of!(CollectionOffer, seed), 160);
+    assert_eq!(offset_of!(StakeEscrow, receive), 96);
+    assert_eq!(offset_of!(MakerNonce, open_escrows), 44);
+    assert_eq!(TakerCooldown::LEN, 80);
+    assert_eq!(EscrowCondition::LEN, 136);
+    assert_eq!(ValueFloor::LEN, 120);
+    assert_eq!(ReleaseApprovals::LEN, 200);
+    assert_eq!(RollConfig::LEN, 120);
+    assert_eq!(ProceedsLock::LEN, 128);
+    assert_eq!(ProceedsSplit::LEN, 176);
+    assert_eq!(EscrowStats::LEN, 344);
+    assert_eq!(offset_of!(EscrowStats, volumes), 24);
+    assert_eq!(size_of::<FillEvent>(), 232);
+    assert_eq!(FillEvent::DISCRIMINATOR, b"fill");
+    assert_eq!(size_of::<GroupMemberEvent>(), 136);
+    assert_eq!(GroupMemberEvent::DISCRIMINATOR, b"group_member");
+}
+
+#[cfg(feature = "borsh")]
+#[test]
+fn anchor_discriminators() {
+    use blueshift_escrow::borsh::{MAKE_DISCRIMINATOR, REFUND_DISCRIMINATOR, TAKE_DISCRIMINATOR};
+
+    assert_eq!(MAKE_DISCRIMINATOR.to_vec(), hex("8ae3e84ddfa660c5"));
+    assert_eq!(TAKE_DISCRIMINATOR.to_vec(), hex("95e23468068ee627"));
+    assert_eq!(REFUND_DISCRIMINATOR.to_vec(), hex("0260b7fb3fd02e2e"));
+}
+
+#[cfg(feature = "wormhole")]
+#[test]
+fn wormhole_fill_payload() {
+    use blueshift_escrow::helpers::WormholeFill;
+
+    let payload = WormholeFill {
+        escrow: Address::new_from_array([1; 32]),
+        maker: Address::new_from_array([2; 32]),
+        taker: Address::new_from_array([3; 32]),
+        mint_a: Address::new_from_array([4; 32]),
+        mint_b: Address::new_from_array([5; 32]),
+        amount_a: 1_000,
+        amount_b: 0x0102,
+    }
+    .payload();
+    assert_eq!(WormholeFill::LEN, 177);
+    assert_eq!(payload[0], WormholeFill::PAYLOAD_ID);
+    assert_eq!(payload[1..33], [1; 32]);
+    assert_eq!(payload[129..161], [5; 32]);
+    assert_eq!(
+        payload[161..].to_vec(),
+        hex("00000000000003e8 0000000000000102")
+    );
+}
+
+#[test]
+fn fill_signed_order_instruction_data() {
+    let data = hex(
+        "0101010101010101010101010101010101010101010101010101010101010101
+         0000000000000000000000000000000000000000000000000000000000000000
+         0202020202020202020202020202020202020202020202020202020202020202
+         0303030303030303030303030303030303030303030303030303030303030303
+         e803000000000000 f401000000000000 0065cd1d00000000 0700000000000000",
+    );
+    let order = FillSignedOrderInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(order.maker, Address::new_from_array([1; 32]));
+    assert_eq!(order.taker, Address::default());
+    assert_eq!(order.mint_b, Address::new_from_array([3; 32]));
+    assert_eq!((order.amount, order.receive), (1_000, 500));
+    assert_eq!((order.expiry, order.nonce), (500_000_000, 7));
+    assert_eq!(order.message().to_vec(), data);
+    assert!(FillSignedOrderInstructionData::try_from(&data[1..]).is_err());
+
+    let data = hex("0900000000000000");
+    let parsed = CancelOrderNonceInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(parsed.nonce, 9);
+    assert!(CancelOrderNonceInstructionData::try_from(&data[1..]).is_err());
+
+    let mut nonce = OrderNonce::zeroed();
+    assert!(nonce.consume(7));
+    assert_eq!(nonce.nonce, 8);
+    assert!(!nonce.consume(7));
+    assert!(nonce.consume(9));
+    assert!(!nonce.consume(u64::MAX));
+}
+
+#[test]
+fn truncated_data_is_rejected() {
+    use blueshift_escrow::helpers::{account_bytes, find_extension, instruction_bytes, read_bytes};
+
+    assert_eq!(read_bytes::<4>(&[1, 2, 3, 4, 5], 1), Some([2, 3, 4, 5]));
+    assert_eq!(read_bytes::<4>(&[1, 2, 3, 4], 1), None);
+    assert_eq!(read_bytes::<1>(&[], usize::MAX), None);
+    assert!(instruction_bytes::<8>(&[0; 7], 0).is_err());
+    assert!(account_bytes::<32>(&[0; 40], 16).is_err());
+
+    let mut mint = vec![0u8; 166];
+    mint.extend_from_slice(&hex("1700 0100 2a"));
+    assert_eq!(find_extension(&mint, 23), Some(&[0x2a][..]));
+    mint[169] = 0xff;
+    assert_eq!(find_extension(&mint, 23), None);
+    mint.truncate(168);
+    assert_eq!(find_extension(&mint, 23), None);
+}
+
+fn program_binary() -> Option<Vec<u8>> {
+    let out_dir = std::env::var("SBF_OUT_DIR").ok();
+    let dir = out_dir.as_deref().unwrap_or("target/deploy");
+    let path = std::path::Path::new(dir).join("blueshift_escrow.so");
+    match std::fs::read(&path) {
+        Ok(elf) => Some(elf),
+        Err(_) if out_dir.is_none() => None,
+        Err(err) => panic!("{}: {err}", path.display()),
+    }
+}
+
+#[test]
+fn program_binary_has_no_panic_paths() {
+    let Some(elf) = program_binary() else {
+        return;
+    };
+    for needle in [
+        "index out of bounds",
+        "range end index",
+        "range start index",
+        "slice index starts",
+        "called `Option::unwrap()`",
+        "called `Result::unwrap()`",
+        "attempt to add with overflow",
+    ] {
+        assert!(
+            !elf.windows(needle.len())
+                .any(|window| window == needle.as_bytes()),
+            "{needle}"
+        );
+    }
+}
+
+#[test]
+fn program_binary_never_allocates() {
+    let Some(elf) = program_binary() else {
+        return;
+    };
+    for needle in ["__rust_alloc", "__rg_alloc", "NoAllocator::alloc()"] {
+        assert!(
+            !elf.windows(needle.len())
+                .any(|window| window == needle.as_bytes()),
+            "{needle}"
+        );
+    }
+}
+
+#[test]
+fn escrow_terms_hash() {
+    let mut escrow = Escrow::zeroed();
+    escrow.mint_a = Address::new_from_array([1; 32]);
+    escrow.mint_b = Address::new_from_array([2; 32]);
+    escrow.receive = 3;
+    escrow.amount = 4;
+    assert_eq!(
+        escrow.terms_hash().to_vec(),
+        hex("cfbc5bdf56f93a35e1a9c43b5546bcd32084e32ec4a19ca51b5b99e3a529f3a8")
+    );
+    escrow.receive = 5;
+    assert_ne!(
+        escrow.terms_hash().to_vec(),
+        hex("cfbc5bdf56f93a35e1a9c43b5546bcd32084e32ec4a19ca51b5b99e3a529f3a8")
+    );
+}
@@ -0,0 +1,553 @@
+// Runs against blueshift_escrow.so from SBF_OUT_DIR or target/deploy
+// (cargo build-sbf) and needs spl_token.so / spl_associated_token_account.so
+// in tests/fixtures. Skipped when the program is not built and SBF_OUT_DIR
+// is unset.
+
+use std::collections::HashMap;
+
+use blueshift_escrow::state::Config;
+use bytemuck::Zeroable;
+use mollusk_svm::{
+    Mollusk,
+    program::{create_program_account_loader_v3, keyed_account_for_system_program, loader_keys},
+    result::ProgramResult,
+};
+use pinocchio::error::ProgramError;
+use solana_account::Account;
+use solana_address::Address;
+use solana_instruction::{AccountMeta, Instruction};
+
+const SEED: u64 = 42;
+const AMOUNT: u64 = 1_000_000;
+const RECEIVE: u64 = 500_000;
+
+fn program_binary() -> Option<Vec<u8>> {
+    let out_dir = std::env::var("SBF_OUT_DIR").ok();
+    let dir = out_dir.as_deref().unwrap_or("target/deploy");
+    let path = std::path::Path::new(dir).join("blueshift_escrow.so");
+    match std::fs::read(&path) {
+        Ok(elf) => Some(elf),
+        Err(_) if out_dir.is_none() => None,
+        Err(err) => panic!("{}: {err}", path.display()),
+    }
+}
+
+struct Harness {
+    mollusk: Mollusk,
+    store: HashMap<Address, Account>,
+    maker: Address,
+    taker: Address,
+    approver: Address,
+    escrow: Address,
+    mint_a: Address,
+    mint_b: Address,
+    mint_c: Address,
+    config: Address,
+}
+
+impl Harness {
+    fn new() -> Option<Self> {
+        let mut mollusk = Mollusk::default();
+        mollusk.add_program_with_elf_and_loader(
+            &blueshift_escrow::ID,
+            &program_binary()?,
+            &loader_keys::LOADER_V3,
+        );
+        mollusk.add_program(&pinocchio_token::ID, "spl_token", &loader_keys::LOADER_V3);
+        mollusk.add_program(
+            &pinocchio_associated_token_account::ID,
+            "spl_associated_token_account",
+            &loader_keys::LOADER_V3,
+        );
+
+        let maker = Address::new_from_array([1; 32]);
+        let taker = Address::new_from_array([2; 32]);
+        let mint_a = Address::new_from_array([3; 32]);
+        let mint_b = Address::new_from_array([4; 32]);
+        let mint_c = Address::new_from_array([5; 32]);
+        let approver = Address::new_from_array([6; 32]);
+        let (escrow, _) = Address::find_program_address(
+            &[b"escrow", maker.as_ref(), &SEED.to_le_bytes()],
+            &blueshift_escrow::ID,
+        );
+        let (config, config_bump) =
+            Address::find_program_address(&[b"config"], &blueshift_escrow::ID);
+
+        let mut harness = Self {
+            mollusk,
+            store: HashMap::new(),
+            maker,
+            taker,
+            approver,
+            escrow,
+            mint_a,
+            mint_b,
+            mint_c,
+            config,
+        };
+
+        let (system_program, system_account) = keyed_account_for_system_program();
+        harness.store.insert(system_program, system_account);
+        for program in [pinocchio_token::ID, pinocchio_associated_token_account::ID] {
+            harness
+                .store
+                .insert(program, create_program_account_loader_v3(&program));
+        }
+        for wallet in [maker, taker, approver] {
+            harness.store.insert(
+                wallet,
+                Account::new(10_000_000_000, 0, &pinocchio_system::ID),
+            );
+        }
+        for mint in [mint_a, mint_b, mint_c] {
+            harness.insert_mint(mint);
+        }
+        harness.insert_token_account(harness.ata(&maker, &mint_a), mint_a, maker, AMOUNT);
+        harness.insert_token_account(harness.ata(&taker, &mint_b), mint_b, taker, RECEIVE);
+        harness.insert_token_account(harness.ata(&taker, &mint_c), mint_c, taker, RECEIVE);
+
+        let mut state = Config::zeroed();
+        state.set_inner(maker, maker, [config_bump]);
+        let mut account = harness.rent_exempt_account(Config::LEN, blueshift_escrow::ID);
+        account.data.copy_from_slice(bytemuck::bytes_of(&state));
+        harness.store.insert(config, account);
+        Some(harness)
+    }
+
+    fn ata(&self, owner: &Address, mint: &Address) -> Address {
+        Address::find_program_address(
+            &[owner.as_ref(), pinocchio_token::ID.as_ref(), mint.as_ref()],
+            &pinocchio_associated_token_account::ID,
+        )
+        .0
+    }
+
+    fn vault(&self) -> Address {
+        self.ata(&self.escrow, &self.mint_a)
+    }
+
+    #[cfg(feature = "dispute")]
+    fn approvals(&self) -> Address {
+        Address::find_program_address(
+            &[
+                blueshift_escrow::state::ReleaseApprovals::PREFIX,
+                self.escrow.as_ref(),
+            ],
+            &blueshift_escrow::ID,
+        )
+        .0
+    }
+
+    fn counter(&self) -> Address {
+        Address::find_program_address(
+            &[b"counter", self.escrow.as_ref(), self.taker.as_ref()],
+            &blueshift_escrow::ID,
+        )
+        .0
+    }
+
+    fn rent_exempt_account(&self, len: usize, owner: Address) -> Account {
+        Account::new(self.mollusk.sysvars.rent.minimum_balance(len), len, &owner)
+    }
+
+    fn insert_mint(&mut self, mint: Address) {
+        let mut account = self.rent_exempt_account(82, pinocchio_token::ID);
+        account.data[0..4].copy_from_slice(&1u32.to_le_bytes());
+        account.data[4..36].copy_from_slice(self.maker.as_ref());
+        account.data[36..44].copy_from_slice(&u64::MAX.to_le_bytes());
+        account.data[44] = 6;
+        account.data[45] = 1;
+        self.store.insert(mint, account);
+    }
+
+    fn insert_token_account(
+        &mut self,
+        address: Address,
+        mint: Address,
+        owner: Address,
+        amount: u64,
+    ) {
+        let mut account = self.rent_exempt_account(165, pinocchio_token::ID);
+        account.data[0..32].copy_from_slice(mint.as_ref());
+        account.data[32..64].copy_from_slice(owner.as_ref());
+        account.data[64..72].copy_from_slice(&amount.to_le_bytes());
+        account.data[108] = 1;
+        self.store.insert(address, account);
+    }
+
+    fn lamports(&self, address: &Address) -> u64 {
+        self.store
+            .get(address)
+            .map_or(0, |account| account.lamports)
+    }
+
+    fn amount(&self, address: &Address) -> u64 {
+        let data = &self.store[address].data;
+        u64::from_le_bytes(data[64..72].try_into().unwrap())
+    }
+
+    fn process(&mut self, accounts: Vec<AccountMeta>, data: Vec<u8>) -> ProgramResult {
+        let keyed_accounts: Vec<(Address, Account)> = accounts
+            .iter()
+            .map(|meta| {
+                let account = self.store.get(&meta.pubkey).cloned().unwrap_or_default();
+                (meta.pubkey, account)
+            })
+            .collect();
+        let result = self.mollusk.process_instruction(
+            &Instruction {
+                program_id: blueshift_escrow::ID,
+                accounts,
+                data,
+            },
+            &keyed_accounts,
+        );
+        if result.program_result.is_ok() {
+            self.store.extend(result.resulting_accounts.iter().cloned());
+        }
+        result.program_result
+    }
+
+    fn make(&mut self) {
+        let mut data = vec![
+            *blueshift_escrow::Make::DISCRIMINATOR,
+            blueshift_escrow::MakeInstructionData::VERSION,
+        ];
+        data.extend_from_slice(&SEED.to_le_bytes());
+        data.extend_from_slice(&RECEIVE.to_le_bytes());
+        data.extend_from_slice(&AMOUNT.to_le_bytes());
+        data.resize(1 + blueshift_escrow::MakeInstructionData::LEN, 0);
+        let accounts = vec![
+            AccountMeta::new(self.maker, true),
+            AccountMeta::new(self.escrow, false),
+            AccountMeta::new_readonly(self.mint_a, false),
+            AccountMeta::new_readonly(self.mint_b, false),
+            AccountMeta::new(self.ata(&self.maker, &self.mint_a), false),
+            AccountMeta::new(self.vault(), false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new_readonly(pinocchio_token::ID, false),
+            AccountMeta::new(self.config, false),
+            AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+        ];
+        assert_eq!(self.process(accounts, data), ProgramResult::Success);
+        assert_eq!(self.amount(&self.vault()), AMOUNT);
+    }
+
+    fn take(&mut self, mint_b: Address, remaining: &[AccountMeta]) -> ProgramResult {
+        let mut accounts = vec![
+            AccountMeta::new(self.taker, true),
+            AccountMeta::new(self.maker, false),
+            AccountMeta::new(self.escrow, false),
+            AccountMeta::new_readonly(self.mint_a, false),
+            AccountMeta::new_readonly(mint_b, false),
+            AccountMeta::new(self.vault(), false),
+            AccountMeta::new(self.ata(&self.taker, &self.mint_a), false),
+            AccountMeta::new(self.ata(&self.taker, &mint_b), false),
+            AccountMeta::new(self.ata(&self.maker, &mint_b), false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new_readonly(pinocchio_token::ID, false),
+            AccountMeta::new(self.config, false),
+            AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+        ];
+        accounts.extend_from_slice(remaining);
+        self.process(accounts, vec![*blueshift_escrow::Take::DISCRIMINATOR, 0])
+    }
+
+    fn take_many(&mut self, mint_b: Address) -> ProgramResult {
+        let mut data = vec![*blueshift_escrow::TakeMany::DISCRIMINATOR];
+        data.extend_from_slice(&u64::MAX.to_le_bytes());
+        data.push(4);
+        let accounts = vec![
+            AccountMeta::new(self.taker, true),
+            AccountMeta::new_readonly(self.mint_a, false),
+            AccountMeta::new_readonly(mint_b, false),
+            AccountMeta::new(self.ata(&self.taker, &self.mint_a), false),
+            AccountMeta::new(self.ata(&self.taker, &mint_b), false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new_readonly(pinocchio_token::ID, false),
+            AccountMeta::new(self.config, false),
+            AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+            AccountMeta::new(self.maker, false),
+            AccountMeta::new(self.escrow, false),
+            AccountMeta::new(self.vault(), false),
+            AccountMeta::new(self.ata(&self.maker, &mint_b), false),
+        ];
+        self.process(accounts, data)
+    }
+
+    fn take_signed(&mut self, mint_b: Address) -> ProgramResult {
+        let (delegate, _) = Address::find_program_address(
+            &[blueshift_escrow::TakeSigned::DELEGATE_SEED],
+            &blueshift_escrow::ID,
+        );
+        let accounts = vec![
+            AccountMeta::new(self.approver, true),
+            AccountMeta::new(self.taker, false),
+            AccountMeta::new(self.maker, false),
+            AccountMeta::new(self.escrow, false),
+            AccountMeta::new_readonly(self.mint_a, false),
+            AccountMeta::new_readonly(mint_b, false),
+            AccountMeta::new(self.vault(), false),
+            AccountMeta::new(self.ata(&self.taker, &self.mint_a), false),
+            AccountMeta::new(self.ata(&self.taker, &mint_b), false),
+            AccountMeta::new(self.ata(&self.maker, &mint_b), false),
+            AccountMeta::new_readonly(delegate, false),
+            AccountMeta::new_readonly(pinocchio::sysvars::instructions::INSTRUCTIONS_ID, false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new_readonly(pinocchio_token::ID, false),
+            AccountMeta::new(self.config, false),
+            AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+        ];
+        self.process(accounts, vec![*blueshift_escrow::TakeSigned::DISCRIMINATOR])
+    }
+
+    fn refund(&mut self, remaining: &[AccountMeta]) -> ProgramResult {
+        let mut accounts = vec![
+            AccountMeta::new(self.maker, true),
+            AccountMeta::new(self.escrow, false),
+            AccountMeta::new_readonly(self.mint_a, false),
+            AccountMeta::new(self.vault(), false),
+            AccountMeta::new(self.ata(&self.maker, &self.mint_a), false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new_readonly(pinocchio_token::ID, false),
+            AccountMeta::new(self.config, false),
+            AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+        ];
+        accounts.extend_from_slice(remaining);
+        self.process(accounts, vec![*blueshift_escrow::Refund::DISCRIMINATOR])
+    }
+
+    #[cfg(feature = "dispute")]
+    fn set_release_signers(&mut self) {
+        let mut data = vec![*blueshift_escrow::SetReleaseSigners::DISCRIMINATOR, 1];
+        data.extend_from_slice(self.approver.as_ref());
+        let accounts = vec![
+            AccountMeta::new(self.maker, true),
+            AccountMeta::new(self.escrow, false),
+            AccountMeta::new(self.approvals(), false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new(self.config, false),
+        ];
+        assert_eq!(self.process(accounts, data), ProgramResult::Success);
+    }
+
+    #[cfg(feature = "dispute")]
+    fn approve_release(&mut self) {
+        let accounts = vec![
+            AccountMeta::new_readonly(self.approver, true),
+            AccountMeta::new_readonly(self.escrow, false),
+            AccountMeta::new(self.approvals(), false),
+        ];
+        let data = vec![*blueshift_escrow::ApproveRelease::DISCRIMINATOR];
+        assert_eq!(self.process(accounts, data), ProgramResult::Success);
+    }
+
+    fn counter_offer(&mut self, receive: u64) {
+        let mut data = vec![*blueshift_escrow::Counter::DISCRIMINATOR];
+        data.extend_from_slice(&receive.to_le_bytes());
+        let accounts = vec![
+            AccountMeta::new(self.taker, true),
+            AccountMeta::new_readonly(self.escrow, false),
+            AccountMeta::new_readonly(self.mint_b, false),
+            AccountMeta::new(self.ata(&self.taker, &self.mint_b), false),
+            AccountMeta::new(self.counter(), false),
+            AccountMeta::new(self.ata(&self.counter(), &self.mint_b), false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new_readonly(pinocchio_token::ID, false),
+            AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+        ];
+        assert_eq!(self.process(accounts, data), ProgramResult::Success);
+    }
+
+    fn accept_counter(&mut self) -> ProgramResult {
+        let accounts = vec![
+            AccountMeta::new(self.maker, true),
+            AccountMeta::new(self.escrow, false),
+            AccountMeta::new_readonly(self.mint_a, false),
+            AccountMeta::new_readonly(self.mint_b, false),
+            AccountMeta::new(self.vault(), false),
+            AccountMeta::new(self.taker, false),
+            AccountMeta::new(self.counter(), false),
+            AccountMeta::new(self.ata(&self.counter(), &self.mint_b), false),
+            AccountMeta::new(self.ata(&self.taker, &self.mint_a), false),
+            AccountMeta::new(self.ata(&self.maker, &self.mint_b), false),
+            AccountMeta::new_readonly(pinocchio_system::ID, false),
+            AccountMeta::new_readonly(pinocchio_token::ID, false),
+            AccountMeta::new(self.config, false),
+            AccountMeta::new_readonly(pinocchio_associated_token_account::ID, false),
+        ];
+        self.process(
+            accounts,
+            vec![*blueshift_escrow::AcceptCounter::DISCRIMINATOR],
+        )
+    }
+}
+
+#[test]
+fn take_settles_and_closes_escrow() {
+    let Some(mut harness) = Harness::new() else {
+        return;
+    };
+    harness.make();
+    assert_eq!(harness.take(harness.mint_b, &[]), ProgramResult::Success);
+    assert_eq!(
+        harness.amount(&harness.ata(&harness.taker, &harness.mint_a)),
+        AMOUNT
+    );
+    assert_eq!(
+        harness.amount(&harness.ata(&harness.maker, &harness.mint_b)),
+        RECEIVE
+    );
+    assert_eq!(harness.lamports(&harness.escrow), 0);
+}
+
+#[test]
+fn take_rejects_mint_not_bound_to_escrow() {
+    let Some(mut harness) = Harness::new() else {
+        return;
+    };
+    harness.make();
+    assert_eq!(
+        harness.take(harness.mint_c, &[]),
+        ProgramResult::Failure(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(harness.amount(&harness.vault()), AMOUNT);
+}
+
+#[test]
+fn take_many_settles_group() {
+    let Some(mut harness) = Harness::new() else {
+        return;
+    };
+    harness.make();
+    assert_eq!(harness.take_many(harness.mint_b), ProgramResult::Success);
+    assert_eq!(
+        harness.amount(&harness.ata(&harness.maker, &harness.mint_b)),
+        RECEIVE
+    );
+    assert_eq!(harness.lamports(&harness.escrow), 0);
+}
+
+#[test]
+fn take_many_rejects_group_with_foreign_mints() {
+    let Some(mut harness) = Harness::new() else {
+        return;
+    };
+    harness.make();
+    assert_eq!(
+        harness.take_many(harness.mint_c),
+        ProgramResult::Failure(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(harness.amount(&harness.vault()), AMOUNT);
+}
+
+#[test]
+fn take_signed_rejects_mint_not_bound_to_escrow() {
+    let Some(mut harness) = Harness::new() else {
+        return;
+    };
+    harness.make();
+    assert_eq!(
+        harness.take_signed(harness.mint_c),
+        ProgramResult::Failure(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(harness.amount(&harness.vault()), AMOUNT);
+}
+
+#[test]
+fn refund_returns_vault_and_closes_escrow() {
+    let Some(mut harness) = Harness::new() else {
+        return;
+    };
+    harness.make();
+    assert_eq!(harness.refund(&[]), ProgramResult::Success);
+    assert_eq!(
+        harness.amount(&harness.ata(&harness.maker, &harness.mint_a)),
+        AMOUNT
+    );
+    assert_eq!(harness.lamports(&harness.escrow), 0);
+}
+
+#[cfg(feature = "dispute")]
+#[test]
+fn release_gated_take_requires_approval() {
+    let Some(mut harness) = Harness::new() else {
+        return;
+    };
+    harness.make();
+    harness.set_release_signers();
+    let approvals = [AccountMeta::new(harness.approvals(), false)];
+    assert_eq!(
+        harness.take(harness.mint_b, &[]),
+        ProgramResult::Failure(ProgramError::NotEnoughAccountKeys)
+    );
+    assert_eq!(
+        harness.take(harness.mint_b, &approvals),
+        ProgramResult::Failure(blueshift_escrow::errors::EscrowError::ReleaseNotApproved.into())
+    );
+    harness.approve_release();
+    assert_eq!(
+        harness.take(harness.mint_b, &approvals),
+        ProgramResult::Success
+    );
+    assert_eq!(harness.lamports(&harness.escrow), 0);
+    assert_eq!(harness.lamports(&harness.approvals()), 0);
+}
+
+#[cfg(feature = "dispute")]
+#[test]
+fn refund_closes_release_approvals() {
+    let Some(mut harness) = Harness::new() else {
+        return;
+    };
+    harness.make();
+    harness.set_release_signers();
+    let rent = harness.lamports(&harness.approvals());
+    let before = harness.lamports(&harness.maker);
+    assert_eq!(
+        harness.refund(&[]),
+        ProgramResult::Failure(ProgramError::NotEnoughAccountKeys)
+    );
+    assert_eq!(
+        harness.refund(&[AccountMeta::new(harness.approvals(), false)]),
+        ProgramResult::Success
+    );
+    assert_eq!(harness.lamports(&harness.approvals()), 0);
+    assert!(harness.lamports(&harness.maker) >= before + rent);
+}
+
+#[test]
+fn accept_counter_settles_counter_offer() {
+    let Some(mut harness) = Harness::new() else {
+        return;
+    };
+    harness.make();
+    harness.counter_offer(RECEIVE / 2);
+    assert_eq!(harness.accept_counter(), ProgramResult::Success);
+    assert_eq!(
+        harness.amount(&harness.ata(&harness.taker, &harness.mint_a)),
+        AMOUNT
+    );
+    assert_eq!(
+        harness.amount(&harness.ata(&harness.maker, &harness.mint_b)),
+        RECEIVE / 2
+    );
+    assert_eq!(harness.lamports(&harness.escrow), 0);
+    assert_eq!(harness.lamports(&harness.counter()), 0);
+}
+
+#[cfg(feature = "dispute")]
+#[test]
+fn accept_counter_rejects_release_gated_escrow() {
+    let Some(mut harness) = Harness::new() else {
+        return;
+    };
+    harness.make();
+    harness.set_release_signers();
+    harness.counter_offer(RECEIVE / 2);
+    assert_eq!(
+        harness.accept_counter(),
+        ProgramResult::Failure(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(harness.amount(&harness.vault()), AMOUNT);
+}